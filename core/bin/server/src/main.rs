@@ -6,7 +6,10 @@ use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
-use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
+use zksync_api::{
+    api_server::rest::{EnabledApis, PanicPolicy},
+    fee_ticker::{run_updaters, FeeTicker, TickerInfo},
+};
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_client::EthereumGateway;
 use zksync_forced_exit_requests::run_forced_exit_requests_actors;
@@ -271,6 +274,13 @@ async fn run_server(components: &ComponentsToRun) {
                 sign_check_sender,
                 mempool_tx_request_sender,
                 private_config.url,
+                "actix-rest-api".to_string(),
+                PanicPolicy::Notify,
+                EnabledApis {
+                    rpc: components.0.contains(&Component::RpcApi),
+                    websocket: components.0.contains(&Component::RpcWebSocketApi),
+                    web3: components.0.contains(&Component::Web3Api),
+                },
             ));
         }
     }
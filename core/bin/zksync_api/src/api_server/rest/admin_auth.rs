@@ -0,0 +1,46 @@
+//! Authentication for the REST API routes available to the node operators only.
+//!
+//! Requests to such routes must have the `Authorization: Bearer <token>` header
+//! with a JWT signed by the `api.admin.secret_auth` secret, issued for the
+//! `Authorization` subject, same as the tokens of the other internal APIs.
+
+use actix_web::dev::ServiceRequest;
+use actix_web_httpauth::extractors::{
+    bearer::{BearerAuth, Config},
+    AuthenticationError,
+};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+
+/// Subject the admin tokens must be issued for.
+const ADMIN_TOKEN_SUBJECT: &str = "Authorization";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Rejects the request with `401 Unauthorized` unless its token is signed by the provided secret.
+pub fn validate_admin_token(
+    secret: &str,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let config = req.app_data::<Config>().cloned().unwrap_or_default();
+
+    let validation = Validation {
+        sub: Some(ADMIN_TOKEN_SUBJECT.to_string()),
+        ..Validation::default()
+    };
+    decode::<PayloadAuthToken>(
+        credentials.token(),
+        &DecodingKey::from_secret(secret.as_ref()),
+        &validation,
+    )
+    .map_err(|_| AuthenticationError::from(config))?;
+
+    Ok(req)
+}
@@ -0,0 +1,78 @@
+//! Cache policies for the REST API routes.
+//!
+//! Routes listed in the `api.rest.cache_control` config option respond with the
+//! `Cache-Control` header, so that CDNs and browsers are able to cache the data
+//! which doesn't change often. Handlers may set the header on their own (e.g. for the
+//! data known to be immutable), in that case the configured policy is not applied.
+
+use std::{collections::HashMap, sync::Arc};
+
+use actix_web::{
+    dev::{ServiceRequest, ServiceResponse},
+    http::{header, HeaderValue},
+};
+use zksync_config::configs::api::RestApiConfig;
+
+/// Policy for the responses that are never going to change, e.g. verified blocks.
+pub const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// Policy for the token list, which only changes when new tokens are added via governance.
+pub const TOKEN_LIST: &str = "public, max-age=60, stale-while-revalidate=120";
+
+/// `Cache-Control` header values for the REST API routes.
+#[derive(Debug, Clone)]
+pub struct CachePolicies {
+    routes: Arc<HashMap<String, HeaderValue>>,
+}
+
+impl CachePolicies {
+    pub fn new(config: &RestApiConfig) -> Self {
+        let routes = config
+            .cache_control
+            .iter()
+            .map(|entry| {
+                let (pattern, max_age) = entry
+                    .rsplit_once('=')
+                    .expect("Cache policy should be in the `<route>=<max age>` format");
+                let max_age: u64 = max_age
+                    .trim()
+                    .parse()
+                    .expect("Invalid cache policy max age");
+                (pattern.trim().to_string(), Self::header_value(max_age))
+            })
+            .collect();
+
+        Self {
+            routes: Arc::new(routes),
+        }
+    }
+
+    fn header_value(max_age: u64) -> HeaderValue {
+        if max_age == 0 {
+            HeaderValue::from_static("no-store")
+        } else {
+            HeaderValue::from_str(&format!("public, max-age={}", max_age))
+                .expect("Cache policy header is always valid")
+        }
+    }
+
+    /// Returns the `Cache-Control` header value for the route handling the request.
+    pub fn policy(&self, req: &ServiceRequest) -> Option<HeaderValue> {
+        if self.routes.is_empty() {
+            return None;
+        }
+        req.match_pattern()
+            .and_then(|pattern| self.routes.get(&pattern).cloned())
+    }
+
+    /// Attaches the `Cache-Control` header to the successful response
+    /// unless the handler has already set it.
+    pub fn add_header<B>(policy: HeaderValue, response: &mut ServiceResponse<B>) {
+        if !response.status().is_success() {
+            return;
+        }
+        let headers = response.headers_mut();
+        if !headers.contains_key(header::CACHE_CONTROL) {
+            headers.insert(header::CACHE_CONTROL, policy);
+        }
+    }
+}
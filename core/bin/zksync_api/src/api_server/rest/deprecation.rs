@@ -0,0 +1,62 @@
+//! Deprecation notices for the REST API routes.
+//!
+//! Routes listed in the `api.rest.deprecated_routes` config option keep working as usual,
+//! but their responses are extended with the `Deprecation` and `Warning` headers
+//! (and the `Sunset` one if the removal date is configured), so that clients
+//! get notified in advance that they should migrate.
+
+use std::{collections::HashSet, sync::Arc};
+
+use actix_web::{
+    dev::{ServiceRequest, ServiceResponse},
+    http::{header, HeaderName, HeaderValue},
+};
+use zksync_config::configs::api::RestApiConfig;
+
+const DEPRECATION_WARNING: &str =
+    "299 - \"This endpoint is deprecated and is going to be removed, please migrate\"";
+
+/// Set of the REST API routes marked as deprecated in the config.
+#[derive(Debug, Clone)]
+pub struct DeprecatedRoutes {
+    routes: Arc<HashSet<String>>,
+    sunset: Option<HeaderValue>,
+}
+
+impl DeprecatedRoutes {
+    pub fn new(config: &RestApiConfig) -> Self {
+        let sunset = config.deprecated_routes_sunset.as_ref().map(|sunset| {
+            HeaderValue::from_str(sunset).expect("Invalid deprecated routes sunset date")
+        });
+
+        Self {
+            routes: Arc::new(config.deprecated_routes.iter().cloned().collect()),
+            sunset,
+        }
+    }
+
+    /// Checks whether the request is going to be handled by a deprecated route.
+    pub fn is_deprecated(&self, req: &ServiceRequest) -> bool {
+        !self.routes.is_empty()
+            && req
+                .match_pattern()
+                .map(|pattern| self.routes.contains(&pattern))
+                .unwrap_or(false)
+    }
+
+    /// Attaches the deprecation headers to the response.
+    pub fn add_headers<B>(&self, response: &mut ServiceResponse<B>) {
+        let headers = response.headers_mut();
+        headers.insert(
+            HeaderName::from_static("deprecation"),
+            HeaderValue::from_static("true"),
+        );
+        headers.insert(
+            header::WARNING,
+            HeaderValue::from_static(DEPRECATION_WARNING),
+        );
+        if let Some(sunset) = &self.sunset {
+            headers.insert(HeaderName::from_static("sunset"), sunset.clone());
+        }
+    }
+}
@@ -8,7 +8,7 @@ use reqwest::{header::CONTENT_TYPE, StatusCode};
 // Workspace uses
 use zksync_api_client::rest::error::ErrorBody;
 // Local uses
-use crate::api_server::tx_sender::SubmitError;
+use crate::api_server::{rest::json_response::JSON_CONTENT_TYPE, tx_sender::SubmitError};
 
 /// An HTTP error structure.
 #[derive(Debug)]
@@ -69,7 +69,7 @@ impl ResponseError for ApiError {
         match serde_json::to_vec_pretty(&self.body) {
             Ok(body) => {
                 resp.headers_mut()
-                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                    .insert(CONTENT_TYPE, HeaderValue::from_static(JSON_CONTENT_TYPE));
                 resp.set_body(Body::from_slice(&body))
             }
 
@@ -86,6 +86,7 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    TokenSuspended = 107,
 
     Internal = 110,
     CommunicationCoreServer = 111,
@@ -101,6 +102,7 @@ impl SumbitErrorCode {
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::TokenSuspended(_) => Self::TokenSuspended,
             SubmitError::MempoolCommunication(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
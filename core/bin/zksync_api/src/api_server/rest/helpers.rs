@@ -1,7 +1,6 @@
 //! Utilities for the REST API.
 
-use actix_web::error::InternalError;
-use actix_web::{HttpResponse, Result as ActixResult};
+use crate::api_server::rest::v01::error::ApiError;
 use std::collections::HashMap;
 use zksync_storage::chain::{
     block::records::StorageBlockDetails,
@@ -128,13 +127,17 @@ pub fn priority_op_to_tx_history(
         verified: false,
         created_at: current_time,
         batch_id: None,
+        batch_hash: None,
+        eth_signature: None,
+        is_priority_op: true,
+        usd_value_at_time: None,
     }
 }
 
 pub async fn parse_tx_id(
     data: &str,
     storage: &mut StorageProcessor<'_>,
-) -> ActixResult<(u64, u64)> {
+) -> Result<(u64, u64), ApiError> {
     if data.is_empty() || data == "-" {
         let last_block_id = storage
             .chain()
@@ -143,7 +146,7 @@ pub async fn parse_tx_id(
             .await
             .map_err(|err| {
                 vlog::warn!("Internal Server Error: '{}'; input: ({})", err, data,);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         let next_block_id = last_block_id + 1;
@@ -153,12 +156,12 @@ pub async fn parse_tx_id(
 
     let parts: Vec<u64> = data
         .split(',')
-        .map(|val| val.parse().map_err(actix_web::error::ErrorBadRequest))
-        .collect::<Result<Vec<u64>, actix_web::error::Error>>()?;
+        .map(|val| val.parse().map_err(ApiError::invalid_parameter))
+        .collect::<Result<Vec<u64>, _>>()?;
     if parts.len() != 2 {
-        return Err(actix_web::error::ErrorBadRequest(anyhow::anyhow!(
-            "Wrong amount of parameters. There must be two parameters: block and transaction ID"
-        )));
+        return Err(ApiError::invalid_parameter(
+            "Wrong amount of parameters. There must be two parameters: block and transaction ID",
+        ));
     }
 
     Ok((parts[0], parts[1]))
@@ -0,0 +1,42 @@
+//! JSON responses of the REST API.
+//!
+//! `HttpResponseBuilder::json` sets the `Content-Type: application/json` header without
+//! the charset parameter, which is required by some strict clients and gateways. Handlers
+//! build JSON responses via `JsonResponseBuilder::json_utf8` instead, while the responses
+//! produced by the `web::Json` responders are fixed up by `add_charset`.
+
+use actix_web::{
+    dev::ServiceResponse,
+    http::{header, HeaderValue},
+    HttpResponse, HttpResponseBuilder,
+};
+use serde::Serialize;
+
+/// `Content-Type` header value of all the JSON responses.
+pub const JSON_CONTENT_TYPE: &str = "application/json; charset=utf-8";
+
+pub trait JsonResponseBuilder {
+    /// Serializes the value into the response body with the explicit UTF-8 charset.
+    fn json_utf8(&mut self, value: impl Serialize) -> HttpResponse;
+}
+
+impl JsonResponseBuilder for HttpResponseBuilder {
+    fn json_utf8(&mut self, value: impl Serialize) -> HttpResponse {
+        // `json` keeps the content type if it's already set.
+        self.content_type(JSON_CONTENT_TYPE).json(value)
+    }
+}
+
+/// Adds the charset to the `Content-Type` header of the JSON response if it's missing.
+pub fn add_charset<B>(response: &mut ServiceResponse<B>) {
+    let headers = response.headers_mut();
+    let is_plain_json = headers
+        .get(header::CONTENT_TYPE)
+        .map_or(false, |value| value == "application/json");
+    if is_plain_json {
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(JSON_CONTENT_TYPE),
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Conditional responses based on the verified block height.
+//!
+//! Read endpoints accept the optional `min_verified_block` query parameter. If the last
+//! verified block known to the server is below the requested one, the request is not
+//! handled, and `409 Conflict` with the current verified height in the error message is
+//! returned instead.
+//! This way clients never act on the data which is less fresh than a block they already
+//! know to be verified.
+//!
+//! Note that the verified height is taken from the network status, which is updated
+//! periodically, so a recently verified block may be reported as not verified yet.
+//! Until the first update the height is unknown, and `503 Service Unavailable` is returned.
+
+use actix_web::{dev::ServiceRequest, http::Method, web, Error};
+use serde::Deserialize;
+
+use super::{network_status::SharedNetworkStatus, v01::error::ApiError};
+
+#[derive(Debug, Deserialize)]
+struct MinVerifiedBlockQuery {
+    min_verified_block: Option<u32>,
+}
+
+/// Rejects the request if the block required by the client is not verified yet.
+pub fn check_min_verified_block(
+    req: &ServiceRequest,
+    network_status: &SharedNetworkStatus,
+) -> Result<(), Error> {
+    if req.method() != Method::GET {
+        return Ok(());
+    }
+
+    let query = web::Query::<MinVerifiedBlockQuery>::from_query(req.query_string())?;
+    if let Some(min_verified_block) = query.min_verified_block {
+        // Verified height is unknown until the first status update, so the request
+        // can be neither served nor rejected as not verified yet.
+        if !network_status.is_loaded() {
+            return Err(ApiError::unavailable("Network status is not loaded yet").into());
+        }
+        let last_verified_block = network_status.last_verified_block();
+        if *last_verified_block < min_verified_block {
+            return Err(ApiError::conflict(format!(
+                "Block {} is not verified yet, the last verified block is {}",
+                min_verified_block, *last_verified_block
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,17 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{dev::Service, web, App, HttpResponse, HttpServer};
 use futures::channel::mpsc;
 use std::net::SocketAddr;
+use std::time::Duration;
 use zksync_storage::ConnectionPool;
-use zksync_types::{SequentialTxId, H160};
+use zksync_types::{SequentialTxId, TokenLike, TokenRiskLevel, H160};
 
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
-use self::v01::api_decl::ApiV01;
+use self::{
+    cache_control::CachePolicies, deprecation::DeprecatedRoutes, json_response::add_charset,
+    min_verified_block::check_min_verified_block, rate_limit::RateLimiter, v01::api_decl::ApiV01,
+};
 use crate::signature_checker::VerifySignatureRequest;
 
 use super::tx_sender::TxSender;
@@ -15,22 +19,27 @@ use super::tx_sender::TxSender;
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
 use tokio::task::JoinHandle;
-use zksync_config::ZkSyncConfig;
+use zksync_config::{configs::api::TokenConfig, ZkSyncConfig};
 use zksync_mempool::MempoolTransactionRequest;
 
+mod admin_auth;
+mod cache_control;
+mod deprecation;
 mod forced_exit_requests;
 mod helpers;
+mod json_response;
+mod min_verified_block;
 pub mod network_status;
+mod rate_limit;
 mod v01;
 pub mod v02;
 
-async fn start_server(
-    api_v01: ApiV01,
-    fee_ticker: FeeTicker,
-    sign_verifier: mpsc::Sender<VerifySignatureRequest>,
-    bind_to: SocketAddr,
-    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
-) {
+/// Interval between the attempts to load the initial network status.
+const NETWORK_STATUS_WARM_UP_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+async fn start_server(api_v01: ApiV01, tx_sender: TxSender, bind_to: SocketAddr) {
+    // Limiter is shared between the workers, so that the limits apply to the whole server.
+    let rate_limiter = RateLimiter::new(&api_v01.config.api.rest);
     HttpServer::new(move || {
         let api_v01 = api_v01.clone();
         // This api stores forced exit requests, it's necessary to use main database connection
@@ -45,17 +54,15 @@ async fn start_server(
             api_v01.config.contracts.forced_exit_addr,
         );
 
-        let api_v02_scope = {
-            let tx_sender = TxSender::new(
-                api_v01.connection_pool.clone(),
-                sign_verifier.clone(),
-                fee_ticker.clone(),
-                &api_v01.config.api.common,
-                &api_v01.config.api.token_config,
-                mempool_tx_sender.clone(),
-            );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
-        };
+        let api_v02_scope = v02::api_scope(
+            tx_sender.clone(),
+            &api_v01.config,
+            api_v01.network_status.clone(),
+        );
+        let deprecated_routes = DeprecatedRoutes::new(&api_v01.config.api.rest);
+        let cache_policies = CachePolicies::new(&api_v01.config.api.rest);
+        let network_status = api_v01.network_status.clone();
+        let rate_limiter = rate_limiter.clone();
         App::new()
             .wrap(
                 Cors::default()
@@ -65,6 +72,27 @@ async fn start_server(
                     .allow_any_header()
                     .allow_any_method(),
             )
+            .wrap_fn(move |req, srv| {
+                let is_deprecated = deprecated_routes.is_deprecated(&req);
+                let deprecated_routes = deprecated_routes.clone();
+                let cache_policy = cache_policies.policy(&req);
+                let response = rate_limiter
+                    .check(&req)
+                    .and_then(|_| check_min_verified_block(&req, &network_status))
+                    .map(|_| srv.call(req));
+                async move {
+                    response?.await.map(|mut response| {
+                        if is_deprecated {
+                            deprecated_routes.add_headers(&mut response);
+                        }
+                        if let Some(cache_policy) = cache_policy {
+                            CachePolicies::add_header(cache_policy, &mut response);
+                        }
+                        add_charset(&mut response);
+                        response
+                    })
+                }
+            })
             .service(api_v01.into_scope())
             .service(forced_exit_requests_api_scope)
             .service(api_v02_scope)
@@ -85,6 +113,86 @@ async fn start_server(
     .expect("REST API server has crashed");
 }
 
+/// Updates the network status for the first time, retrying until the database is available,
+/// so that the server never starts with the default status reporting zero blocks.
+async fn warm_up_network_status(
+    network_status: &mut SharedNetworkStatus,
+    connection_pool: &ConnectionPool,
+) -> SequentialTxId {
+    loop {
+        match network_status
+            .update(connection_pool, SequentialTxId(0))
+            .await
+        {
+            Ok(last_tx_id) => return last_tx_id,
+            Err(err) => {
+                vlog::warn!("Can't load the initial network status: {}", err);
+                tokio::time::sleep(NETWORK_STATUS_WARM_UP_RETRY_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// Loads all the known tokens into the token cache used by the API handlers.
+async fn warm_up_token_cache(tx_sender: &mut TxSender, connection_pool: &ConnectionPool) {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .expect("Unable to access storage to warm up the token cache");
+    tx_sender.tokens.fill_token_cache(&mut storage).await;
+}
+
+/// Stores the token risk levels from the config into the database.
+/// Tokens from the config that are not registered in the network are skipped.
+async fn seed_token_risk_levels(config: &TokenConfig, connection_pool: &ConnectionPool) {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .expect("Unable to access storage to seed the token risk levels");
+
+    let mut risk_levels = Vec::with_capacity(config.risk_levels.len());
+    for entry in &config.risk_levels {
+        let (token, level) = entry
+            .rsplit_once('=')
+            .expect("Token risk level should be in the `<token>=<risk level>` format");
+        let level: TokenRiskLevel = level.trim().parse().expect("Invalid token risk level");
+
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::parse(token.trim()))
+            .await
+            .expect("Unable to load token to seed its risk level");
+        match token {
+            Some(token) => risk_levels.push((token.id, level)),
+            None => vlog::warn!("Unknown token in the risk levels config: '{}'", entry),
+        }
+    }
+
+    storage
+        .tokens_schema()
+        .store_token_risk_levels(&risk_levels)
+        .await
+        .expect("Unable to store the token risk levels");
+}
+
+/// Other APIs run by the same node, reported to the clients by the REST API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnabledApis {
+    pub rpc: bool,
+    pub websocket: bool,
+    pub web3: bool,
+}
+
+/// Behavior of the REST API server in case one of its threads panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Complete the task returned by `start_server_thread_detached`,
+    /// so that the caller decides how to handle the failure.
+    Notify,
+    /// Abort the whole process.
+    Abort,
+}
+
 /// Start HTTP REST API
 #[allow(clippy::too_many_arguments)]
 #[must_use]
@@ -97,11 +205,30 @@ pub fn start_server_thread_detached(
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     core_address: String,
+    thread_name: String,
+    panic_policy: PanicPolicy,
+    enabled_apis: EnabledApis,
 ) -> JoinHandle<()> {
     let (handler, panic_sender) = spawn_panic_handler();
+    let handler = match panic_policy {
+        PanicPolicy::Notify => handler,
+        PanicPolicy::Abort => {
+            let thread_name = thread_name.clone();
+            tokio::spawn(async move {
+                // Handler fails if the server threads exit without a panic.
+                if handler.await.is_ok() {
+                    vlog::error!(
+                        "REST API server thread '{}' panicked, aborting",
+                        thread_name
+                    );
+                    std::process::abort();
+                }
+            })
+        }
+    };
 
     std::thread::Builder::new()
-        .name("actix-rest-api".to_string())
+        .name(thread_name)
         .spawn(move || {
             actix_rt::System::new().block_on(async move {
                 let _panic_sentinel = ThreadPanicNotify(panic_sender.clone());
@@ -112,10 +239,22 @@ pub fn start_server_thread_detached(
                 // We want to update the network status, as soon as possible, otherwise we can catch the situation,
                 // when the node is started and receiving the request, but the status is still `null` and
                 // monitoring tools spawn the notification that our node is down, though it's just a default status
-                let last_tx_id = network_status
-                    .update(&read_only_connection_pool, SequentialTxId(0))
-                    .await
-                    .unwrap();
+                let last_tx_id =
+                    warm_up_network_status(&mut network_status, &read_only_connection_pool).await;
+
+                // Token cache is shared between all the server workers, so it's filled once
+                // before the server starts, letting the first requests after restart avoid the database.
+                let mut tx_sender = TxSender::new(
+                    read_only_connection_pool.clone(),
+                    sign_verifier,
+                    fee_ticker.clone(),
+                    &config.api.common,
+                    &config.api.token_config,
+                    mempool_tx_sender,
+                );
+                warm_up_token_cache(&mut tx_sender, &read_only_connection_pool).await;
+                seed_token_risk_levels(&config.api.token_config, &main_database_connection_pool)
+                    .await;
 
                 let api_v01 = ApiV01::new(
                     read_only_connection_pool,
@@ -123,18 +262,21 @@ pub fn start_server_thread_detached(
                     contract_address,
                     config,
                     network_status,
+                    fee_ticker,
+                    tx_sender.tokens.clone(),
+                    enabled_apis,
                 );
 
+                // Token lists are loaded here rather than by the updater, which runs
+                // concurrently with the server and thus can't warm the cache in time.
+                api_v01.refresh_token_lists().await;
+
                 api_v01.spawn_network_status_updater(panic_sender, last_tx_id);
+                api_v01.spawn_token_list_updater();
+                api_v01.spawn_exit_proof_generator();
+                api_v01.spawn_ws_notifier();
 
-                start_server(
-                    api_v01,
-                    fee_ticker,
-                    sign_verifier,
-                    listen_addr,
-                    mempool_tx_sender.clone(),
-                )
-                .await;
+                start_server(api_v01, tx_sender, listen_addr).await;
             });
         })
         .expect("Api server thread");
@@ -1,14 +1,20 @@
 use futures::channel::mpsc;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::watch;
 use tokio::{runtime::Runtime, time};
 use zksync_api_types::CoreStatus;
 use zksync_storage::ConnectionPool;
 use zksync_types::{BlockNumber, SequentialTxId};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+/// Amount of the latest proof jobs used to calculate the average proof time.
+const PROOF_TIME_JOBS_COUNT: u32 = 100;
+
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 pub struct NetworkStatus {
     pub next_block_at_max: Option<u64>,
@@ -16,13 +22,34 @@ pub struct NetworkStatus {
     pub last_verified: BlockNumber,
     pub total_transactions: u32,
     pub outstanding_txs: u32,
+    /// Amount of committed blocks awaiting for the proof generation.
+    pub pending_proofs: u32,
+    /// Amount of blocks being proven at the moment.
+    pub proving: u32,
+    /// Amount of verified blocks.
+    pub verified: u32,
     pub mempool_size: u32,
     pub core_status: Option<CoreStatus>,
+    /// Latest Ethereum block processed by the server, i.e. deposits and other priority operations
+    /// are known up to this block. `None` if the core server is unavailable or hasn't processed any block yet.
+    pub monitored_eth_block: Option<u64>,
+    /// Average time from adding a block to the prover queue up to storing its proof,
+    /// over the latest proven blocks. `None` if there are no proven blocks yet.
+    pub avg_proof_time_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SharedNetworkStatus {
-    status: Arc<RwLock<NetworkStatus>>,
+    /// Publishes the status updates to the subscribers, e.g. the WebSocket clients.
+    updates: Arc<watch::Sender<NetworkStatus>>,
+    /// Latest status. The receiver is kept here, so that publishing never fails
+    /// for the lack of subscribers.
+    status: watch::Receiver<NetworkStatus>,
+    /// Copy of the `last_verified` field that can be read without awaiting for the lock.
+    last_verified: Arc<AtomicU32>,
+    /// Whether the status has been updated at least once, the default status
+    /// reports zero blocks and must not be served as the actual one.
+    is_loaded: Arc<AtomicBool>,
     core_status_address: String,
     core_client: reqwest::Client,
 }
@@ -31,15 +58,34 @@ impl SharedNetworkStatus {
     pub fn new(core_address: String) -> Self {
         let core_client = reqwest::Client::new();
         let core_status_address = format!("{}/status", core_address);
+        let (updates, status) = watch::channel(NetworkStatus::default());
         Self {
-            status: Default::default(),
+            updates: Arc::new(updates),
+            status,
+            last_verified: Default::default(),
+            is_loaded: Default::default(),
             core_status_address,
             core_client,
         }
     }
 
     pub async fn read(&self) -> NetworkStatus {
-        (*self.status.as_ref().read().await).clone()
+        self.status.borrow().clone()
+    }
+
+    /// Returns the receiver notified on every status update.
+    pub fn subscribe(&self) -> watch::Receiver<NetworkStatus> {
+        self.status.clone()
+    }
+
+    /// Returns `true` once the status has been updated at least once.
+    pub fn is_loaded(&self) -> bool {
+        self.is_loaded.load(Ordering::Acquire)
+    }
+
+    /// Returns the last verified block as of the latest status update.
+    pub fn last_verified_block(&self) -> BlockNumber {
+        BlockNumber(self.last_verified.load(Ordering::Relaxed))
     }
 
     /// Get healthcheck status from core server.
@@ -66,19 +112,19 @@ impl SharedNetworkStatus {
             total_transactions, ..
         } = self.read().await;
 
+        // Unlike the other fields, block numbers are required: the status reporting
+        // zero blocks would be taken for the actual one, e.g. by `min_verified_block`.
         let last_verified = transaction
             .chain()
             .block_schema()
             .get_last_verified_confirmed_block()
-            .await
-            .unwrap_or(BlockNumber(0));
+            .await?;
 
         let last_committed = transaction
             .chain()
             .block_schema()
             .get_last_committed_block()
-            .await
-            .unwrap_or(BlockNumber(0));
+            .await?;
 
         let (total_new_transactions, last_tx_id) = transaction
             .chain()
@@ -101,21 +147,45 @@ impl SharedNetworkStatus {
             .await
             .unwrap_or(0);
 
+        let (pending_proofs, proving) = transaction
+            .chain()
+            .stats_schema()
+            .count_blocks_by_proof_status(last_verified)
+            .await
+            .unwrap_or((0, 0));
+
+        let avg_proof_time_ms = transaction
+            .prover_schema()
+            .load_average_proof_time(PROOF_TIME_JOBS_COUNT)
+            .await
+            .unwrap_or(None)
+            .map(|time| time.as_millis() as u64);
+
         transaction.commit().await.unwrap_or_default();
 
         let core_status = self.get_core_status().await.ok();
+        let monitored_eth_block = core_status
+            .as_ref()
+            .and_then(|status| status.monitored_eth_block);
         let status = NetworkStatus {
             next_block_at_max: None,
             last_committed,
             last_verified,
             total_transactions: total_transactions + total_new_transactions,
             outstanding_txs,
+            pending_proofs,
+            proving,
+            verified: *last_verified,
             mempool_size,
             core_status,
+            monitored_eth_block,
+            avg_proof_time_ms,
         };
 
         // save status to state
-        *self.status.as_ref().write().await = status;
+        self.last_verified.store(*last_verified, Ordering::Relaxed);
+        self.updates.send(status).ok();
+        self.is_loaded.store(true, Ordering::Release);
         Ok(last_tx_id)
     }
 
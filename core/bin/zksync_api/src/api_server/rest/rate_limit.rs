@@ -0,0 +1,318 @@
+//! Rate limiting for the REST API.
+//!
+//! Every client has a bucket of tokens, one token per request. Buckets are refilled
+//! continuously at the client's per-second rate and hold up to `api.rest.rate_limit_burst`
+//! tokens (the per-second limit if unset), so that the clients are able to make short bursts
+//! of requests. Clients are identified by the API key passed in the `X-API-Key` header if
+//! it's one of the keys listed in the `api.rest.api_keys` config option, and by the IP address
+//! otherwise. Authenticated clients are limited by `api.rest.api_key_rate_limit_per_sec` (no
+//! limit if unset), while the rest of the clients share the `api.rest.public_rate_limit_per_sec`
+//! one (no limit if unset). Requests with an unknown API key are treated as the public ones.
+//! Reachability checks of the `/favicon.ico` route are never limited.
+//!
+//! The IP address of the client is the address of the peer, unless the peer is one of the
+//! `api.rest.trusted_proxies`. Requests coming through the trusted proxies are attributed to
+//! the last address in the `X-Forwarded-For` header not belonging to the trusted proxies,
+//! since the preceding entries are set by the client itself.
+//!
+//! Buckets are kept in memory of the server, so every server instance has its own limits.
+
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use actix_web::{dev::ServiceRequest, Error};
+use dashmap::DashMap;
+use zksync_config::configs::api::RestApiConfig;
+
+use super::v01::error::ApiError;
+
+const API_KEY_HEADER: &str = "x-api-key";
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+/// Routes which are not counted against the limits.
+const EXEMPT_ROUTES: &[&str] = &["/favicon.ico"];
+/// Period of the removal of the buckets which got refilled completely.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Client {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Shared state of the REST API rate limits.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    api_keys: Arc<HashSet<String>>,
+    trusted_proxies: Arc<HashSet<IpAddr>>,
+    public_limit: Option<u32>,
+    api_key_limit: Option<u32>,
+    burst: Option<u32>,
+    /// Buckets are sharded, so that the requests of the different clients don't contend.
+    buckets: Arc<DashMap<Client, TokenBucket>>,
+    cleaned_at: Arc<Mutex<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RestApiConfig) -> Self {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .filter(|key| !key.is_empty())
+            .cloned()
+            .collect();
+        let trusted_proxies = config
+            .trusted_proxies
+            .iter()
+            .map(|ip| ip.parse().expect("Invalid trusted proxy IP address"))
+            .collect();
+
+        Self {
+            api_keys: Arc::new(api_keys),
+            trusted_proxies: Arc::new(trusted_proxies),
+            public_limit: config.public_rate_limit_per_sec,
+            api_key_limit: config.api_key_rate_limit_per_sec,
+            burst: config.rate_limit_burst,
+            buckets: Arc::new(DashMap::new()),
+            cleaned_at: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Rejects the request with `429 Too Many Requests` if the client has exceeded its limit.
+    pub fn check(&self, req: &ServiceRequest) -> Result<(), Error> {
+        if EXEMPT_ROUTES.contains(&req.path()) {
+            return Ok(());
+        }
+
+        let api_key = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|key| self.api_keys.contains(*key));
+        let client = match api_key {
+            Some(api_key) => Client::ApiKey(api_key.to_string()),
+            None => Client::Ip(self.client_ip(req)),
+        };
+
+        self.take_token(client, Instant::now())
+            .map_err(|retry_after| ApiError::RateLimited { retry_after }.into())
+    }
+
+    fn client_ip(&self, req: &ServiceRequest) -> IpAddr {
+        let peer_ip = match req.peer_addr() {
+            Some(peer_addr) => peer_addr.ip(),
+            None => return Ipv4Addr::UNSPECIFIED.into(),
+        };
+        if !self.trusted_proxies.contains(&peer_ip) {
+            return peer_ip;
+        }
+
+        // Every proxy appends the address of its peer, so the chain is walked from the end.
+        let forwarded_for: Vec<&str> = req
+            .headers()
+            .get_all(FORWARDED_FOR_HEADER)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect();
+        for address in forwarded_for.into_iter().rev() {
+            match address.trim().parse::<IpAddr>() {
+                Ok(ip) if self.trusted_proxies.contains(&ip) => continue,
+                Ok(ip) => return ip,
+                // Entries before the malformed one can't be attributed to the trusted proxies.
+                Err(_) => break,
+            }
+        }
+        peer_ip
+    }
+
+    /// Returns the capacity of the client bucket and its refill rate in tokens per second,
+    /// `None` if the client is not limited.
+    fn bucket_params(&self, client: &Client) -> Option<(f64, f64)> {
+        let limit = match client {
+            Client::ApiKey(_) => self.api_key_limit,
+            Client::Ip(_) => self.public_limit,
+        }?;
+        let capacity = f64::from(self.burst.map_or(limit, |burst| burst.max(1)));
+        Some((capacity, f64::from(limit)))
+    }
+
+    /// Takes a token from the client bucket. Returns the amount of seconds to wait
+    /// until the next token is available if the bucket is empty.
+    fn take_token(&self, client: Client, now: Instant) -> Result<(), u64> {
+        let (capacity, rate) = match self.bucket_params(&client) {
+            Some(params) => params,
+            None => return Ok(()),
+        };
+        self.cleanup(now);
+
+        let mut bucket = self.buckets.entry(client).or_insert_with(|| TokenBucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens < 1.0 {
+            let retry_after = if rate > 0.0 {
+                ((1.0 - bucket.tokens) / rate).ceil() as u64
+            } else {
+                CLEANUP_INTERVAL.as_secs()
+            };
+            return Err(retry_after.max(1));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+
+    /// Removes the full buckets, since they are indistinguishable from the missing ones.
+    /// Cleanup is skipped if it's already being done by another request.
+    fn cleanup(&self, now: Instant) {
+        let mut cleaned_at = match self.cleaned_at.try_lock() {
+            Ok(cleaned_at) => cleaned_at,
+            Err(_) => return,
+        };
+        if now.duration_since(*cleaned_at) < CLEANUP_INTERVAL {
+            return;
+        }
+        *cleaned_at = now;
+        self.buckets.retain(|client, bucket| {
+            self.bucket_params(client)
+                .map_or(false, |(capacity, rate)| {
+                    bucket.tokens + now.duration_since(bucket.updated_at).as_secs_f64() * rate
+                        < capacity
+                })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test::TestRequest};
+    use std::net::SocketAddr;
+
+    const PROXY: &str = "10.0.0.1";
+    const API_KEY: &str = "sample";
+
+    fn limiter(public_limit: Option<u32>, api_key_limit: Option<u32>) -> RateLimiter {
+        RateLimiter {
+            api_keys: Arc::new(vec![API_KEY.to_string()].into_iter().collect()),
+            trusted_proxies: Arc::new(vec![PROXY.parse().unwrap()].into_iter().collect()),
+            public_limit,
+            api_key_limit,
+            burst: Some(2),
+            buckets: Arc::new(DashMap::new()),
+            cleaned_at: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    fn request(peer_ip: &str, forwarded_for: Option<&str>) -> ServiceRequest {
+        let peer_addr = SocketAddr::new(peer_ip.parse().unwrap(), 12345);
+        let mut request = TestRequest::default().peer_addr(peer_addr);
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.insert_header((FORWARDED_FOR_HEADER, forwarded_for));
+        }
+        request.to_srv_request()
+    }
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    /// Checks that the forwarded addresses are only trusted if they are passed by the trusted proxies.
+    #[test]
+    fn client_ip() {
+        let limiter = limiter(Some(1), None);
+        let test_vector = vec![
+            ("1.1.1.1", None, "1.1.1.1"),
+            ("1.1.1.1", Some("2.2.2.2"), "1.1.1.1"),
+            (PROXY, None, PROXY),
+            (PROXY, Some("2.2.2.2"), "2.2.2.2"),
+            // The first entries are set by the client, the last one is appended by the proxy.
+            (PROXY, Some("3.3.3.3, 2.2.2.2"), "2.2.2.2"),
+            (PROXY, Some("2.2.2.2, 10.0.0.1"), "2.2.2.2"),
+            (PROXY, Some("2.2.2.2, junk"), PROXY),
+        ];
+        for (peer_ip, forwarded_for, expected_ip) in test_vector {
+            let request = request(peer_ip, forwarded_for);
+            assert_eq!(
+                limiter.client_ip(&request),
+                ip(expected_ip),
+                "{} {:?}",
+                peer_ip,
+                forwarded_for
+            );
+        }
+    }
+
+    /// Checks that the bucket allows the burst of requests and is then refilled at the limit rate.
+    #[test]
+    fn token_bucket() {
+        let limiter = limiter(Some(1), None);
+        let client = Client::Ip(ip("1.1.1.1"));
+        let now = Instant::now();
+
+        assert_eq!(limiter.take_token(client.clone(), now), Ok(()));
+        assert_eq!(limiter.take_token(client.clone(), now), Ok(()));
+        assert_eq!(limiter.take_token(client.clone(), now), Err(1));
+        // Other clients have their own buckets.
+        assert_eq!(limiter.take_token(Client::Ip(ip("2.2.2.2")), now), Ok(()));
+
+        // The limit is 1 request per second, so a token is added every second.
+        let now = now + Duration::from_secs(1);
+        assert_eq!(limiter.take_token(client.clone(), now), Ok(()));
+        assert_eq!(limiter.take_token(client, now), Err(1));
+    }
+
+    /// Checks that the API key holders are limited by their own limit.
+    #[test]
+    fn api_key_tier() {
+        let limiter = limiter(Some(1), None);
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.take_token(Client::ApiKey(API_KEY.to_string()), now),
+                Ok(())
+            );
+        }
+
+        let request = TestRequest::default()
+            .peer_addr(SocketAddr::new(ip("1.1.1.1"), 12345))
+            .insert_header((API_KEY_HEADER, "unknown"))
+            .to_srv_request();
+        assert!(limiter.check(&request).is_ok());
+        assert!(limiter.check(&request).is_ok());
+        let response = limiter
+            .check(&request)
+            .unwrap_err()
+            .as_response_error()
+            .error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get("retry-after").unwrap(), "1");
+    }
+
+    /// Checks that the full buckets are removed by the cleanup.
+    #[test]
+    fn cleanup() {
+        let limiter = limiter(Some(1), None);
+        let now = Instant::now();
+        limiter.take_token(Client::Ip(ip("1.1.1.1")), now).unwrap();
+        assert_eq!(limiter.buckets.len(), 1);
+
+        limiter
+            .take_token(Client::Ip(ip("2.2.2.2")), now + CLEANUP_INTERVAL)
+            .unwrap();
+        assert_eq!(limiter.buckets.len(), 1);
+        assert!(limiter.buckets.contains_key(&Client::Ip(ip("2.2.2.2"))));
+    }
+}
@@ -1,23 +1,70 @@
 //! Declaration of the API structure.
 
-use crate::api_server::rest::{helpers::*, v01::caches::Caches};
-use actix_web::error::InternalError;
-use actix_web::{web, HttpResponse, Result as ActixResult};
-use futures::channel::mpsc;
+use crate::api_server::rest::{
+    admin_auth::validate_admin_token,
+    helpers::*,
+    json_response::JsonResponseBuilder,
+    v01::{
+        caches::Caches,
+        error::ApiError,
+        exit_proofs::{generate_pending_exit_proof, VerifiedAccounts, MAX_PENDING_EXIT_PROOFS},
+        types::{BlockFees, BlockGasUsed, ExitProofResponse, ServerConfig, TxHistoryFilterQuery},
+        ws::WsNotifier,
+    },
+};
+use crate::fee_ticker::{account_creation_fee_wei, FeeTicker};
+use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use bigdecimal::BigDecimal;
+use chrono_tz::Tz;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, TryStreamExt,
+};
+use num::{BigUint, ToPrimitive};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
 
-use crate::api_server::rest::network_status::SharedNetworkStatus;
+use crate::api_server::rest::{network_status::SharedNetworkStatus, EnabledApis};
+use zksync_api_types::v02::account::Account as ApiAccount;
 use zksync_config::ZkSyncConfig;
+use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::{
     chain::{
         block::records::StorageBlockDetails,
-        operations_ext::records::{PriorityOpReceiptResponse, TxReceiptResponse},
+        operations_ext::{
+            records::{PriorityOpReceiptResponse, TxReceiptResponse},
+            AccountHistoryFilter,
+        },
     },
     ConnectionPool, StorageProcessor,
 };
+use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
-    block::ExecutedOperations, BlockNumber, PriorityOp, SequentialTxId, H160, H256,
+    block::ExecutedOperations, helpers::to_checksum_address, Account, AccountId, AccountMap,
+    Address, BlockNumber, PriorityOp, SequentialTxId, Token, TokenId, TokenLike, H160, H256,
 };
 
+/// Columns of the balances snapshot CSV.
+const BALANCES_SNAPSHOT_HEADER: &str = "account_id,address,token_id,balance\n";
+/// Size of the balances snapshot chunks sent to the client.
+const BALANCES_SNAPSHOT_CHUNK_SIZE: usize = 64 * 1024;
+/// Amount of the balances snapshot chunks buffered before the client receives them.
+const BALANCES_SNAPSHOT_BUFFER_SIZE: usize = 16;
+/// Endpoints of the `/api/v0.1/admin` scope.
+pub const ADMIN_ENDPOINTS: &[&str] = &[
+    "/api/v0.1/admin/balances_snapshot",
+    "/api/v0.1/admin/account/{address}/proof_at/{block}",
+    "/api/v0.1/admin/tokens/{id}/suspend",
+    "/api/v0.1/admin/tokens/{id}/resume",
+];
+/// Endpoint streaming the network status and the subscribed accounts transactions, see `ws`.
+pub const WS_ENDPOINT: &str = "/api/v0.1/ws";
+/// Interval of polling the pending exit proof requests.
+const EXIT_PROOF_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 /// `ApiV01` structure contains the implementation of `/api/v0.1` endpoints set.
 /// It is considered (somewhat) stable and will be supported for a while.
 ///
@@ -30,7 +77,25 @@ pub struct ApiV01 {
     pub(crate) main_database_connection_pool: ConnectionPool,
     pub(crate) network_status: SharedNetworkStatus,
     pub(crate) contract_address: String,
+    /// Root hash of the genesis state, which identifies the zkSync deployment.
+    pub(crate) genesis_block_hash: String,
     pub(crate) config: ZkSyncConfig,
+    pub(crate) fee_ticker: FeeTicker,
+    /// Token cache shared with the transaction sender, filled on the server startup.
+    pub(crate) tokens: TokenDBCache,
+    pub(crate) enabled_apis: EnabledApis,
+    /// Maximum time to wait for a database connection, see `access_storage`.
+    pub(crate) connection_acquisition_timeout: Option<Duration>,
+    /// Public configuration parameters served by the `/config` endpoint.
+    pub(crate) server_config: ServerConfig,
+    /// Page size of the paginated endpoints used if the `limit` is not specified.
+    pub(crate) default_page_limit: u32,
+    /// Maximum page size accepted by the paginated endpoints.
+    pub(crate) max_page_limit: u32,
+    /// Verified state the exit proofs are generated against, see `spawn_exit_proof_generator`.
+    pub(crate) verified_accounts: VerifiedAccounts,
+    /// Transactions notifier shared by the `/ws` clients, see `spawn_ws_notifier`.
+    pub(crate) ws_notifier: WsNotifier,
 }
 
 impl ApiV01 {
@@ -40,28 +105,73 @@ impl ApiV01 {
         contract_address: H160,
         config: ZkSyncConfig,
         network_status: SharedNetworkStatus,
+        fee_ticker: FeeTicker,
+        tokens: TokenDBCache,
+        enabled_apis: EnabledApis,
     ) -> Self {
         Self {
             caches: Caches::new(config.api.common.caches_size),
+            connection_acquisition_timeout: config.api.rest.connection_acquisition_timeout(),
+            server_config: ServerConfig::new(&config),
+            default_page_limit: config.api.rest.default_page_limit(),
+            max_page_limit: config.api.rest.max_page_limit(),
+            verified_accounts: VerifiedAccounts::default(),
+            ws_notifier: WsNotifier::new(config.api.rest.max_ws_connections()),
             connection_pool,
             main_database_connection_pool,
             network_status,
             contract_address: format!("{:?}", contract_address),
+            genesis_block_hash: format!("{:?}", config.contracts.genesis_root),
             config,
+            fee_ticker,
+            tokens,
+            enabled_apis,
         }
     }
 
     /// Creates an actix-web `Scope`, which can be mounted to the Http server.
     pub fn into_scope(self) -> actix_web::Scope {
+        let admin_secret = self.config.api.admin.secret_auth.clone();
+        let admin_auth = HttpAuthentication::bearer(move |req, credentials| {
+            let admin_secret = admin_secret.clone();
+            async move { validate_admin_token(&admin_secret, req, credentials) }
+        });
+
         web::scope("/api/v0.1")
             .app_data(web::Data::new(self))
+            // Malformed paths, queries and bodies (e.g. non-numeric `limit`) are reported
+            // the same way as the invalid parameters instead of the plain text actix errors.
+            .app_data(
+                web::PathConfig::default()
+                    .error_handler(|err, _| ApiError::invalid_parameter(err).into()),
+            )
+            .app_data(
+                web::QueryConfig::default()
+                    .error_handler(|err, _| ApiError::invalid_parameter(err).into()),
+            )
+            .app_data(
+                web::JsonConfig::default()
+                    .error_handler(|err, _| ApiError::invalid_parameter(err).into()),
+            )
             .route("/testnet_config", web::get().to(Self::testnet_config))
+            .route("/contracts", web::get().to(Self::contracts))
             .route("/status", web::get().to(Self::status))
+            .route("/ws", web::get().to(Self::ws))
+            .route("/capabilities", web::get().to(Self::capabilities))
+            .route("/config", web::get().to(Self::server_config))
             .route("/tokens", web::get().to(Self::tokens))
+            .route(
+                "/tokens/{token}/decimals",
+                web::get().to(Self::token_decimals),
+            )
             .route(
                 "/tokens_acceptable_for_fees",
                 web::get().to(Self::tokens_acceptable_for_fees),
             )
+            .route(
+                "/account/{address}/history",
+                web::get().to(Self::tx_history_page),
+            )
             .route(
                 "/account/{address}/history/{offset}/{limit}",
                 web::get().to(Self::tx_history),
@@ -74,6 +184,25 @@ impl ApiV01 {
                 "/account/{address}/history/newer_than",
                 web::get().to(Self::tx_history_newer_than),
             )
+            .route(
+                "/account/{address}/timeline",
+                web::get().to(Self::account_timeline),
+            )
+            .route(
+                "/account/{address}/deposits",
+                web::get().to(Self::account_deposits_history),
+            )
+            .route(
+                "/account/{address}/forced_exits_initiated",
+                web::get().to(Self::forced_exits_initiated),
+            )
+            .route(
+                "/account/{address}/history_grouped",
+                web::get().to(Self::tx_history_grouped),
+            )
+            .route("/transactions/rejected", web::get().to(Self::rejected_txs))
+            .route("/mempool", web::get().to(Self::mempool_txs))
+            .route("/mempool/count", web::get().to(Self::mempool_count))
             .route(
                 "/transactions/{tx_hash}",
                 web::get().to(Self::executed_tx_by_hash),
@@ -86,6 +215,18 @@ impl ApiV01 {
                 "/priority_operations/{pq_id}/",
                 web::get().to(Self::priority_op),
             )
+            .route(
+                "/priority_operations/{serial_id}/confirmations",
+                web::get().to(Self::priority_op_confirmations),
+            )
+            .route(
+                "/batches/{batch_id}/transactions/{index}",
+                web::get().to(Self::batch_tx),
+            )
+            .route(
+                "/blocks/{block_id}/transactions/{tx_id}/signed",
+                web::get().to(Self::block_signed_tx),
+            )
             .route(
                 "/blocks/{block_id}/transactions/{tx_id}",
                 web::get().to(Self::block_tx),
@@ -94,25 +235,429 @@ impl ApiV01 {
                 "/blocks/{block_id}/transactions",
                 web::get().to(Self::block_transactions),
             )
+            .route(
+                "/blocks/{block_id}/rejected_txs",
+                web::get().to(Self::block_rejected_txs),
+            )
+            .route(
+                "/blocks/{block_id}/pubdata",
+                web::get().to(Self::block_pubdata),
+            )
+            .route(
+                "/blocks/{block_id}/fee_recipient",
+                web::get().to(Self::block_fee_recipient),
+            )
             .route("/blocks/{block_id}", web::get().to(Self::block_by_id))
             .route("/blocks", web::get().to(Self::blocks))
             .route("/search", web::get().to(Self::explorer_search))
+            .route("/account/{address}", web::get().to(Self::account_state))
+            .route(
+                "/account_by_id/{account_id}",
+                web::get().to(Self::account_state_by_id),
+            )
+            .route(
+                "/validate_address/{address}",
+                web::get().to(Self::validate_address),
+            )
+            .route("/accounts/by_id", web::post().to(Self::accounts_by_id))
+            .route(
+                "/accounts/history",
+                web::post().to(Self::accounts_tx_history),
+            )
+            .route(
+                "/accounts/aggregate_balance",
+                web::post().to(Self::aggregate_balance),
+            )
+            .route(
+                "/account/{address}/activity_span",
+                web::get().to(Self::account_activity_span),
+            )
+            .route(
+                "/account/{address}/change_pubkey",
+                web::get().to(Self::latest_change_pubkey),
+            )
+            .route(
+                "/account/{address}/nonce",
+                web::get().to(Self::account_nonce),
+            )
+            .route(
+                "/account/{address}/nonce/{nonce}/used",
+                web::get().to(Self::nonce_used),
+            )
+            .route(
+                "/account/{address}/exit_proof/{token}",
+                web::get().to(Self::exit_proof),
+            )
+            .route(
+                "/account/{address}/state_at_time/{unix_ts}",
+                web::get().to(Self::account_state_at_time),
+            )
+            .route(
+                "/account/{address}/withdrawals/{tx_hash}/proof_status",
+                web::get().to(Self::withdrawal_proof_status),
+            )
+            .route(
+                "/exit_proof/{account_id}/{token_id}",
+                web::get().to(Self::exit_proof_by_id),
+            )
             .route(
                 "/withdrawal_processing_time",
                 web::get().to(Self::withdrawal_processing_time),
             )
+            .route(
+                "/stats/tx_type_breakdown",
+                web::get().to(Self::tx_type_breakdown),
+            )
+            .route("/stats/block_times", web::get().to(Self::block_times))
+            .route("/stats/proving_times", web::get().to(Self::proving_times))
+            .route(
+                "/stats/priority_ops",
+                web::get().to(Self::priority_ops_stats),
+            )
+            .route(
+                "/stats/fee_token_usage",
+                web::get().to(Self::fee_token_usage),
+            )
+            .route("/fee/schedule", web::get().to(Self::fee_schedule))
+            .route("/operator/gas_policy", web::get().to(Self::gas_policy))
+            .route("/prover/latency", web::get().to(Self::prover_latency))
+            .service(
+                web::scope("/admin")
+                    .wrap(admin_auth)
+                    .route("/balances_snapshot", web::get().to(Self::balances_snapshot))
+                    // Proofs require building the whole account tree of the block,
+                    // so they're not available to the public.
+                    .route(
+                        "/account/{address}/proof_at/{block}",
+                        web::get().to(Self::account_proof_at),
+                    )
+                    .route("/tokens/{id}/suspend", web::post().to(Self::suspend_token))
+                    .route("/tokens/{id}/resume", web::post().to(Self::resume_token)),
+            )
     }
 
-    pub(crate) async fn access_storage(&self) -> ActixResult<StorageProcessor<'_>> {
+    /// Acquires a database connection for the request handler.
+    ///
+    /// If the connection acquisition timeout is configured, requests which can't get a connection
+    /// in time are rejected with `503 Service Unavailable` instead of waiting for the pool.
+    pub(crate) async fn access_storage(&self) -> Result<StorageProcessor<'_>, ApiError> {
+        if let Some(timeout) = self.connection_acquisition_timeout {
+            return self
+                .connection_pool
+                .access_storage_with_timeout(timeout)
+                .await
+                .map_err(|err| {
+                    vlog::warn!("DB connection acquisition timeout: '{}';", err);
+                    ApiError::StorageUnavailable
+                });
+        }
+
         self.connection_pool.access_storage().await.map_err(|err| {
             vlog::warn!("DB await timeout: '{}';", err);
-            actix_web::error::ErrorRequestTimeout(err)
+            ApiError::StorageTimeout
         })
     }
 
-    pub(crate) fn db_error(error: anyhow::Error) -> InternalError<anyhow::Error> {
+    pub(crate) fn db_error(error: anyhow::Error) -> ApiError {
         vlog::warn!("DB error: '{}';", error);
-        InternalError::from_response(error, HttpResponse::InternalServerError().finish())
+        ApiError::Storage
+    }
+
+    /// Responds with `404 Not Found` if there's no token with the given ID.
+    pub(crate) async fn check_token_exists(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> Result<(), ApiError> {
+        self.tokens
+            .get_token(storage, token_id)
+            .await
+            .map_err(Self::db_error)?
+            .map(drop)
+            .ok_or_else(|| ApiError::not_found("Token"))
+    }
+
+    /// Returns the requested page size or the configured default one if it's not specified.
+    /// Fails if the size is zero or exceeds the configured maximum.
+    pub(crate) fn page_limit(&self, limit: Option<u32>) -> Result<u32, ApiError> {
+        let limit = limit.unwrap_or(self.default_page_limit);
+        if limit == 0 {
+            return Err(ApiError::invalid_parameter("limit must be positive"));
+        }
+        self.check_page_limit(u64::from(limit))?;
+        Ok(limit)
+    }
+
+    /// Fails with the maximum page size in the message if the requested page size exceeds it.
+    pub(crate) fn check_page_limit(&self, limit: u64) -> Result<(), ApiError> {
+        if limit > u64::from(self.max_page_limit) {
+            return Err(ApiError::invalid_parameter(format!(
+                "limit exceeds maximum of {}",
+                self.max_page_limit
+            )));
+        }
+        Ok(())
+    }
+
+    /// Resolves the token and parses the amount bounds of the account history filter.
+    pub(crate) async fn history_filter(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        query: &TxHistoryFilterQuery,
+    ) -> Result<AccountHistoryFilter, ApiError> {
+        let parse_amount = |amount: &Option<String>| {
+            amount
+                .as_deref()
+                .map(BigUint::from_str)
+                .transpose()
+                .map_err(|_| ApiError::invalid_parameter("amount must be a decimal number"))
+        };
+        let min_amount = parse_amount(&query.min_amount)?;
+        let max_amount = parse_amount(&query.max_amount)?;
+        if let (Some(min_amount), Some(max_amount)) = (&min_amount, &max_amount) {
+            if min_amount > max_amount {
+                return Err(ApiError::invalid_parameter(
+                    "min_amount must not exceed max_amount",
+                ));
+            }
+        }
+
+        let token = match &query.token {
+            Some(token) => {
+                let token = storage
+                    .tokens_schema()
+                    .get_token(TokenLike::parse(token))
+                    .await
+                    .map_err(Self::db_error)?
+                    .ok_or_else(|| ApiError::not_found("Token"))?;
+                Some(token.id)
+            }
+            None if min_amount.is_some() || max_amount.is_some() => {
+                return Err(ApiError::invalid_parameter(
+                    "amount bounds require the token to be specified",
+                ));
+            }
+            None => None,
+        };
+
+        let dust_threshold_usd = query
+            .hide_dust
+            .unwrap_or(false)
+            .then(|| self.config.api.rest.dust_threshold_usd());
+        Ok(AccountHistoryFilter {
+            token,
+            min_amount,
+            max_amount,
+            dust_threshold_usd,
+        })
+    }
+
+    /// Checks whether the pending deposit reported in the account history passes the filter.
+    pub(crate) fn pending_deposit_matches(op: &PriorityOp, filter: &AccountHistoryFilter) -> bool {
+        op.data.try_get_deposit().map_or(false, |deposit| {
+            filter.matches(deposit.token, &deposit.amount)
+        })
+    }
+
+    /// Parses the timezone requested via the `X-Timezone` header (e.g. `Europe/Berlin`).
+    /// Returns `None` if the header is absent.
+    pub(crate) fn parse_timezone(req: &HttpRequest) -> Result<Option<Tz>, ApiError> {
+        req.headers()
+            .get("x-timezone")
+            .map(|value| {
+                value
+                    .to_str()
+                    .map_err(ApiError::invalid_parameter)?
+                    .parse::<Tz>()
+                    .map_err(ApiError::invalid_parameter)
+            })
+            .transpose()
+    }
+
+    /// Estimates the one-time fee for the account creation in ETH wei
+    /// based on the average gas price.
+    pub(crate) async fn account_creation_fee_hint(
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<String, ApiError> {
+        let gas_price_wei = storage
+            .ethereum_schema()
+            .load_average_gas_price()
+            .await
+            .map_err(Self::db_error)?
+            .unwrap_or_default();
+        // Gas price is converted without the loss of precision, it may not fit into `u64`.
+        let mut gas_price_bytes = [0u8; 32];
+        gas_price_wei.to_big_endian(&mut gas_price_bytes);
+        let gas_price_wei = BigUint::from_bytes_be(&gas_price_bytes);
+        Ok(account_creation_fee_wei(gas_price_wei).to_string())
+    }
+
+    /// Loads the state of the accounts tree as of the given block, along with the ID of the account.
+    /// `None` if the account didn't exist in that block.
+    pub(crate) async fn account_state_at_block(
+        storage: &mut StorageProcessor<'_>,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> Result<Option<(AccountId, AccountMap)>, ApiError> {
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Self::db_error)?;
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => return Ok(None),
+        };
+
+        // The state of the block is obtained by reverting the updates of the later blocks.
+        let (_, accounts) = storage
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(block_number))
+            .await
+            .map_err(Self::db_error)?;
+        // Account may be created after the requested block.
+        if !accounts.contains_key(&account_id) {
+            return Ok(None);
+        }
+        Ok(Some((account_id, accounts)))
+    }
+
+    /// Converts the account into its API representation, listing the balances by the token
+    /// symbols the same way the v0.2 accounts API does.
+    pub(crate) async fn api_account(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        account_id: AccountId,
+        account: Account,
+        last_update_in_block: BlockNumber,
+    ) -> Result<ApiAccount, ApiError> {
+        let mut balances = BTreeMap::new();
+        let mut nfts = BTreeMap::new();
+        for (token_id, balance) in account.get_nonzero_balances() {
+            match token_id.0 {
+                // The special token is neither a balance nor an NFT.
+                NFT_TOKEN_ID_VAL => {}
+                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL => {
+                    let nft = self
+                        .tokens
+                        .get_nft_by_id(storage, token_id)
+                        .await
+                        .map_err(Self::db_error)?
+                        .ok_or_else(|| ApiError::not_found("NFT"))?;
+                    nfts.insert(token_id, nft.into());
+                }
+                _ => {
+                    let symbol = self
+                        .tokens
+                        .token_symbol(storage, token_id)
+                        .await
+                        .map_err(Self::db_error)?
+                        .ok_or_else(|| ApiError::not_found("Token"))?;
+                    balances.insert(symbol, balance);
+                }
+            }
+        }
+        let minted_nfts = account
+            .minted_nfts
+            .iter()
+            .map(|(id, nft)| (*id, nft.clone().into()))
+            .collect();
+        let account_type = storage
+            .chain()
+            .account_schema()
+            .account_type_by_id(account_id)
+            .await
+            .map_err(Self::db_error)?
+            .map(Into::into);
+
+        Ok(ApiAccount {
+            account_id,
+            address: account.address,
+            nonce: account.nonce,
+            pub_key_hash: account.pub_key_hash,
+            last_update_in_block,
+            balances,
+            nfts,
+            minted_nfts,
+            account_type,
+        })
+    }
+
+    /// Returns the exit proof of the account balance generated against the last verified block.
+    ///
+    /// Proofs are never generated by the handlers: if there is no proof for the current verified
+    /// state yet, it's requested from the background generator and `202 Accepted` is returned,
+    /// so that the client polls the endpoint until the proof is ready.
+    pub(crate) async fn exit_proof_response(
+        &self,
+        account_id: AccountId,
+        token: Token,
+    ) -> Result<HttpResponse, ApiError> {
+        if token.is_nft {
+            return Err(ApiError::invalid_parameter(
+                "Exit proofs of NFTs are not supported",
+            ));
+        }
+        let block_number = match self.verified_accounts.account_block(account_id).await {
+            Some(block_number) => block_number,
+            None if self.verified_accounts.is_loaded().await => {
+                return Err(ApiError::not_found("Verified account"))
+            }
+            None => return Err(ApiError::unavailable("Verified state is not loaded yet")),
+        };
+
+        let mut storage = self
+            .main_database_connection_pool
+            .access_storage()
+            .await
+            .map_err(Self::db_error)?;
+        let exit_proof = storage
+            .chain()
+            .block_schema()
+            .get_exit_proof(account_id, token.id)
+            .await
+            .map_err(Self::db_error)?;
+        let exit_proof = match exit_proof {
+            Some(exit_proof) if exit_proof.block_number >= *block_number as i64 => exit_proof,
+            exit_proof => {
+                // Outdated pending requests are already counted in the queue.
+                let is_queued = exit_proof.map_or(false, |exit_proof| exit_proof.proof.is_none());
+                let pending = storage
+                    .chain()
+                    .block_schema()
+                    .count_pending_exit_proofs()
+                    .await
+                    .map_err(Self::db_error)?;
+                if !is_queued && pending >= MAX_PENDING_EXIT_PROOFS {
+                    return Err(ApiError::unavailable("Too many pending exit proofs"));
+                }
+                storage
+                    .chain()
+                    .block_schema()
+                    .request_exit_proof(account_id, token.id, block_number)
+                    .await
+                    .map_err(Self::db_error)?;
+                return Ok(HttpResponse::Accepted().json_utf8(ExitProofResponse {
+                    account: account_id,
+                    token: token.id,
+                    block_number,
+                    amount: None,
+                    proof: None,
+                }));
+            }
+        };
+
+        let response = ExitProofResponse::new(exit_proof).map_err(|err| {
+            vlog::warn!("Failed to decode the exit proof: {}", err);
+            ApiError::Internal
+        })?;
+        if response.proof.is_some() {
+            Ok(HttpResponse::Ok().json_utf8(response))
+        } else {
+            Ok(HttpResponse::Accepted().json_utf8(response))
+        }
     }
 
     // Spawns future updating SharedNetworkStatus in the current `actix::System`
@@ -128,11 +673,129 @@ impl ApiV01 {
         );
     }
 
+    /// Spawns future refreshing the token lists served by the `/tokens` endpoint in the current
+    /// `actix::System`. Lists are refreshed every `api.rest.token_list_refresh_interval_sec`,
+    /// so the new tokens may be missing from the responses for up to this interval.
+    /// The lists are expected to be loaded on startup, so the first refresh is delayed.
+    pub fn spawn_token_list_updater(&self) {
+        let api = self.clone();
+        let interval = self.config.api.rest.token_list_refresh_interval();
+        actix_rt::spawn(async move {
+            let mut timer =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            loop {
+                timer.tick().await;
+                api.refresh_token_lists().await;
+            }
+        });
+    }
+
+    /// Spawns future sending the transactions of the accounts subscribed over WebSocket
+    /// in the current `actix::System`. The accounts are queried once per committed block.
+    pub fn spawn_ws_notifier(&self) {
+        actix_rt::spawn(self.ws_notifier.clone().run(
+            self.network_status.subscribe(),
+            self.connection_pool.clone(),
+        ));
+    }
+
+    /// Spawns future generating the requested exit proofs in the current `actix::System`.
+    ///
+    /// Proofs are generated one at a time, so a burst of requests can't exhaust the CPU
+    /// of the server; requests are capped by `MAX_PENDING_EXIT_PROOFS` instead.
+    pub fn spawn_exit_proof_generator(&self) {
+        // Requests are written by the handlers, so they must be read from the main database.
+        let connection_pool = self.main_database_connection_pool.clone();
+        let verified_accounts = self.verified_accounts.clone();
+        actix_rt::spawn(async move {
+            let mut timer = tokio::time::interval(EXIT_PROOF_POLL_INTERVAL);
+            loop {
+                timer.tick().await;
+                if let Err(err) =
+                    generate_pending_exit_proof(&connection_pool, &verified_accounts).await
+                {
+                    vlog::warn!("Failed to generate the exit proof: {}", err);
+                }
+            }
+        });
+    }
+
+    /// Spawns a task streaming the non-zero verified balances of all the accounts as CSV chunks.
+    /// Returns the number of the verified block the balances correspond to.
+    ///
+    /// Balances are read from the database cursor as the client consumes the chunks, so
+    /// the task never keeps more than a few chunks in memory. The whole stream is read in a
+    /// single snapshot transaction, so the blocks verified in the meantime don't affect it.
+    /// If the database fails in the middle of the stream, an error is sent, so that the
+    /// response is aborted rather than silently truncated.
+    pub(crate) async fn spawn_balances_snapshot_stream(
+        &self,
+    ) -> Result<(BlockNumber, mpsc::Receiver<Result<Bytes, actix_web::Error>>), ApiError> {
+        let (mut sender, receiver) = mpsc::channel(BALANCES_SNAPSHOT_BUFFER_SIZE);
+        let (block_sender, block_receiver) = oneshot::channel();
+        let connection_pool = self.connection_pool.clone();
+
+        actix_rt::spawn(async move {
+            let result: anyhow::Result<()> = async {
+                let mut storage = connection_pool.access_storage().await?;
+                let mut transaction = storage.start_snapshot_transaction().await?;
+                let block_number = transaction
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_confirmed_block()
+                    .await?;
+                if block_sender.send(block_number).is_err() {
+                    return Ok(());
+                }
+                let mut balances = transaction
+                    .chain()
+                    .state_schema()
+                    .verified_balances_stream();
+
+                let mut chunk = String::from(BALANCES_SNAPSHOT_HEADER);
+                while let Some(balance) = balances.try_next().await? {
+                    writeln!(
+                        chunk,
+                        "{},{:?},{},{}",
+                        balance.account_id,
+                        H160::from_slice(&balance.address),
+                        balance.coin_id,
+                        balance.balance
+                    )?;
+                    if chunk.len() >= BALANCES_SNAPSHOT_CHUNK_SIZE {
+                        let chunk = Bytes::from(std::mem::take(&mut chunk));
+                        if sender.send(Ok(chunk)).await.is_err() {
+                            // Client has disconnected, no need to read the rest of the balances.
+                            return Ok(());
+                        }
+                    }
+                }
+                if !chunk.is_empty() {
+                    sender.send(Ok(Bytes::from(chunk))).await.ok();
+                }
+                Ok(())
+            }
+            .await;
+
+            if let Err(err) = result {
+                vlog::warn!("Failed to stream the balances snapshot: '{}'", err);
+                sender
+                    .send(Err(actix_web::error::ErrorInternalServerError(err)))
+                    .await
+                    .ok();
+            }
+        });
+
+        // The task drops the sender without sending the block number if it fails to start.
+        let block_number = block_receiver.await.map_err(|_| ApiError::Storage)?;
+        Ok((block_number, receiver))
+    }
+
     // cache access functions
     pub async fn get_tx_receipt(
         &self,
         transaction_hash: Vec<u8>,
-    ) -> Result<Option<TxReceiptResponse>, actix_web::error::Error> {
+    ) -> Result<Option<TxReceiptResponse>, ApiError> {
         if let Some(tx_receipt) = self.caches.transaction_receipts.get(&transaction_hash) {
             return Ok(Some(tx_receipt));
         }
@@ -160,7 +823,7 @@ impl ApiV01 {
     pub async fn get_priority_op_receipt(
         &self,
         id: u32,
-    ) -> Result<PriorityOpReceiptResponse, actix_web::error::Error> {
+    ) -> Result<PriorityOpReceiptResponse, ApiError> {
         if let Some(receipt) = self.caches.priority_op_receipts.get(&id) {
             return Ok(receipt);
         }
@@ -173,7 +836,7 @@ impl ApiV01 {
             .await
             .map_err(|err| {
                 vlog::warn!("Internal Server Error: '{}'; input: {}", err, id);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         // Unverified blocks can still change, so we can't cache them.
@@ -187,7 +850,7 @@ impl ApiV01 {
     pub async fn get_block_executed_ops(
         &self,
         block_id: BlockNumber,
-    ) -> Result<Vec<ExecutedOperations>, actix_web::error::Error> {
+    ) -> Result<Vec<ExecutedOperations>, ApiError> {
         if let Some(executed_ops) = self.caches.block_executed_ops.get(&block_id) {
             return Ok(executed_ops);
         }
@@ -201,7 +864,7 @@ impl ApiV01 {
             .await
             .map_err(|err| {
                 vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         if let Ok(block_details) = transaction
@@ -225,7 +888,7 @@ impl ApiV01 {
     pub async fn get_block_info(
         &self,
         block_id: BlockNumber,
-    ) -> Result<Option<StorageBlockDetails>, actix_web::error::Error> {
+    ) -> Result<Option<StorageBlockDetails>, ApiError> {
         if let Some(block) = self.caches.blocks_info.get(&block_id) {
             return Ok(Some(block));
         }
@@ -238,7 +901,7 @@ impl ApiV01 {
             .await
             .map_err(|err| {
                 vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         if !blocks.is_empty()
@@ -251,10 +914,242 @@ impl ApiV01 {
         Ok(blocks.pop())
     }
 
+    /// Returns the fee statistics of the given blocks: the sums of fees converted to ETH (in wei)
+    /// and the average fees per operation in USD.
+    ///
+    /// Fees are converted with the token prices at the time the block was committed, so they
+    /// don't change over time. Fees are `None` if the price of ETH or any of the tokens fees
+    /// were paid in was unknown. Average fee is also `None` for the blocks without operations.
+    pub async fn get_blocks_fees(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blocks: &[StorageBlockDetails],
+    ) -> Result<HashMap<i64, BlockFees>, ApiError> {
+        let mut blocks_fees = HashMap::new();
+        let mut missing_blocks = Vec::new();
+        for block in blocks {
+            let block_number = block.block_number as u32;
+            match self.caches.blocks_fees.get(&block_number) {
+                Some(block_fees) => {
+                    blocks_fees.insert(block.block_number, block_fees);
+                }
+                None => missing_blocks.push(BlockNumber(block_number)),
+            }
+        }
+        if missing_blocks.is_empty() {
+            return Ok(blocks_fees);
+        }
+
+        let mut fees = storage
+            .chain()
+            .block_schema()
+            .load_blocks_fees(&missing_blocks)
+            .await
+            .map_err(Self::db_error)?;
+        let tx_counts = storage
+            .chain()
+            .block_schema()
+            .load_blocks_tx_counts(&missing_blocks)
+            .await
+            .map_err(Self::db_error)?;
+
+        for block in blocks {
+            let block_number = BlockNumber(block.block_number as u32);
+            if !missing_blocks.contains(&block_number) {
+                continue;
+            }
+            let stored_fees = fees.remove(&block_number);
+            let tx_count = tx_counts.get(&block_number).copied().unwrap_or_default();
+            let avg_tx_fee_usd = stored_fees
+                .as_ref()
+                .and_then(|fees| fees.total_fee_usd.as_ref())
+                .filter(|_| tx_count > 0)
+                .and_then(|fee_usd| (fee_usd / BigDecimal::from(tx_count)).to_f64());
+            let total_fee_eth_wei = stored_fees
+                .as_ref()
+                .and_then(|fees| fees.total_fee_eth_wei.as_ref())
+                .map(|total_fee| total_fee.with_scale(0).to_string());
+            let block_fees = BlockFees {
+                total_fee_eth_wei,
+                avg_tx_fee_usd,
+            };
+            // Fees are stored once the block is committed and never change afterwards.
+            if stored_fees.is_some() {
+                self.caches
+                    .blocks_fees
+                    .insert(block.block_number as u32, block_fees.clone());
+            }
+            blocks_fees.insert(block.block_number, block_fees);
+        }
+
+        Ok(blocks_fees)
+    }
+
+    /// Returns the numbers of unique senders of the operations in the provided blocks.
+    ///
+    /// Operations of the committed blocks are not going to change, so the numbers are cached
+    /// once loaded, and it's mostly the recently committed blocks that are queried from the database.
+    pub async fn get_blocks_unique_senders(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blocks: &[StorageBlockDetails],
+    ) -> Result<HashMap<i64, u32>, ApiError> {
+        let mut blocks_senders = HashMap::new();
+        let mut missing_blocks = Vec::new();
+        for block in blocks {
+            let block_number = block.block_number as u32;
+            match self.caches.blocks_unique_senders.get(&block_number) {
+                Some(senders) => {
+                    blocks_senders.insert(block.block_number, senders);
+                }
+                None => missing_blocks.push(BlockNumber(block_number)),
+            }
+        }
+        if missing_blocks.is_empty() {
+            return Ok(blocks_senders);
+        }
+
+        let loaded_senders = storage
+            .chain()
+            .block_schema()
+            .load_blocks_unique_senders(&missing_blocks)
+            .await
+            .map_err(Self::db_error)?;
+        for block_number in missing_blocks {
+            // Blocks without operations are omitted by the query.
+            let senders = loaded_senders
+                .get(&block_number)
+                .copied()
+                .unwrap_or_default();
+            self.caches
+                .blocks_unique_senders
+                .insert(*block_number, senders);
+            blocks_senders.insert(i64::from(*block_number), senders);
+        }
+
+        Ok(blocks_senders)
+    }
+
+    /// Returns the gas used by the L1 transactions committing the provided blocks and publishing
+    /// their proofs.
+    ///
+    /// Gas is cached once both transactions are mined, so that the requests for the recent
+    /// blocks mostly hit the cache.
+    pub async fn get_blocks_gas_used(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blocks: &[StorageBlockDetails],
+    ) -> Result<HashMap<i64, BlockGasUsed>, ApiError> {
+        let mut blocks_gas_used = HashMap::new();
+        let mut missing_blocks = Vec::new();
+        for block in blocks {
+            let block_number = block.block_number as u32;
+            match self.caches.blocks_gas_used.get(&block_number) {
+                Some(gas_used) => {
+                    blocks_gas_used.insert(block.block_number, gas_used);
+                }
+                None => missing_blocks.push(BlockNumber(block_number)),
+            }
+        }
+        if missing_blocks.is_empty() {
+            return Ok(blocks_gas_used);
+        }
+
+        let loaded_gas_used = storage
+            .chain()
+            .block_schema()
+            .load_blocks_gas_used(&missing_blocks)
+            .await
+            .map_err(Self::db_error)?;
+        for block in loaded_gas_used {
+            let gas_used = BlockGasUsed {
+                commit_gas_used: block.commit_gas_used.map(|gas| gas as u64),
+                verify_gas_used: block.verify_gas_used.map(|gas| gas as u64),
+            };
+            if gas_used.verify_gas_used.is_some() {
+                self.caches
+                    .blocks_gas_used
+                    .insert(block.block_number as u32, gas_used);
+            }
+            blocks_gas_used.insert(block.block_number, gas_used);
+        }
+
+        Ok(blocks_gas_used)
+    }
+
+    /// Returns the checksummed addresses which sent the L1 transactions publishing the proofs
+    /// of the provided blocks. Blocks that are not verified yet are skipped, the addresses never change afterwards
+    /// and thus are always cached.
+    pub async fn get_blocks_verifiers(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blocks: &[StorageBlockDetails],
+    ) -> Result<HashMap<i64, String>, ApiError> {
+        let mut blocks_verifiers = HashMap::new();
+        let mut missing_blocks = Vec::new();
+        for block in blocks.iter().filter(|block| block_verified(block)) {
+            let block_number = block.block_number as u32;
+            match self.caches.blocks_verifiers.get(&block_number) {
+                Some(verifier) => {
+                    blocks_verifiers.insert(block.block_number, verifier);
+                }
+                None => missing_blocks.push(BlockNumber(block_number)),
+            }
+        }
+        if missing_blocks.is_empty() {
+            return Ok(blocks_verifiers);
+        }
+
+        let loaded_verifiers = storage
+            .chain()
+            .block_schema()
+            .load_blocks_verifiers(&missing_blocks)
+            .await
+            .map_err(Self::db_error)?;
+        for block in loaded_verifiers {
+            let verifier = to_checksum_address(&Address::from_slice(&block.verifier_address));
+            self.caches
+                .blocks_verifiers
+                .insert(block.block_number as u32, verifier.clone());
+            blocks_verifiers.insert(block.block_number, verifier);
+        }
+
+        Ok(blocks_verifiers)
+    }
+
+    /// Returns the state roots of the blocks preceding the provided ones, keyed by the numbers
+    /// of the provided blocks.
+    pub async fn get_prev_block_hashes(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blocks: &[StorageBlockDetails],
+    ) -> Result<HashMap<i64, Vec<u8>>, ApiError> {
+        let prev_blocks: Vec<_> = blocks
+            .iter()
+            .filter(|block| block.block_number > 0)
+            .map(|block| BlockNumber(block.block_number as u32 - 1))
+            .collect();
+        let mut state_roots = storage
+            .chain()
+            .block_schema()
+            .load_state_roots(&prev_blocks)
+            .await
+            .map_err(Self::db_error)?;
+
+        let prev_block_hashes = prev_blocks
+            .into_iter()
+            .filter_map(|block_number| {
+                let state_root = state_roots.remove(&block_number)?;
+                Some((i64::from(*block_number) + 1, state_root))
+            })
+            .collect();
+        Ok(prev_block_hashes)
+    }
+
     pub async fn get_block_by_height_or_hash(
         &self,
         query: String,
-    ) -> Result<Option<StorageBlockDetails>, actix_web::error::Error> {
+    ) -> Result<Option<StorageBlockDetails>, ApiError> {
         if let Some(block) = self.caches.blocks_by_height_or_hash.get(&query) {
             return Ok(Some(block));
         }
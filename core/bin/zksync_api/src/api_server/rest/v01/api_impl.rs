@@ -7,57 +7,267 @@
 use crate::api_server::{
     helpers::try_parse_hash,
     rest::{
+        cache_control,
         helpers::{deposit_op_to_tx_by_hash, parse_tx_id, priority_op_to_tx_history},
-        v01::{api_decl::ApiV01, types::*},
+        json_response::{JsonResponseBuilder, JSON_CONTENT_TYPE},
+        v01::{
+            api_decl::{ApiV01, ADMIN_ENDPOINTS, WS_ENDPOINT},
+            error::ApiError,
+            types::*,
+        },
     },
 };
-use actix_web::error::InternalError;
-use actix_web::{web, HttpResponse, Result as ActixResult};
-use chrono::Duration;
-use num::{rational::Ratio, BigUint, FromPrimitive};
-use std::time::Instant;
-use zksync_storage::chain::operations_ext::SearchDirection;
-use zksync_types::{Address, BlockNumber, Token, TokenId, TokenKind};
-
-/// Helper macro which wraps the serializable object into `Ok(HttpResponse::Ok().json(...))`.
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use chrono::{Duration, Utc};
+use num::{rational::Ratio, BigUint, FromPrimitive, ToPrimitive};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::TryFrom,
+    time::Instant,
+};
+use zksync_crypto::{
+    circuit::{account::CircuitAccount, CircuitAccountTree},
+    convert::FeConvert,
+    params::{account_tree_depth, MIN_NFT_TOKEN_ID},
+};
+use zksync_storage::{
+    chain::{
+        account::{
+            records::{Account2FA, AccountLastUpdates},
+            StoredAccountState,
+        },
+        block::records::StorageBlockDetails,
+        operations_ext::SearchDirection,
+    },
+    StorageProcessor,
+};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    block::compute_chunk_offsets,
+    helpers::{parse_checksum_address, to_checksum_address},
+    prover::ProverJobStatus,
+    tx::TxHash,
+    Account, AccountId, AccountMap, Address, BlockNumber, ExecutedOperations, Nonce, Token,
+    TokenId, TokenKind, TokenLike, TxFeeTypes,
+};
+
+/// Largest index of a transaction in a block. No block contains more operations,
+/// so the requests for the greater indices are rejected without accessing the database.
+const MAX_BLOCK_TX_INDEX: u32 = 65535;
+/// Types of the transactions moving funds from L2 to L1.
+const WITHDRAWAL_TX_TYPES: &[&str] = &["Withdraw", "ForcedExit", "FullExit", "WithdrawNFT"];
+
+/// Header nudging the clients of the existing accounts without the L2 public key to set it.
+/// Accounts which don't exist yet can't call `ChangePubKey`, so they don't get it.
+const LEGACY_SIGNATURE_WARNING_HEADER: &str = "x-deprecation-warning";
+const LEGACY_SIGNATURE_WARNING: &str =
+    "Account uses legacy signature scheme; call ChangePubKey to enable L2 native signing";
+
+/// Helper macro which wraps the serializable object into `Ok(HttpResponse::Ok().json_utf8(...))`.
 macro_rules! ok_json {
     ($resp:expr) => {
-        Ok(HttpResponse::Ok().json($resp))
+        Ok(HttpResponse::Ok().json_utf8($resp))
     };
 }
 
 impl ApiV01 {
-    pub async fn testnet_config(self_: web::Data<Self>) -> ActixResult<HttpResponse> {
+    pub async fn testnet_config(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         let contract_address = self_.contract_address.clone();
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "testnet_config");
         ok_json!(TestnetConfigResponse { contract_address })
     }
 
-    pub async fn status(self_: web::Data<Self>) -> ActixResult<HttpResponse> {
+    pub async fn contracts(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
-        let result = ok_json!(self_.network_status.read().await);
+        let config = &self_.config.contracts;
+        let contracts: Vec<_> = [
+            (ContractRole::Main, config.contract_addr),
+            (ContractRole::Governance, config.governance_addr),
+            (ContractRole::Verifier, config.verifier_addr),
+            (
+                ContractRole::UpgradeGatekeeper,
+                config.upgrade_gatekeeper_addr,
+            ),
+            (
+                ContractRole::AdditionalZksync,
+                config.additional_zksync_addr,
+            ),
+            (ContractRole::ForcedExit, config.forced_exit_addr),
+            (ContractRole::MainTarget, config.contract_target_addr),
+            (
+                ContractRole::GovernanceTarget,
+                config.governance_target_addr,
+            ),
+            (ContractRole::VerifierTarget, config.verifier_target_addr),
+        ]
+        .iter()
+        .map(|(role, address)| ContractInfo {
+            role: *role,
+            address: format!("{:?}", address),
+        })
+        .collect();
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "contracts");
+        ok_json!(contracts)
+    }
+
+    pub async fn status(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        if !self_.network_status.is_loaded() {
+            return Err(ApiError::unavailable("Network status is not loaded yet"));
+        }
+        let result = ok_json!(NetworkStatusResponse {
+            status: self_.network_status.read().await,
+            genesis_block_hash: self_.genesis_block_hash.clone(),
+        });
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "status");
         result
     }
 
-    pub async fn tokens(self_: web::Data<Self>) -> ActixResult<HttpResponse> {
+    /// Upgrades the connection to WebSocket streaming the network status updates
+    /// and the transactions of the subscribed accounts, see `StatusSubscriber`.
+    pub async fn ws(
+        self_: web::Data<Self>,
+        req: HttpRequest,
+        stream: web::Payload,
+    ) -> Result<HttpResponse, ApiError> {
+        let subscriber = self_
+            .ws_notifier
+            .connect(self_.network_status.subscribe())
+            .ok_or_else(|| {
+                ApiError::unavailable("Too many WebSocket connections, try again later")
+            })?;
+        ws::start(subscriber, &req, stream).map_err(ApiError::invalid_parameter)
+    }
+
+    pub async fn capabilities(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
-        let mut storage = self_.access_storage().await?;
+        let rest_config = &self_.config.api.rest;
+        let capabilities = CapabilitiesResponse {
+            rpc_api: self_.enabled_apis.rpc,
+            websocket_api: self_.enabled_apis.websocket,
+            web3_api: self_.enabled_apis.web3,
+            forced_exit_requests: self_.config.forced_exit_requests.enabled,
+            rate_limits: rest_config.public_rate_limit_per_sec.is_some()
+                || rest_config.api_key_rate_limit_per_sec.is_some(),
+            api_keys: rest_config.api_keys.iter().any(|key| !key.is_empty()),
+            admin_endpoints: ADMIN_ENDPOINTS.to_vec(),
+            ws_endpoint: WS_ENDPOINT,
+        };
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "capabilities");
+        ok_json!(capabilities)
+    }
+
+    pub async fn server_config(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let server_config = self_.server_config.clone();
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "server_config");
+        ok_json!(server_config)
+    }
+
+    pub async fn tokens(
+        self_: web::Data<Self>,
+        req: HttpRequest,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let token_list = match self_.caches.token_list.get(&()) {
+            Some(token_list) => token_list,
+            // The list is only loaded here until the first background refresh completes.
+            None => {
+                let token_list = self_.load_token_list().await?;
+                self_.caches.token_list.insert((), token_list.clone());
+                token_list
+            }
+        };
+
+        let not_modified = is_not_modified(&req, &token_list.etag);
+        let mut response = if not_modified {
+            HttpResponse::NotModified()
+        } else {
+            HttpResponse::Ok()
+        };
+        response
+            .insert_header((header::ETAG, token_list.etag))
+            .insert_header((header::CACHE_CONTROL, cache_control::TOKEN_LIST));
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tokens");
+        if not_modified {
+            Ok(response.finish())
+        } else {
+            Ok(response
+                .content_type(JSON_CONTENT_TYPE)
+                .body(token_list.body))
+        }
+    }
+
+    /// Returns the decimals of the token with the given ID, address or symbol. Tokens are
+    /// served from the token cache, the database is only accessed for the tokens missing in it.
+    pub async fn token_decimals(
+        self_: web::Data<Self>,
+        token: web::Path<String>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let token_like = TokenLike::parse(&token);
+        let token = match self_
+            .tokens
+            .try_get_token_from_cache(token_like.clone())
+            .await
+        {
+            Some(token) => Some(token),
+            None => {
+                let mut storage = self_.access_storage().await?;
+                self_
+                    .tokens
+                    .get_token(&mut storage, token_like)
+                    .await
+                    .map_err(|err| {
+                        vlog::warn!("Internal Server Error: '{}'; input: {}", err, *token);
+                        ApiError::Storage
+                    })?
+            }
+        };
+        let result = match token {
+            Some(token) => ok_json!(TokenDecimalsResponse {
+                decimals: token.decimals
+            }),
+            None => Err(ApiError::not_found("Token")),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "token_decimals");
+        result
+    }
+
+    /// Reloads the token list into the cache.
+    pub(crate) async fn refresh_token_lists(&self) {
+        match self.load_token_list().await {
+            Ok(token_list) => self.caches.token_list.insert((), token_list),
+            Err(err) => vlog::warn!("Couldn't refresh the token list: {}", err),
+        }
+    }
+
+    async fn load_token_list(&self) -> Result<CachedTokenList, ApiError> {
+        let mut storage = self.access_storage().await?;
         let tokens = storage
             .tokens_schema()
-            .load_tokens()
+            .load_tokens_with_suspension_status()
             .await
             .map_err(Self::db_error)?;
 
-        let mut vec_tokens = tokens.values().cloned().collect::<Vec<_>>();
-        vec_tokens.sort_by_key(|t| t.id);
+        let vec_tokens = tokens
+            .into_iter()
+            .map(TokenResponse::new)
+            .collect::<Vec<_>>();
 
-        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tokens");
-        ok_json!(vec_tokens)
+        CachedTokenList::new(&vec_tokens).map_err(|err| {
+            vlog::warn!("Internal Server Error: '{}';", err);
+            ApiError::Internal
+        })
     }
 
-    pub async fn tokens_acceptable_for_fees(self_: web::Data<Self>) -> ActixResult<HttpResponse> {
+    pub async fn tokens_acceptable_for_fees(
+        self_: web::Data<Self>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
 
         let liquidity_volume = Ratio::from(
@@ -91,14 +301,17 @@ impl ApiV01 {
 
     pub async fn tx_history(
         self_: web::Data<Self>,
+        req: HttpRequest,
         path: web::Path<(Address, u64, u64)>,
-    ) -> ActixResult<HttpResponse> {
+        web::Query(filter_query): web::Query<TxHistoryFilterQuery>,
+    ) -> Result<HttpResponse, ApiError> {
         let (address, mut offset, mut limit) = path.into_inner();
         let start = Instant::now();
-        const MAX_LIMIT: u64 = 100;
-        if limit > MAX_LIMIT {
-            return Ok(HttpResponse::BadRequest().finish());
-        }
+        self_.check_page_limit(limit)?;
+        let timezone = Self::parse_timezone(&req)?;
+        let filter = self_
+            .history_filter(&mut self_.access_storage().await?, &filter_query)
+            .await?;
 
         let tokens = self_
             .access_storage()
@@ -114,7 +327,7 @@ impl ApiV01 {
                     offset,
                     limit,
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         // Fetch ongoing deposits, since they must be reported within the transactions history.
@@ -133,9 +346,10 @@ impl ApiV01 {
                     offset,
                     limit,
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
+        ongoing_ops.retain(|op| Self::pending_deposit_matches(op, &filter));
         // Sort operations by block number from smaller (older) to greater (newer).
         ongoing_ops.sort_by(|lhs, rhs| rhs.eth_block.cmp(&lhs.eth_block));
 
@@ -168,7 +382,7 @@ impl ApiV01 {
             .await?
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_history(&address, offset, limit)
+            .get_account_transactions_history(&address, offset, limit, &filter)
             .await
             .map_err(|err| {
                 vlog::warn!(
@@ -178,31 +392,76 @@ impl ApiV01 {
                     offset,
                     limit,
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         // Append ongoing operations to the end of the end of the list, as the history
         // goes from oldest tx to the newest tx.
         transactions_history.append(&mut ongoing_transactions_history);
 
+        let usd_values: Vec<_> = transactions_history
+            .iter()
+            .map(|item| {
+                item.usd_value_at_time
+                    .as_ref()
+                    .and_then(ToPrimitive::to_f64)
+            })
+            .collect();
+        let transactions_history: Vec<_> =
+            TxHistoryItemResponse::localize(transactions_history, timezone)
+                .into_iter()
+                .zip(usd_values)
+                .map(|(item, usd_value_at_time)| TxHistoryItemWithValue {
+                    item,
+                    usd_value_at_time,
+                })
+                .collect();
+
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history");
         ok_json!(transactions_history)
     }
 
     pub async fn tx_history_older_than(
         self_: web::Data<Self>,
+        req: HttpRequest,
         address: web::Path<Address>,
         web::Query(query): web::Query<TxHistoryQuery>,
-    ) -> ActixResult<HttpResponse> {
+        web::Query(filter_query): web::Query<TxHistoryFilterQuery>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         let tx_id = query.tx_id.as_ref().map(|s| s.as_ref()).unwrap_or("-");
-        let limit = query.limit.unwrap_or(MAX_LIMIT);
-
-        const MAX_LIMIT: u64 = 100;
-        if limit > MAX_LIMIT {
-            return Ok(HttpResponse::BadRequest().finish());
-        }
+        let limit = u64::from(self_.page_limit(query.limit)?);
+        let timezone = Self::parse_timezone(&req)?;
         let mut storage = self_.access_storage().await?;
+        let filter = self_.history_filter(&mut storage, &filter_query).await?;
+
+        if let Some(before_hash) = &query.before_hash {
+            if query.tx_id.is_some() {
+                return Err(ApiError::invalid_parameter(
+                    "`tx_id` and `before_hash` are mutually exclusive",
+                ));
+            }
+            let anchor_hash = before_hash
+                .parse::<TxHash>()
+                .map_err(ApiError::invalid_hash)?;
+            let transactions_history = storage
+                .chain()
+                .operations_ext_schema()
+                .get_account_history_before(&address, anchor_hash, limit, &filter)
+                .await
+                .map_err(Self::db_error)?;
+            let transactions_history = match transactions_history {
+                Some(transactions_history) => transactions_history,
+                None => return Err(ApiError::not_found("Transaction")),
+            };
+
+            metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history_older_than");
+            return ok_json!(TxHistoryItemResponse::localize(
+                transactions_history,
+                timezone
+            ));
+        }
+
         let mut transaction = storage.start_transaction().await.map_err(Self::db_error)?;
 
         let tx_id = parse_tx_id(tx_id, &mut transaction).await?;
@@ -211,7 +470,7 @@ impl ApiV01 {
         let transactions_history = transaction
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_history_from(&address, tx_id, direction, limit)
+            .get_account_transactions_history_from(&address, tx_id, direction, limit, &filter)
             .await
             .map_err(|err| {
                 vlog::warn!(
@@ -221,37 +480,39 @@ impl ApiV01 {
                     tx_id,
                     limit,
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         transaction.commit().await.map_err(Self::db_error)?;
 
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history_older_than");
-        ok_json!(transactions_history)
+        ok_json!(TxHistoryItemResponse::localize(
+            transactions_history,
+            timezone
+        ))
     }
 
     pub async fn tx_history_newer_than(
         self_: web::Data<Self>,
+        req: HttpRequest,
         address: web::Path<Address>,
         web::Query(query): web::Query<TxHistoryQuery>,
-    ) -> ActixResult<HttpResponse> {
+        web::Query(filter_query): web::Query<TxHistoryFilterQuery>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         let tx_id = query.tx_id.as_ref().map(|s| s.as_ref()).unwrap_or("-");
-        let mut limit = query.limit.unwrap_or(MAX_LIMIT);
-
-        const MAX_LIMIT: u64 = 100;
-        if limit > MAX_LIMIT {
-            return Ok(HttpResponse::BadRequest().finish());
-        }
+        let mut limit = u64::from(self_.page_limit(query.limit)?);
+        let timezone = Self::parse_timezone(&req)?;
 
         let direction = SearchDirection::Newer;
-        let mut transactions_history = {
+        let (mut transactions_history, filter) = {
             let mut storage = self_.access_storage().await?;
+            let filter = self_.history_filter(&mut storage, &filter_query).await?;
             let tx_id = parse_tx_id(tx_id, &mut storage).await?;
-            storage
+            let transactions_history = storage
                 .chain()
                 .operations_ext_schema()
-                .get_account_transactions_history_from(&address, tx_id, direction, limit)
+                .get_account_transactions_history_from(&address, tx_id, direction, limit, &filter)
                 .await
                 .map_err(|err| {
                     vlog::warn!(
@@ -261,8 +522,9 @@ impl ApiV01 {
                         tx_id,
                         limit,
                     );
-                    InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-                })?
+                    ApiError::Storage
+                })?;
+            (transactions_history, filter)
         };
 
         limit -= transactions_history.len() as u64;
@@ -285,9 +547,10 @@ impl ApiV01 {
                         tx_id,
                         limit,
                     );
-                    InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                    ApiError::Storage
                 })?;
 
+            ongoing_ops.retain(|op| Self::pending_deposit_matches(op, &filter));
             // Sort operations by block number from smaller (older) to greater (newer).
             ongoing_ops.sort_by(|lhs, rhs| rhs.eth_block.cmp(&lhs.eth_block));
 
@@ -299,7 +562,7 @@ impl ApiV01 {
                     tx_id,
                     limit,
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
             // Collect the unconfirmed priority operations with respect to the
             // `limit` parameters.
@@ -316,21 +579,178 @@ impl ApiV01 {
         }
 
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history_newer_than");
-        ok_json!(transactions_history)
+        ok_json!(TxHistoryItemResponse::localize(
+            transactions_history,
+            timezone
+        ))
+    }
+
+    /// Loads the account history from the oldest transaction to the newest one page by page.
+    /// Unlike the offset-based `tx_history`, pages are not shifted by the new transactions.
+    pub async fn tx_history_page(
+        self_: web::Data<Self>,
+        req: HttpRequest,
+        address: web::Path<Address>,
+        web::Query(query): web::Query<TxHistoryCursorQuery>,
+        web::Query(filter_query): web::Query<TxHistoryFilterQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let cursor = match query.cursor.as_deref().map(str::parse::<HistoryCursor>) {
+            Some(Ok(cursor)) => Some(cursor),
+            Some(Err(_)) => return Err(ApiError::invalid_parameter("invalid cursor")),
+            None => None,
+        };
+        let timezone = Self::parse_timezone(&req)?;
+
+        let mut storage = self_.access_storage().await?;
+        let filter = self_.history_filter(&mut storage, &filter_query).await?;
+        let transactions_history = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_newer_than(
+                &address,
+                cursor.map(|cursor| (cursor.0, cursor.1)),
+                u64::from(limit),
+                &filter,
+            )
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {:?}, {})",
+                    err,
+                    address,
+                    cursor,
+                    limit,
+                );
+                ApiError::Storage
+            })?;
+
+        let next_cursor = transactions_history
+            .last()
+            .and_then(|tx| HistoryCursor::from_tx_id(&tx.tx_id))
+            .or(cursor)
+            .map(HistoryCursor::encode);
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history_page");
+        ok_json!(TxHistoryPage {
+            transactions: TxHistoryItemResponse::localize(transactions_history, timezone),
+            next_cursor,
+        })
+    }
+
+    /// Same as `tx_history_older_than`, but the transactions of every batch are merged
+    /// into a single entry. Batches are only merged within the loaded page, so a batch
+    /// on the page boundary may be split between the adjacent pages.
+    pub async fn tx_history_grouped(
+        self_: web::Data<Self>,
+        req: HttpRequest,
+        address: web::Path<Address>,
+        web::Query(query): web::Query<TxHistoryQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let tx_id = query.tx_id.as_ref().map(|s| s.as_ref()).unwrap_or("-");
+        let limit = u64::from(self_.page_limit(query.limit)?);
+        let timezone = Self::parse_timezone(&req)?;
+        let mut storage = self_.access_storage().await?;
+        let mut transaction = storage.start_transaction().await.map_err(Self::db_error)?;
+
+        let tx_id = parse_tx_id(tx_id, &mut transaction).await?;
+
+        let transactions_history = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_from(
+                &address,
+                tx_id,
+                SearchDirection::Older,
+                limit,
+                &Default::default(),
+            )
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {:?}, {})",
+                    err,
+                    address,
+                    tx_id,
+                    limit,
+                );
+                ApiError::Storage
+            })?;
+
+        transaction.commit().await.map_err(Self::db_error)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_history_grouped");
+        ok_json!(GroupedTxHistoryItem::group(
+            TxHistoryItemResponse::localize(transactions_history, timezone)
+        ))
+    }
+
+    /// Returns the merged transactions history of several accounts, newest first.
+    pub async fn accounts_tx_history(
+        self_: web::Data<Self>,
+        req: HttpRequest,
+        web::Json(request): web::Json<AccountsHistoryRequest>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        const MAX_ADDRESSES: usize = 10;
+        if request.addresses.len() > MAX_ADDRESSES {
+            return Err(ApiError::invalid_parameter("too many addresses"));
+        }
+        self_.check_page_limit(u64::from(request.limit))?;
+        let addresses = request
+            .addresses
+            .iter()
+            .map(|address| parse_checksum_address(address))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ApiError::invalid_address)?;
+        let timezone = Self::parse_timezone(&req)?;
+        let mut storage = self_.access_storage().await?;
+
+        let cursor = match &request.cursor {
+            Some(cursor) => Some(parse_tx_id(cursor, &mut storage).await?),
+            None => None,
+        };
+        let transactions_history = storage
+            .chain()
+            .operations_ext_schema()
+            .get_multi_account_history(&addresses, u64::from(request.limit), cursor, false)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({:?}, {:?}, {})",
+                    err,
+                    addresses,
+                    cursor,
+                    request.limit,
+                );
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "accounts_tx_history");
+        ok_json!(TxHistoryItemResponse::localize(
+            transactions_history,
+            timezone
+        ))
     }
 
     pub async fn executed_tx_by_hash(
         self_: web::Data<Self>,
         tx_hash_hex: web::Path<String>,
-    ) -> ActixResult<HttpResponse> {
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         if tx_hash_hex.len() < 2 {
-            return Ok(HttpResponse::BadRequest().finish());
+            return Err(ApiError::invalid_hash("hash is too short"));
         }
-        let transaction_hash =
-            hex::decode(&tx_hash_hex[2..]).map_err(actix_web::error::ErrorBadRequest)?;
+        let transaction_hash = hex::decode(&tx_hash_hex[2..]).map_err(ApiError::invalid_hash)?;
 
         let tx_receipt = self_.get_tx_receipt(transaction_hash).await?;
+        let last_verified = self_.network_status.last_verified_block();
+        let tx_receipt = tx_receipt.map(|receipt| {
+            let block_number = receipt.block_number as u32;
+            ReceiptWithConfirmations::new(receipt, Some(block_number), last_verified)
+        });
 
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "executed_tx_by_hash");
         ok_json!(tx_receipt)
@@ -339,10 +759,9 @@ impl ApiV01 {
     pub async fn tx_by_hash(
         self_: web::Data<Self>,
         hash_hex_with_prefix: web::Path<String>,
-    ) -> ActixResult<HttpResponse> {
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
-        let hash =
-            try_parse_hash(&hash_hex_with_prefix).map_err(actix_web::error::ErrorBadRequest)?;
+        let hash = try_parse_hash(&hash_hex_with_prefix).map_err(ApiError::invalid_hash)?;
 
         let mut res = self_
             .access_storage()
@@ -357,7 +776,7 @@ impl ApiV01 {
                     err,
                     hex::encode(&hash)
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         // If storage returns Some, return the result.
@@ -375,7 +794,7 @@ impl ApiV01 {
                     err,
                     hex::encode(&hash)
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
         // If eth watcher has a priority op with given hash, transform it
@@ -389,7 +808,7 @@ impl ApiV01 {
                 .await
                 .map_err(|err| {
                     vlog::warn!("Internal Server Error: '{}';", err);
-                    InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                    ApiError::Storage
                 })?;
 
             res = deposit_op_to_tx_by_hash(&tokens, &priority_op);
@@ -402,73 +821,312 @@ impl ApiV01 {
     pub async fn priority_op(
         self_: web::Data<Self>,
         pq_id: web::Path<u32>,
-    ) -> ActixResult<HttpResponse> {
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         let receipt = self_.get_priority_op_receipt(*pq_id).await?;
+        let last_verified = self_.network_status.last_verified_block();
+        let block_number = receipt.verified_in_l2_block;
+        let receipt = ReceiptWithConfirmations::new(receipt, block_number, last_verified);
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "priority_op");
         ok_json!(receipt)
     }
 
+    pub async fn priority_op_confirmations(
+        self_: web::Data<Self>,
+        serial_id: web::Path<u32>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let serial_id = serial_id.into_inner();
+        let mut storage = self_.access_storage().await?;
+
+        let executed_op = storage
+            .chain()
+            .operations_schema()
+            .get_executed_priority_operation(serial_id)
+            .await
+            .map_err(Self::db_error)?;
+        let (l1_block, executed) = match executed_op {
+            Some(op) => (op.eth_block as u64, true),
+            None => {
+                let pending_op = storage
+                    .chain()
+                    .mempool_schema()
+                    .get_pending_operation_by_serial_id(u64::from(serial_id))
+                    .await
+                    .map_err(Self::db_error)?;
+                match pending_op {
+                    Some(op) => (op.eth_block, false),
+                    None => return Err(ApiError::not_found("Priority operation")),
+                }
+            }
+        };
+
+        let current_l1_block = self_.network_status.read().await.monitored_eth_block;
+        let confirmations =
+            current_l1_block.map(|current_block| current_block.saturating_sub(l1_block));
+        let required = self_.config.eth_watch.confirmations_for_eth_event;
+        let confirmed =
+            executed || confirmations.map_or(false, |confirmations| confirmations >= required);
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "priority_op_confirmations");
+        ok_json!(PriorityOpConfirmationsResponse {
+            l1_block,
+            current_l1_block,
+            confirmations,
+            required,
+            confirmed,
+        })
+    }
+
+    pub async fn batch_tx(
+        self_: web::Data<Self>,
+        path: web::Path<(i64, u32)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let (batch_id, index) = path.into_inner();
+        let start = Instant::now();
+        let tx = self_
+            .access_storage()
+            .await?
+            .chain()
+            .operations_ext_schema()
+            .get_batch_transaction(batch_id, index)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {})",
+                    err,
+                    batch_id,
+                    index
+                );
+                ApiError::Storage
+            })?;
+
+        let result = if let Some(tx) = tx {
+            ok_json!(tx)
+        } else {
+            Err(ApiError::not_found("Transaction"))
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "batch_tx");
+        result
+    }
+
+    /// Returns the operation executed in the block at the `tx_id` index.
+    /// Indices greater than `MAX_BLOCK_TX_INDEX` (65535) are always reported as not found.
     pub async fn block_tx(
         self_: web::Data<Self>,
         path: web::Path<(BlockNumber, u32)>,
-    ) -> ActixResult<HttpResponse> {
+    ) -> Result<HttpResponse, ApiError> {
         let (block_id, tx_id) = path.into_inner();
+        if tx_id > MAX_BLOCK_TX_INDEX {
+            return Err(ApiError::not_found("Transaction"));
+        }
         let start = Instant::now();
         let exec_ops = self_.get_block_executed_ops(block_id).await?;
 
         let result = if let Some(exec_op) = exec_ops.get(tx_id as usize) {
             ok_json!(exec_op.clone())
         } else {
-            Ok(HttpResponse::NotFound().finish())
+            Err(ApiError::not_found("Transaction"))
         };
 
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_tx");
         result
     }
 
+    /// Same as `block_tx`, but returns the signed L2 transaction, so the indices of
+    /// priority operations and the ones greater than `MAX_BLOCK_TX_INDEX` are not found.
+    pub async fn block_signed_tx(
+        self_: web::Data<Self>,
+        path: web::Path<(BlockNumber, u32)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let (block_id, tx_id) = path.into_inner();
+        if tx_id > MAX_BLOCK_TX_INDEX {
+            return Err(ApiError::not_found("Transaction"));
+        }
+        let start = Instant::now();
+        let exec_ops = self_.get_block_executed_ops(block_id).await?;
+
+        // Priority operations come from L1 and have no signed L2 payload.
+        let result = match exec_ops.get(tx_id as usize) {
+            Some(ExecutedOperations::Tx(exec_tx)) => {
+                let signed_tx = &exec_tx.signed_tx;
+                ok_json!(SignedTxResponse {
+                    block_number: block_id,
+                    block_index: exec_tx.block_index,
+                    tx_hash: signed_tx.tx.hash(),
+                    tx: signed_tx.tx.clone(),
+                    eth_sign_data: signed_tx.eth_sign_data.clone(),
+                })
+            }
+            _ => Err(ApiError::not_found("Transaction")),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_signed_tx");
+        result
+    }
+
     // pub async fn block_transactions(self_: web::Data<Self>, block_id: BlockNumber) -> !;
     pub async fn blocks(
         self_: web::Data<Self>,
         web::Query(block_query): web::Query<HandleBlocksQuery>,
-    ) -> ActixResult<HttpResponse> {
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
-        let max_block = block_query.max_block.unwrap_or(999_999_999);
-        let limit = block_query.limit.unwrap_or(20);
-        if limit > 100 {
-            return Ok(HttpResponse::BadRequest().finish());
-        }
+        let limit = self_.page_limit(block_query.limit)?;
+        let (query, filter) = match block_query.validate(limit) {
+            Ok(validated) => validated,
+            Err(err) => return Err(ApiError::invalid_parameter(err)),
+        };
         let mut storage = self_.access_storage().await?;
 
         let resp = storage
             .chain()
             .block_schema()
-            .load_block_range_desc(BlockNumber(max_block), limit)
+            .load_filtered_block_page(&query, &filter)
             .await
             .map_err(|err| {
                 vlog::warn!(
-                    "Internal Server Error: '{}'; input: ({}, {})",
+                    "Internal Server Error: '{}'; input: ({}, {}, {:?})",
                     err,
-                    max_block,
-                    limit
+                    *query.from,
+                    limit,
+                    query.direction
                 );
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
+        let mut blocks_fees = self_.get_blocks_fees(&mut storage, &resp).await?;
+        let mut blocks_gas_used = self_.get_blocks_gas_used(&mut storage, &resp).await?;
+        let mut blocks_verifiers = self_.get_blocks_verifiers(&mut storage, &resp).await?;
+        let mut prev_block_hashes = self_.get_prev_block_hashes(&mut storage, &resp).await?;
+        let mut unique_senders = if block_query.include_unique_senders.unwrap_or(false) {
+            self_.get_blocks_unique_senders(&mut storage, &resp).await?
+        } else {
+            HashMap::new()
+        };
+        let resp: Vec<_> = resp
+            .into_iter()
+            .map(|block| {
+                let fees = blocks_fees.remove(&block.block_number).unwrap_or_default();
+                let senders = unique_senders.remove(&block.block_number);
+                let gas_used = blocks_gas_used
+                    .remove(&block.block_number)
+                    .unwrap_or_default();
+                let prev_block_hash = prev_block_hashes.remove(&block.block_number);
+                let verifier_address = blocks_verifiers.remove(&block.block_number);
+                BlockInfo::from(block)
+                    .with_fees(fees)
+                    .with_gas_used(gas_used)
+                    .with_verifier_address(verifier_address)
+                    .with_prev_block_hash(prev_block_hash)
+                    .with_unique_senders(senders)
+            })
+            .collect();
+
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "blocks");
         ok_json!(resp)
     }
 
     pub async fn block_by_id(
         self_: web::Data<Self>,
-        block_id: web::Path<BlockNumber>,
-    ) -> ActixResult<HttpResponse> {
+        req: HttpRequest,
+        block_id: web::Path<String>,
+        web::Query(query): web::Query<BlockByIdQuery>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
-        let block = self_.get_block_info(*block_id).await?;
-        let result = if let Some(block) = block {
-            ok_json!(block)
+        let expansion = query.expansion().map_err(ApiError::invalid_parameter)?;
+        let block_id = match block_id
+            .parse::<BlockId>()
+            .map_err(ApiError::invalid_parameter)?
+        {
+            BlockId::Number(block_number) => block_number,
+            BlockId::StateRoot(root_hash) => {
+                let block_number = self_
+                    .access_storage()
+                    .await?
+                    .chain()
+                    .block_schema()
+                    .get_block_number_by_hash(&root_hash)
+                    .await
+                    .map_err(|err| {
+                        vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                        ApiError::Storage
+                    })?;
+                match block_number {
+                    Some(block_number) => block_number,
+                    None => return Err(ApiError::not_found("Block")),
+                }
+            }
+        };
+
+        let block_info = match self_.get_block_info(block_id).await? {
+            Some(block_info) => block_info,
+            None => return Err(ApiError::not_found("Block")),
+        };
+        let etag = block_etag(&block_info, block_id);
+        if let Some(etag) = etag.as_deref().filter(|etag| is_not_modified(&req, etag)) {
+            metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_by_id");
+            return Ok(HttpResponse::NotModified()
+                .insert_header((header::ETAG, etag))
+                .insert_header((header::CACHE_CONTROL, cache_control::IMMUTABLE))
+                .finish());
+        }
+
+        let block = if expansion.is_empty() {
+            Some(BlockInfo::from(block_info))
         } else {
-            Ok(HttpResponse::NotFound().finish())
+            self_
+                .access_storage()
+                .await?
+                .chain()
+                .block_schema()
+                .load_expanded_block_details(block_id, &expansion)
+                .await
+                .map_err(|err| {
+                    vlog::warn!("Internal Server Error: '{}'; input: {}", err, block_id);
+                    ApiError::Storage
+                })?
+                .map(|block| BlockInfo::new(block, &expansion))
+        };
+        let block = match block {
+            Some(block) => {
+                let mut storage = self_.access_storage().await?;
+                let fees = self_
+                    .get_blocks_fees(&mut storage, std::slice::from_ref(&block.details))
+                    .await?
+                    .remove(&block.details.block_number)
+                    .unwrap_or_default();
+                let gas_used = self_
+                    .get_blocks_gas_used(&mut storage, std::slice::from_ref(&block.details))
+                    .await?
+                    .remove(&block.details.block_number)
+                    .unwrap_or_default();
+                let prev_block_hash = self_
+                    .get_prev_block_hashes(&mut storage, std::slice::from_ref(&block.details))
+                    .await?
+                    .remove(&block.details.block_number);
+                let verifier_address = self_
+                    .get_blocks_verifiers(&mut storage, std::slice::from_ref(&block.details))
+                    .await?
+                    .remove(&block.details.block_number);
+                Some(
+                    block
+                        .with_fees(fees)
+                        .with_gas_used(gas_used)
+                        .with_verifier_address(verifier_address)
+                        .with_prev_block_hash(prev_block_hash),
+                )
+            }
+            None => None,
+        };
+        let result = match (block, etag) {
+            // Verified blocks never change, so they can be cached forever.
+            (Some(block), Some(etag)) => Ok(HttpResponse::Ok()
+                .insert_header((header::ETAG, etag))
+                .insert_header((header::CACHE_CONTROL, cache_control::IMMUTABLE))
+                .json_utf8(block)),
+            (Some(block), None) => ok_json!(block),
+            (None, _) => Err(ApiError::not_found("Block")),
         };
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_by_id");
         result
@@ -476,76 +1134,1506 @@ impl ApiV01 {
 
     pub async fn block_transactions(
         self_: web::Data<Self>,
+        req: HttpRequest,
         block_id: web::Path<BlockNumber>,
-    ) -> ActixResult<HttpResponse> {
+        web::Query(query): web::Query<BlockTransactionsQuery>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
+        let etag = self_
+            .get_block_info(*block_id)
+            .await?
+            .and_then(|block_info| block_etag(&block_info, *block_id));
+        if let Some(etag) = etag.as_deref().filter(|etag| is_not_modified(&req, etag)) {
+            metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_transactions");
+            return Ok(HttpResponse::NotModified()
+                .insert_header((header::ETAG, etag))
+                .finish());
+        }
+
         let mut storage = self_.access_storage().await?;
 
         let txs = storage
             .chain()
             .block_schema()
-            .get_block_transactions(*block_id)
+            .get_block_transactions(*block_id, query.op_type.as_deref())
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                ApiError::Storage
+            })?;
+        // Operations are needed in the order of their execution to locate them in the public data.
+        let ops = storage
+            .chain()
+            .block_schema()
+            .get_block_executed_ops(*block_id)
             .await
             .map_err(|err| {
                 vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                ApiError::Storage
             })?;
 
+        let chunk_positions: HashMap<u32, (u32, u32)> = compute_chunk_offsets(&ops)
+            .into_iter()
+            .zip(&ops)
+            .filter(|(_, op)| op.is_successful())
+            .filter_map(|(position, op)| {
+                let block_index = match op {
+                    ExecutedOperations::Tx(tx) => tx.block_index?,
+                    ExecutedOperations::PriorityOp(op) => op.block_index,
+                };
+                Some((block_index, position))
+            })
+            .collect();
+        let txs: Vec<_> = txs
+            .into_iter()
+            .map(|tx| {
+                let position = tx
+                    .block_index
+                    .filter(|_| tx.success)
+                    .and_then(|block_index| chunk_positions.get(&(block_index as u32)));
+                BlockTransactionResponse {
+                    chunk_offset: position.map(|(offset, _)| *offset),
+                    chunk_size: position.map(|(_, size)| *size),
+                    tx,
+                }
+            })
+            .collect();
+
         metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_transactions");
-        ok_json!(txs)
+        match etag {
+            Some(etag) => Ok(HttpResponse::Ok()
+                .insert_header((header::ETAG, etag))
+                .json_utf8(txs)),
+            None => ok_json!(txs),
+        }
     }
 
-    pub async fn explorer_search(
+    pub async fn block_rejected_txs(
         self_: web::Data<Self>,
-        web::Query(block_query): web::Query<BlockExplorerSearchQuery>,
-    ) -> ActixResult<HttpResponse> {
-        let start = Instant::now();
-        let block = self_.get_block_by_height_or_hash(block_query.query).await?;
-
-        let result = if let Some(block) = block {
-            ok_json!(block)
-        } else {
-            Ok(HttpResponse::NotFound().finish())
-        };
-
-        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "explorer_search");
-        result
-    }
-
-    pub async fn withdrawal_processing_time(self_: web::Data<Self>) -> ActixResult<HttpResponse> {
+        block_id: web::Path<BlockNumber>,
+    ) -> Result<HttpResponse, ApiError> {
         let start = Instant::now();
         let mut storage = self_.access_storage().await?;
-        let block_number = storage
+
+        let block = storage
             .chain()
             .block_schema()
-            .get_last_saved_block()
+            .get_storage_block(*block_id)
             .await
             .map_err(|err| {
-                vlog::warn!("Internal Server Error: '{}';", err,);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                ApiError::Storage
             })?;
-        let block = storage
+        if block.is_none() {
+            return Err(ApiError::not_found("Block"));
+        }
+
+        let txs = storage
             .chain()
             .block_schema()
-            .get_block(block_number)
+            .get_rejected_txs(*block_id)
             .await
             .map_err(|err| {
-                vlog::warn!("Internal Server Error: '{}';", err);
-                InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-            })?
-            .expect("Should exist");
-        let state_keeper_config = &self_.config.chain.state_keeper;
-        let average_proof_generating_time = Duration::minutes(30);
-        let normal = block.timestamp_utc()
-            + Duration::from_std(state_keeper_config.block_execute_deadline()).unwrap()
-            + average_proof_generating_time * 2i32;
-        let fast = block.timestamp_utc() + average_proof_generating_time * 2i32;
-        let processing_time = WithdrawalProcessingTimeResponse {
-            normal: normal.timestamp() as u64,
-            fast: fast.timestamp() as u64,
-        };
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                ApiError::Storage
+            })?;
 
-        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "withdrawal_processing_time");
-        ok_json!(processing_time)
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_rejected_txs");
+        ok_json!(txs)
+    }
+
+    /// Returns the latest rejected transactions of all the blocks with their rejection reasons.
+    pub async fn rejected_txs(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<RejectedTxsQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let mut storage = self_.access_storage().await?;
+
+        let txs = storage
+            .chain()
+            .operations_ext_schema()
+            .load_recent_rejected_txs(limit)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, limit);
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "rejected_txs");
+        ok_json!(txs)
+    }
+
+    pub async fn mempool_txs(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<MempoolTxsQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let mut storage = self_.access_storage().await?;
+
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .load_pending_txs(limit)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, limit);
+                ApiError::Storage
+            })?;
+        let txs: Vec<_> = txs.into_iter().map(MempoolTxEntry::from).collect();
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "mempool_txs");
+        ok_json!(txs)
+    }
+
+    pub async fn mempool_count(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+
+        let count = storage
+            .chain()
+            .mempool_schema()
+            .get_pending_txs_count()
+            .await
+            .map_err(Self::db_error)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "mempool_count");
+        ok_json!(count)
+    }
+
+    pub async fn block_pubdata(
+        self_: web::Data<Self>,
+        block_id: web::Path<BlockNumber>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(*block_id)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                ApiError::Storage
+            })?;
+
+        let result = match block {
+            Some(block) => ok_json!(BlockPubdataResponse {
+                block_number: block.block_number,
+                pubdata: format!("0x{}", hex::encode(block.get_eth_public_data())),
+            }),
+            None => Err(ApiError::not_found("Block")),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_pubdata");
+        result
+    }
+
+    pub async fn block_fee_recipient(
+        self_: web::Data<Self>,
+        block_id: web::Path<BlockNumber>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_storage_block(*block_id)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *block_id);
+                ApiError::Storage
+            })?;
+
+        let result = match block {
+            Some(block) => {
+                let fee_account_id = AccountId(block.fee_account_id as u32);
+                let fee_account_address = storage
+                    .chain()
+                    .account_schema()
+                    .account_address_by_id(fee_account_id)
+                    .await
+                    .map_err(Self::db_error)?;
+                ok_json!(BlockFeeRecipientResponse {
+                    block_number: *block_id,
+                    fee_account_id,
+                    fee_account_address,
+                })
+            }
+            None => Err(ApiError::not_found("Block")),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_fee_recipient");
+        result
+    }
+
+    pub async fn explorer_search(
+        self_: web::Data<Self>,
+        web::Query(block_query): web::Query<BlockExplorerSearchQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        // Numeric queries may also be account IDs.
+        let account_id = block_query.query.parse().ok().map(AccountId);
+        let block = self_.get_block_by_height_or_hash(block_query.query).await?;
+
+        // Blocks take precedence and are reported as is, so that the response doesn't change
+        // for the clients unaware of the accounts search.
+        let result = match (block, account_id) {
+            (Some(block), _) => ok_json!(block),
+            (None, Some(account_id)) => {
+                let address = self_
+                    .access_storage()
+                    .await?
+                    .chain()
+                    .account_schema()
+                    .account_address_by_id(account_id)
+                    .await
+                    .map_err(Self::db_error)?;
+                match address {
+                    Some(address) => ok_json!(SearchResult::Account {
+                        id: account_id,
+                        address,
+                    }),
+                    None => Err(ApiError::not_found("Block or account")),
+                }
+            }
+            (None, None) => Err(ApiError::not_found("Block or account")),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "explorer_search");
+        result
+    }
+
+    pub async fn withdrawal_processing_time(
+        self_: web::Data<Self>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+        let block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}';", err,);
+                ApiError::Storage
+            })?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}';", err);
+                ApiError::Storage
+            })?
+            .expect("Should exist");
+        let state_keeper_config = &self_.config.chain.state_keeper;
+        let average_proof_generating_time = Duration::minutes(30);
+        let normal = block.timestamp_utc()
+            + Duration::from_std(state_keeper_config.block_execute_deadline()).unwrap()
+            + average_proof_generating_time * 2i32;
+        let fast = block.timestamp_utc() + average_proof_generating_time * 2i32;
+        let processing_time = WithdrawalProcessingTimeResponse {
+            normal: normal.timestamp() as u64,
+            fast: fast.timestamp() as u64,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "withdrawal_processing_time");
+        ok_json!(processing_time)
+    }
+
+    pub async fn gas_policy(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+        let average_gas_price = storage
+            .ethereum_schema()
+            .load_average_gas_price()
+            .await
+            .map_err(Self::db_error)?;
+        let gas_price_limit = storage
+            .ethereum_schema()
+            .load_gas_price_limit()
+            .await
+            .map_err(Self::db_error)?;
+
+        let gas_limit_config = &self_.config.eth_sender.gas_price_limit;
+        let gas_policy = GasPolicyResponse {
+            target_gas_price_wei: average_gas_price.map(|price| price.to_string()),
+            gas_price_limit_wei: gas_price_limit.to_string(),
+            limit_update_interval_secs: gas_limit_config.update_interval,
+            limit_scale_factor: gas_limit_config.scale_factor,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "gas_policy");
+        ok_json!(gas_policy)
+    }
+
+    pub async fn balances_snapshot(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (block_number, balances) = self_.spawn_balances_snapshot_stream().await?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "balances_snapshot");
+        Ok(HttpResponse::Ok()
+            .content_type("text/csv")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"balances_snapshot_{}.csv\"",
+                    *block_number
+                ),
+            ))
+            .insert_header((header::CACHE_CONTROL, "no-store"))
+            .streaming(balances))
+    }
+
+    pub async fn tx_type_breakdown(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<TxTypeBreakdownQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        if query.from_block > query.to_block {
+            return Err(ApiError::invalid_parameter(
+                "`from_block` is greater than `to_block`",
+            ));
+        }
+        let mut storage = self_.access_storage().await?;
+
+        let counts = storage
+            .chain()
+            .stats_schema()
+            .count_operations_by_type(query.from_block, query.to_block)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {})",
+                    err,
+                    *query.from_block,
+                    *query.to_block
+                );
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "tx_type_breakdown");
+        ok_json!(TxTypeBreakdownResponse {
+            from_block: query.from_block,
+            to_block: query.to_block,
+            counts,
+        })
+    }
+
+    pub async fn priority_ops_stats(self_: web::Data<Self>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let mut storage = self_.access_storage().await?;
+
+        let processed = storage
+            .chain()
+            .block_schema()
+            .next_expected_serial_id()
+            .await
+            .map_err(Self::db_error)?;
+        let (pending, unconfirmed) = storage
+            .chain()
+            .stats_schema()
+            .count_pending_priority_operations()
+            .await
+            .map_err(Self::db_error)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "priority_ops_stats");
+        ok_json!(PriorityOpsStatsResponse {
+            total: processed + pending,
+            processed,
+            pending,
+            unconfirmed,
+        })
+    }
+
+    pub async fn fee_token_usage(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<FeeTokenUsageQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        query.validate().map_err(ApiError::invalid_parameter)?;
+        let mut storage = self_.access_storage().await?;
+
+        let tokens = storage
+            .chain()
+            .stats_schema()
+            .load_fee_token_usage(query.from_block, query.to_block)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {})",
+                    err,
+                    *query.from_block,
+                    *query.to_block
+                );
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "fee_token_usage");
+        ok_json!(FeeTokenUsageResponse {
+            from_block: query.from_block,
+            to_block: query.to_block,
+            tokens: tokens.into_iter().map(FeeTokenUsage::from).collect(),
+        })
+    }
+
+    pub async fn block_times(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<BlockTimesQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let window = query.window().map_err(ApiError::invalid_parameter)?;
+        let window_secs = window.as_secs();
+
+        if let Some(block_times) = self_
+            .caches
+            .block_times
+            .get(&window_secs)
+            .filter(CachedBlockTimes::is_fresh)
+        {
+            metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_times");
+            return ok_json!(block_times.response);
+        }
+
+        let since = chrono::Utc::now().timestamp() - window_secs as i64;
+        let intervals = self_
+            .access_storage()
+            .await?
+            .chain()
+            .stats_schema()
+            .load_block_intervals(since)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, window_secs);
+                ApiError::Storage
+            })?;
+
+        let response = BlockTimesResponse::new(window, &intervals);
+        self_
+            .caches
+            .block_times
+            .insert(window_secs, CachedBlockTimes::new(response.clone()));
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "block_times");
+        ok_json!(response)
+    }
+
+    pub async fn prover_latency(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<ProverLatencyQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let bucket_size = match query.validate() {
+            Ok(bucket_size) => bucket_size,
+            Err(err) => return Err(ApiError::invalid_parameter(err)),
+        };
+
+        let buckets = self_
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .get_proof_latency_buckets(
+                BlockNumber(query.from_block),
+                BlockNumber(query.to_block),
+                bucket_size,
+            )
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {}, {:?})",
+                    err,
+                    query.from_block,
+                    query.to_block,
+                    bucket_size
+                );
+                ApiError::Storage
+            })?;
+        let buckets: Vec<_> = buckets.into_iter().map(LatencyBucket::from).collect();
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "prover_latency");
+        ok_json!(buckets)
+    }
+
+    pub async fn proving_times(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<BlockTimesQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let window = query.window().map_err(ApiError::invalid_parameter)?;
+        let window_secs = window.as_secs();
+
+        if let Some(proving_times) = self_
+            .caches
+            .proving_times
+            .get(&window_secs)
+            .filter(CachedProvingTimes::is_fresh)
+        {
+            metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "proving_times");
+            return ok_json!(proving_times.response);
+        }
+
+        let since = Utc::now() - Duration::seconds(window_secs as i64);
+        let proving_times = self_
+            .access_storage()
+            .await?
+            .chain()
+            .stats_schema()
+            .load_proving_times(since)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, window_secs);
+                ApiError::Storage
+            })?;
+
+        let response = ProvingTimesResponse::new(window, &proving_times);
+        self_
+            .caches
+            .proving_times
+            .insert(window_secs, CachedProvingTimes::new(response.clone()));
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "proving_times");
+        ok_json!(response)
+    }
+
+    pub async fn fee_schedule(
+        self_: web::Data<Self>,
+        web::Query(query): web::Query<FeeScheduleQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let token = TokenLike::Id(query.token_id);
+        let allowed = self_
+            .fee_ticker
+            .token_allowed_for_fees(token.clone())
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: {}",
+                    err,
+                    *query.token_id
+                );
+                ApiError::Internal
+            })?;
+        if !allowed {
+            return Err(ApiError::invalid_parameter("token is not allowed for fees"));
+        }
+
+        // The fee account always exists, so the fee is calculated for a transfer to the existing account.
+        let recipient = self_.config.chain.state_keeper.fee_account_addr;
+        let fee = self_
+            .fee_ticker
+            .get_fee_from_ticker_in_wei(TxFeeTypes::Transfer, token, recipient)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: {}",
+                    err,
+                    *query.token_id
+                );
+                ApiError::Internal
+            })?
+            .normal_fee;
+
+        // The required fee is enough to get the transaction into one of the next blocks.
+        let slow_multiplier = Ratio::from_integer(BigUint::from(1u32));
+        // Same margin the server tolerates for the token price movements between
+        // the fee request and the transaction submission.
+        let standard_multiplier = Ratio::new(BigUint::from(105u32), BigUint::from(100u32));
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "fee_schedule");
+        ok_json!(FeeScheduleResponse {
+            slow: FeeBreakdown::new(&fee, &slow_multiplier, 10),
+            standard: FeeBreakdown::new(&fee, &standard_multiplier, 3),
+        })
+    }
+
+    /// Returns the state of the account. Address is accepted either in the single case
+    /// or in the EIP-55 mixed-case encoding, in the latter case the checksum is validated.
+    pub async fn account_state(
+        self_: web::Data<Self>,
+        address: web::Path<String>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let address = parse_checksum_address(&address).map_err(ApiError::invalid_address)?;
+        let mut storage = self_.access_storage().await?;
+        let state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                ApiError::Storage
+            })?;
+        let result = Self::account_state_response(&mut storage, address, state).await;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_state");
+        result
+    }
+
+    /// Returns the state of the account with the given ID in the same format as `/account/{address}`.
+    /// Account ID is the index of the account leaf in the Merkle tree, it's assigned once the first
+    /// transfer to the address is committed and never changes afterwards.
+    pub async fn account_state_by_id(
+        self_: web::Data<Self>,
+        account_id: web::Path<u32>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let account_id = AccountId(account_id.into_inner());
+        let mut storage = self_.access_storage().await?;
+        let state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_id(account_id)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, account_id);
+                ApiError::Storage
+            })?;
+        let address = match &state.committed {
+            Some((_, account)) => account.address,
+            None => return Err(ApiError::not_found("Account")),
+        };
+        let result = Self::account_state_response(&mut storage, address, state).await;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_state_by_id");
+        result
+    }
+
+    /// Builds the response of the account state endpoints from the loaded state of the account.
+    async fn account_state_response(
+        storage: &mut StorageProcessor<'_>,
+        address: Address,
+        state: StoredAccountState,
+    ) -> Result<HttpResponse, ApiError> {
+        let is_new_account = state.committed.is_none();
+        let init_fee_hint = if is_new_account {
+            Some(Self::account_creation_fee_hint(storage).await?)
+        } else {
+            None
+        };
+
+        let last_updates = storage
+            .chain()
+            .account_schema()
+            .get_account_last_updates(address)
+            .await
+            .map_err(Self::db_error)?;
+
+        let mut response = AccountStateResponse::new(state, init_fee_hint, last_updates);
+        if let Some(id) = response.id {
+            let two_fa = storage
+                .chain()
+                .account_schema()
+                .get_account_2fa(id)
+                .await
+                .map_err(Self::db_error)?;
+            response = response.with_2fa(two_fa);
+        }
+
+        let mut builder = HttpResponse::Ok();
+        if !is_new_account && !response.pubkey_registered {
+            builder.insert_header((LEGACY_SIGNATURE_WARNING_HEADER, LEGACY_SIGNATURE_WARNING));
+        }
+        Ok(builder.json_utf8(response))
+    }
+
+    /// Returns the latest deposits to the account along with the number of their L1 confirmations.
+    pub async fn account_deposits_history(
+        self_: web::Data<Self>,
+        address: web::Path<String>,
+        web::Query(query): web::Query<AccountDepositsQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let address = parse_checksum_address(&address).map_err(ApiError::invalid_address)?;
+        let mut storage = self_.access_storage().await?;
+
+        let deposits = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_deposits(address, limit)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {})",
+                    err,
+                    address,
+                    limit
+                );
+                ApiError::Storage
+            })?;
+        let current_eth_block = self_.network_status.read().await.monitored_eth_block;
+        let deposits: Vec<_> = deposits
+            .into_iter()
+            .map(|deposit| DepositEntry::new(deposit, current_eth_block))
+            .collect();
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_deposits_history");
+        ok_json!(deposits)
+    }
+
+    /// Returns the `ForcedExit` transactions the account has initiated against the other accounts.
+    pub async fn forced_exits_initiated(
+        self_: web::Data<Self>,
+        address: web::Path<String>,
+        web::Query(query): web::Query<ForcedExitsQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let offset = query.offset.unwrap_or(0);
+        let address = parse_checksum_address(&address).map_err(ApiError::invalid_address)?;
+        let filter = query.status.unwrap_or(ForcedExitsStatusFilter::All);
+        let mut storage = self_.access_storage().await?;
+
+        let forced_exits = storage
+            .chain()
+            .operations_ext_schema()
+            .get_forced_exits_by_initiator(address, filter.into(), offset, limit)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {:?}, {}, {})",
+                    err,
+                    address,
+                    filter,
+                    offset,
+                    limit
+                );
+                ApiError::Storage
+            })?;
+        let forced_exits: Vec<_> = forced_exits
+            .into_iter()
+            .map(ForcedExitEntry::from)
+            .collect();
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "forced_exits_initiated");
+        ok_json!(forced_exits)
+    }
+
+    /// Returns all the operations which changed the account state, newest first.
+    /// The events are loaded in pages of the account history, so the pages may contain fewer
+    /// events than requested; the last page is the one without the `next_cursor`.
+    pub async fn account_timeline(
+        self_: web::Data<Self>,
+        address: web::Path<String>,
+        web::Query(query): web::Query<AccountTimelineQuery>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let limit = self_.page_limit(query.limit)?;
+        let address = parse_checksum_address(&address).map_err(ApiError::invalid_address)?;
+        let mut storage = self_.access_storage().await?;
+
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(parse_tx_id(cursor, &mut storage).await?),
+            None => None,
+        };
+        let history = storage
+            .chain()
+            .operations_ext_schema()
+            .get_multi_account_history(&[address], u64::from(limit), cursor, true)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {:?}, {})",
+                    err,
+                    address,
+                    cursor,
+                    limit,
+                );
+                ApiError::Storage
+            })?;
+        // The page may contain fewer events than history items, so the cursor is
+        // the last loaded item rather than the last event.
+        let next_cursor = if history.len() == limit as usize {
+            history.last().map(|item| item.tx_id.clone())
+        } else {
+            None
+        };
+        let events = history
+            .into_iter()
+            .filter_map(|item| AccountTimelineEvent::from_history_item(item, address))
+            .collect();
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_timeline");
+        ok_json!(AccountTimelineResponse {
+            events,
+            next_cursor
+        })
+    }
+
+    /// Checks whether the string is a well-formed address the same way the account
+    /// endpoints do, without accessing the database.
+    pub async fn validate_address(address: web::Path<String>) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let canonical = parse_checksum_address(&address)
+            .ok()
+            .map(|address| to_checksum_address(&address));
+        let response = AddressValidationResponse {
+            valid: canonical.is_some(),
+            canonical,
+        };
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "validate_address");
+        // The result only depends on the requested string.
+        Ok(HttpResponse::Ok()
+            .insert_header((header::CACHE_CONTROL, cache_control::IMMUTABLE))
+            .json_utf8(response))
+    }
+
+    pub async fn accounts_by_id(
+        self_: web::Data<Self>,
+        web::Json(request): web::Json<AccountsByIdRequest>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        if request.ids.len() > 100 {
+            return Err(ApiError::invalid_parameter("too many account IDs"));
+        }
+        let mut storage = self_.access_storage().await?;
+
+        let mut states = storage
+            .chain()
+            .account_schema()
+            .account_states_by_ids(&request.ids)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {:?}", err, request.ids);
+                ApiError::Storage
+            })?;
+
+        // Accounts that don't exist are reported with the default state, same as for lookups by address.
+        let init_fee_hint = if request.ids.iter().any(|id| !states.contains_key(id)) {
+            Some(Self::account_creation_fee_hint(&mut storage).await?)
+        } else {
+            None
+        };
+        let addresses: Vec<Address> = states
+            .values()
+            .filter_map(|state| state.committed.as_ref())
+            .map(|(_, account)| account.address)
+            .collect();
+        let mut last_updates = storage
+            .chain()
+            .account_schema()
+            .get_accounts_last_updates(&addresses)
+            .await
+            .map_err(Self::db_error)?;
+        let existing_ids: Vec<AccountId> = states.keys().copied().collect();
+        let mut two_fa = storage
+            .chain()
+            .account_schema()
+            .get_accounts_2fa(&existing_ids)
+            .await
+            .map_err(Self::db_error)?;
+
+        let mut response = HashMap::with_capacity(request.ids.len());
+        for id in request.ids {
+            let account_state = match states.remove(&id) {
+                Some(state) => {
+                    let address = state
+                        .committed
+                        .as_ref()
+                        .map(|(_, account)| account.address)
+                        .unwrap_or_default();
+                    let last_updates = last_updates.remove(&address).unwrap_or_default();
+                    // Accounts without a stored type are the owned ones.
+                    let two_fa = two_fa.remove(&id).unwrap_or(Account2FA::Required);
+                    AccountStateResponse::new(state, None, last_updates).with_2fa(two_fa)
+                }
+                None if response.contains_key(&id) => continue,
+                None => AccountStateResponse::new(
+                    StoredAccountState {
+                        committed: None,
+                        verified: None,
+                    },
+                    init_fee_hint.clone(),
+                    AccountLastUpdates::default(),
+                ),
+            };
+            response.insert(id, account_state);
+        }
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "accounts_by_id");
+        ok_json!(response)
+    }
+
+    /// Returns the balances summed over the given accounts. Repeated addresses are counted once,
+    /// NFTs are not counted.
+    pub async fn aggregate_balance(
+        self_: web::Data<Self>,
+        web::Json(request): web::Json<AggregateBalanceRequest>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        const MAX_ADDRESSES: usize = 100;
+        if request.addresses.len() > MAX_ADDRESSES {
+            return Err(ApiError::invalid_parameter("too many addresses"));
+        }
+        let addresses = request
+            .addresses
+            .iter()
+            .map(|address| parse_checksum_address(address))
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(ApiError::invalid_address)?;
+        let addresses: Vec<_> = addresses.into_iter().collect();
+        let mut storage = self_.access_storage().await?;
+        // Accounts are loaded in a single snapshot, so that the blocks committed in the meantime
+        // don't make the sums combine the states of different blocks.
+        let mut transaction = storage
+            .start_snapshot_transaction()
+            .await
+            .map_err(Self::db_error)?;
+        let account_ids: Vec<_> = transaction
+            .chain()
+            .account_schema()
+            .account_ids_by_addresses(&addresses)
+            .await
+            .map_err(Self::db_error)?
+            .into_values()
+            .collect();
+        let states = transaction
+            .chain()
+            .account_schema()
+            .account_states_by_ids(&account_ids)
+            .await
+            .map_err(Self::db_error)?;
+        transaction.commit().await.map_err(Self::db_error)?;
+
+        let mut committed = BTreeMap::<TokenId, BigUint>::new();
+        let mut verified = BTreeMap::<TokenId, BigUint>::new();
+        for state in states.into_values() {
+            for (total, account) in [
+                (&mut committed, state.committed),
+                (&mut verified, state.verified),
+            ] {
+                let balances = account
+                    .map(|(_, account)| account.get_nonzero_balances())
+                    .unwrap_or_default();
+                for (token_id, balance) in balances {
+                    if token_id.0 < MIN_NFT_TOKEN_ID {
+                        *total.entry(token_id).or_default() += balance.0;
+                    }
+                }
+            }
+        }
+
+        let to_strings = |balances: BTreeMap<TokenId, BigUint>| {
+            balances
+                .into_iter()
+                .map(|(token_id, balance)| (token_id, balance.to_string()))
+                .collect()
+        };
+        let response = AggregateBalanceResponse {
+            committed: to_strings(committed),
+            verified: to_strings(verified),
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "aggregate_balance");
+        ok_json!(response)
+    }
+
+    /// Returns the nonces of the account without the rest of its state, so that wallets are able
+    /// to sign the next transaction cheaply. Nonces of the unknown accounts are zero, so that the
+    /// first transaction of the account can be built as well.
+    pub async fn account_nonce(
+        self_: web::Data<Self>,
+        address: web::Path<String>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let address = parse_checksum_address(&address).map_err(ApiError::invalid_address)?;
+        let state = self_
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .account_state_by_address(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                ApiError::Storage
+            })?;
+        let nonce = |state: Option<(AccountId, Account)>| {
+            state.map(|(_, account)| account.nonce).unwrap_or_default()
+        };
+
+        let current_block = self_.network_status.read().await.last_committed;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_nonce");
+        ok_json!(AccountNonceResponse {
+            committed: nonce(state.committed),
+            verified: nonce(state.verified),
+            current_block,
+        })
+    }
+
+    pub async fn nonce_used(
+        self_: web::Data<Self>,
+        path: web::Path<(Address, u32)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let (address, nonce) = path.into_inner();
+        let start = Instant::now();
+        let nonce = Nonce(nonce);
+        let tx_hash = self_
+            .access_storage()
+            .await?
+            .chain()
+            .operations_ext_schema()
+            .get_tx_hash_by_nonce(&address, nonce)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: ({}, {})",
+                    err,
+                    address,
+                    *nonce
+                );
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "nonce_used");
+        ok_json!(NonceUsedResponse {
+            nonce,
+            used: tx_hash.is_some(),
+            tx_hash,
+        })
+    }
+
+    pub async fn latest_change_pubkey(
+        self_: web::Data<Self>,
+        address: web::Path<Address>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let change_pubkey = self_
+            .access_storage()
+            .await?
+            .chain()
+            .operations_ext_schema()
+            .get_latest_change_pubkey(&address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *address);
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "latest_change_pubkey");
+        match change_pubkey {
+            Some(change_pubkey) => ok_json!(ChangePubKeyResponse::from(change_pubkey)),
+            None => Err(ApiError::not_found("ChangePubKey transaction")),
+        }
+    }
+
+    pub async fn account_activity_span(
+        self_: web::Data<Self>,
+        address: web::Path<Address>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let span = self_
+            .access_storage()
+            .await?
+            .chain()
+            .operations_ext_schema()
+            .account_activity_span(&address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, *address);
+                ApiError::Storage
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_activity_span");
+        ok_json!(AccountActivitySpanResponse {
+            first_tx_at: span.first_tx_at,
+            last_tx_at: span.last_tx_at,
+            tx_count: span.tx_count as u64,
+        })
+    }
+
+    pub async fn exit_proof(
+        self_: web::Data<Self>,
+        path: web::Path<(Address, String)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (address, token) = path.into_inner();
+        let mut storage = self_.access_storage().await?;
+
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::parse(&token))
+            .await
+            .map_err(Self::db_error)?;
+        let token = match token {
+            Some(token) => token,
+            None => return Err(ApiError::not_found("Token")),
+        };
+
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Self::db_error)?;
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => return Err(ApiError::not_found("Account")),
+        };
+        drop(storage);
+
+        let response = self_.exit_proof_response(account_id, token).await?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "exit_proof");
+        response
+    }
+
+    pub async fn exit_proof_by_id(
+        self_: web::Data<Self>,
+        path: web::Path<(AccountId, TokenId)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (account_id, token_id) = path.into_inner();
+        let mut storage = self_.access_storage().await?;
+
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(token_id))
+            .await
+            .map_err(Self::db_error)?;
+        let token = match token {
+            Some(token) => token,
+            None => return Err(ApiError::not_found("Token")),
+        };
+        drop(storage);
+
+        let response = self_.exit_proof_response(account_id, token).await?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "exit_proof_by_id");
+        response
+    }
+
+    /// Returns the proofs of the account balances against the state root of the given block,
+    /// so that the client can check them against the root accepted on L1.
+    /// Blocks which are not verified yet are rejected with `409 Conflict`.
+    pub async fn account_proof_at(
+        self_: web::Data<Self>,
+        path: web::Path<(Address, BlockNumber)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (address, block_number) = path.into_inner();
+        if *block_number == 0 {
+            return Err(ApiError::not_found("Block"));
+        }
+        let mut storage = self_.access_storage().await?;
+
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Self::db_error)?;
+        if block_number > last_verified_block {
+            return Err(ApiError::conflict("Block is not verified yet"));
+        }
+
+        let state = Self::account_state_at_block(&mut storage, address, block_number).await?;
+        let (account_id, accounts) = match state {
+            Some(state) => state,
+            None => return Err(ApiError::not_found("Account")),
+        };
+
+        // Building the whole account tree is CPU-heavy, so it shouldn't block the executor.
+        let proof = tokio::task::spawn_blocking(move || {
+            account_balances_proof(accounts, block_number, account_id)
+        })
+        .await
+        .map_err(|err| {
+            vlog::warn!(
+                "Internal Server Error: '{}'; input: {}, {}",
+                err,
+                address,
+                *block_number
+            );
+            ApiError::Internal
+        })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_proof_at");
+        ok_json!(proof)
+    }
+
+    /// Suspends the token, so that the transactions using it are rejected by the API.
+    pub async fn suspend_token(
+        self_: web::Data<Self>,
+        token_id: web::Path<TokenId>,
+        web::Json(request): web::Json<SuspendTokenRequest>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let token_id = token_id.into_inner();
+        let mut storage = self_.access_storage().await?;
+        self_.check_token_exists(&mut storage, token_id).await?;
+
+        storage
+            .tokens_schema()
+            .suspend_token(token_id, request.reason.as_deref())
+            .await
+            .map_err(Self::db_error)?;
+        drop(storage);
+        self_.refresh_token_lists().await;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "suspend_token");
+        ok_json!(TokenSuspensionResponse {
+            token_id,
+            is_suspended: true,
+            suspension_reason: request.reason,
+        })
+    }
+
+    /// Removes the suspension of the token.
+    pub async fn resume_token(
+        self_: web::Data<Self>,
+        token_id: web::Path<TokenId>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let token_id = token_id.into_inner();
+        let mut storage = self_.access_storage().await?;
+        self_.check_token_exists(&mut storage, token_id).await?;
+
+        storage
+            .tokens_schema()
+            .resume_token(token_id)
+            .await
+            .map_err(Self::db_error)?;
+        drop(storage);
+        self_.refresh_token_lists().await;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "resume_token");
+        ok_json!(TokenSuspensionResponse {
+            token_id,
+            is_suspended: false,
+            suspension_reason: None,
+        })
+    }
+
+    /// Returns the account state in the latest verified block created at or before
+    /// the given Unix timestamp (in seconds).
+    pub async fn account_state_at_time(
+        self_: web::Data<Self>,
+        path: web::Path<(Address, u64)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (address, timestamp) = path.into_inner();
+        let timestamp = i64::try_from(timestamp)
+            .map_err(|_| ApiError::invalid_parameter("Timestamp is out of range"))?;
+        let mut storage = self_.access_storage().await?;
+
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Self::db_error)?;
+        let block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_block_before_timestamp(timestamp, last_verified_block)
+            .await
+            .map_err(Self::db_error)?;
+        let block_number = match block_number {
+            Some(block_number) => block_number,
+            None => return Err(ApiError::not_found("Verified block")),
+        };
+
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Self::db_error)?
+            .ok_or_else(|| ApiError::not_found("Account"))?;
+        // Account may be created after the requested block.
+        let (last_update_in_block, account) = storage
+            .chain()
+            .account_schema()
+            .account_state_at_block(account_id, block_number)
+            .await
+            .map_err(Self::db_error)?
+            .ok_or_else(|| ApiError::not_found("Account"))?;
+        let response = AccountStateAtResponse {
+            block_number,
+            account: self_
+                .api_account(&mut storage, account_id, account, last_update_in_block)
+                .await?,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_state_at_time");
+        ok_json!(response)
+    }
+
+    /// Returns the progress of the proof of the block containing the withdrawal from the account.
+    /// Transactions which are not successfully executed withdrawals of the account are reported
+    /// with `404 Not Found`.
+    pub async fn withdrawal_proof_status(
+        self_: web::Data<Self>,
+        path: web::Path<(Address, String)>,
+    ) -> Result<HttpResponse, ApiError> {
+        let start = Instant::now();
+        let (address, tx_hash) = path.into_inner();
+        let tx_hash = try_parse_hash(&tx_hash).map_err(ApiError::invalid_hash)?;
+        let mut storage = self_.access_storage().await?;
+
+        let tx = storage
+            .chain()
+            .operations_ext_schema()
+            .get_tx_by_hash(tx_hash.as_ref())
+            .await
+            .map_err(Self::db_error)?;
+        let tx = match tx {
+            Some(tx) => tx,
+            None => return Err(ApiError::not_found("Transaction")),
+        };
+        let is_account_withdrawal = WITHDRAWAL_TX_TYPES.contains(&tx.tx_type.as_str())
+            && tx.fail_reason.is_none()
+            && tx.from.eq_ignore_ascii_case(&format!("{:?}", address));
+        if !is_account_withdrawal {
+            return Err(ApiError::not_found("Withdrawal"));
+        }
+        let block_number = BlockNumber(tx.block_number as u32);
+
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Self::db_error)?;
+        let last_submitted_block = storage
+            .chain()
+            .operations_schema()
+            .get_last_block_by_aggregated_action(
+                AggregatedActionType::PublishProofBlocksOnchain,
+                None,
+            )
+            .await
+            .map_err(Self::db_error)?;
+        let job = storage
+            .prover_schema()
+            .load_block_proof_job(block_number)
+            .await
+            .map_err(Self::db_error)?;
+
+        let proof_status = if block_number <= last_verified_block {
+            WithdrawalProofStatus::Verified
+        } else if block_number <= last_submitted_block {
+            WithdrawalProofStatus::SubmittedToL1
+        } else {
+            match job.as_ref().map(|job| job.job_status) {
+                Some(status) if status == ProverJobStatus::Done.to_number() => {
+                    WithdrawalProofStatus::Generated
+                }
+                Some(status) if status == ProverJobStatus::InProgress.to_number() => {
+                    WithdrawalProofStatus::Generating
+                }
+                _ => WithdrawalProofStatus::Queued,
+            }
+        };
+
+        let is_proof_pending = matches!(
+            proof_status,
+            WithdrawalProofStatus::Queued | WithdrawalProofStatus::Generating
+        );
+        let avg_proof_time_ms = self_.network_status.read().await.avg_proof_time_ms;
+        let estimated_completion = match avg_proof_time_ms {
+            Some(avg_proof_time_ms) if is_proof_pending => {
+                let now = Utc::now();
+                // Block which is not added to the queue yet is expected to be proven
+                // in the average time after it's added.
+                let job_created_at = job.as_ref().map_or(now, |job| job.created_at);
+                let estimation = job_created_at + Duration::milliseconds(avg_proof_time_ms as i64);
+                Some(estimation.max(now).timestamp())
+            }
+            _ => None,
+        };
+
+        let response = WithdrawalProofStatusResponse {
+            circuit_job_id: job.map(|job| job.id.to_string()),
+            proof_status,
+            estimated_completion,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "withdrawal_proof_status");
+        ok_json!(response)
+    }
+}
+
+/// `ETag` of the block data, only set for the verified blocks since the pending
+/// ones may still change. The state root is unique for every block.
+///
+/// Block details are loaded as the latest block not greater than the requested one,
+/// so the block number is checked as well.
+fn block_etag(block: &StorageBlockDetails, block_id: BlockNumber) -> Option<String> {
+    if block.is_verified() && block.block_number == *block_id as i64 {
+        Some(format!("\"{}\"", hex::encode(&block.new_state_root)))
+    } else {
+        None
+    }
+}
+
+/// Checks whether the `If-None-Match` header of the request matches the `ETag`.
+fn is_not_modified(req: &HttpRequest, etag: &str) -> bool {
+    req.headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |value| value == etag)
+}
+
+/// Collects the Merkle paths of all the non-zero balances of the account in the given state.
+/// The account is expected to be present in the state.
+fn account_balances_proof(
+    accounts: AccountMap,
+    block_number: BlockNumber,
+    account_id: AccountId,
+) -> AccountProofResponse {
+    let mut balances: Vec<_> = accounts[&account_id]
+        .get_nonzero_balances()
+        .into_iter()
+        .collect();
+    balances.sort_by_key(|(token_id, _)| *token_id);
+    let tree = build_account_tree(accounts);
+
+    let account_path = tree
+        .merkle_path(*account_id)
+        .into_iter()
+        .map(|(hash, _)| hash.to_hex())
+        .collect();
+    let account = tree
+        .get(*account_id)
+        .expect("Account was inserted into the tree");
+    let balances = balances
+        .into_iter()
+        .map(|(token_id, balance)| BalanceProof {
+            token_id,
+            balance: balance.0.to_string(),
+            balance_path: account
+                .subtree
+                .merkle_path(*token_id)
+                .into_iter()
+                .map(|(hash, _)| hash.to_hex())
+                .collect(),
+        })
+        .collect();
+
+    AccountProofResponse {
+        account_id,
+        block_number,
+        root_hash: tree.root_hash().to_hex(),
+        account_path,
+        balances,
+    }
+}
+
+fn build_account_tree(accounts: AccountMap) -> CircuitAccountTree {
+    let mut tree = CircuitAccountTree::new(account_tree_depth());
+    for (id, account) in accounts {
+        tree.insert(*id, CircuitAccount::from(account));
     }
+    tree
 }
@@ -1,3 +1,6 @@
+use crate::api_server::rest::v01::types::{
+    BlockFees, BlockGasUsed, CachedBlockTimes, CachedProvingTimes, CachedTokenList,
+};
 use crate::utils::shared_lru_cache::SharedLruCache;
 use zksync_storage::chain::{
     block::records::StorageBlockDetails,
@@ -5,6 +8,9 @@ use zksync_storage::chain::{
 };
 use zksync_types::ExecutedOperations;
 
+/// Histograms are cached per window, and the set of windows is fixed (see `BlockTimesQuery`).
+const BLOCK_TIMES_CACHE_SIZE: usize = 3;
+
 /// Caches used by REST API server.
 #[derive(Debug, Clone)]
 pub struct Caches {
@@ -13,6 +19,17 @@ pub struct Caches {
     pub block_executed_ops: SharedLruCache<u32, Vec<ExecutedOperations>>,
     pub blocks_info: SharedLruCache<u32, StorageBlockDetails>,
     pub blocks_by_height_or_hash: SharedLruCache<String, StorageBlockDetails>,
+    pub blocks_fees: SharedLruCache<u32, BlockFees>,
+    pub blocks_unique_senders: SharedLruCache<u32, u32>,
+    pub blocks_gas_used: SharedLruCache<u32, BlockGasUsed>,
+    /// Checksummed addresses of the block verifiers.
+    pub blocks_verifiers: SharedLruCache<u32, String>,
+    /// Holds at most one entry, the current token list.
+    pub token_list: SharedLruCache<(), CachedTokenList>,
+    /// Block times histograms by the window in seconds.
+    pub block_times: SharedLruCache<u64, CachedBlockTimes>,
+    /// Proving times histograms by the window in seconds.
+    pub proving_times: SharedLruCache<u64, CachedProvingTimes>,
 }
 
 impl Caches {
@@ -23,6 +40,13 @@ impl Caches {
             block_executed_ops: SharedLruCache::new(caches_size),
             blocks_info: SharedLruCache::new(caches_size),
             blocks_by_height_or_hash: SharedLruCache::new(caches_size),
+            blocks_fees: SharedLruCache::new(caches_size),
+            blocks_unique_senders: SharedLruCache::new(caches_size),
+            blocks_gas_used: SharedLruCache::new(caches_size),
+            blocks_verifiers: SharedLruCache::new(caches_size),
+            token_list: SharedLruCache::new(1),
+            block_times: SharedLruCache::new(BLOCK_TIMES_CACHE_SIZE),
+            proving_times: SharedLruCache::new(BLOCK_TIMES_CACHE_SIZE),
         }
     }
 }
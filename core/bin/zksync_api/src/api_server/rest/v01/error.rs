@@ -0,0 +1,142 @@
+//! Errors of the REST API v0.1 handlers.
+//!
+//! Failed requests are responded with the `{"errorType": ..., "message": ...}` JSON body,
+//! so that the clients are able to tell the failures apart. Details of the internal errors
+//! are only logged by the server and not exposed to the clients.
+
+// Built-in uses
+use std::fmt::{self, Display};
+
+// External uses
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::Serialize;
+
+// Local uses
+use crate::api_server::rest::json_response::JsonResponseBuilder;
+
+/// Error of the REST API v0.1 handler.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// Account address is malformed (400).
+    InvalidAddress(String),
+    /// Transaction or block hash is malformed (400).
+    InvalidHash(String),
+    /// Request parameter other than an address or a hash is invalid (400).
+    InvalidParameter(String),
+    /// Requested entity doesn't exist (404).
+    NotFound(String),
+    /// Database connection wasn't obtained while waiting for the pool (408).
+    StorageTimeout,
+    /// Request conflicts with the current state of the entity (409).
+    Conflict(String),
+    /// Client has exceeded its rate limit, the request may be retried in the given
+    /// amount of seconds (429).
+    RateLimited { retry_after: u64 },
+    /// Database query failed (500).
+    Storage,
+    /// Request failed for a reason other than the database one (500).
+    Internal,
+    /// Database connection wasn't obtained within the configured timeout (503).
+    StorageUnavailable,
+    /// Request can't be served at the moment for a reason other than the database one (503).
+    Unavailable(String),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiErrorBody {
+    error_type: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    pub fn invalid_address(err: impl Display) -> Self {
+        Self::InvalidAddress(err.to_string())
+    }
+
+    pub fn invalid_hash(err: impl Display) -> Self {
+        Self::InvalidHash(err.to_string())
+    }
+
+    pub fn invalid_parameter(err: impl Display) -> Self {
+        Self::InvalidParameter(err.to_string())
+    }
+
+    pub fn not_found(what: impl Display) -> Self {
+        Self::NotFound(what.to_string())
+    }
+
+    pub fn conflict(reason: impl Display) -> Self {
+        Self::Conflict(reason.to_string())
+    }
+
+    pub fn unavailable(reason: impl Display) -> Self {
+        Self::Unavailable(reason.to_string())
+    }
+
+    /// Machine-readable type of the error.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Self::InvalidAddress(_) => "invalidAddress",
+            Self::InvalidHash(_) => "invalidHash",
+            Self::InvalidParameter(_) => "invalidParameter",
+            Self::NotFound(_) => "notFound",
+            Self::StorageTimeout => "storageTimeout",
+            Self::Conflict(_) => "conflict",
+            Self::RateLimited { .. } => "rateLimited",
+            Self::Storage => "storageError",
+            Self::Internal => "internalError",
+            Self::StorageUnavailable => "storageUnavailable",
+            Self::Unavailable(_) => "unavailable",
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAddress(err) => write!(f, "Invalid address: {}", err),
+            Self::InvalidHash(err) => write!(f, "Invalid hash: {}", err),
+            Self::InvalidParameter(err) => write!(f, "Invalid parameter: {}", err),
+            Self::NotFound(what) => write!(f, "{} not found", what),
+            Self::StorageTimeout => f.write_str("Timed out waiting for the database connection"),
+            Self::Conflict(reason) => f.write_str(reason),
+            Self::RateLimited { retry_after } => write!(
+                f,
+                "Rate limit exceeded, try again in {} seconds",
+                retry_after
+            ),
+            Self::Storage => f.write_str("Database error"),
+            Self::Internal => f.write_str("Internal server error"),
+            Self::StorageUnavailable => f.write_str("Database is overloaded, try again later"),
+            Self::Unavailable(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidAddress(_) | Self::InvalidHash(_) | Self::InvalidParameter(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::StorageTimeout => StatusCode::REQUEST_TIMEOUT,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Storage | Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::StorageUnavailable | Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        if let Self::RateLimited { retry_after } = self {
+            response.insert_header(("retry-after", retry_after.to_string()));
+        }
+        response.json_utf8(ApiErrorBody {
+            error_type: self.error_type(),
+            message: self.to_string(),
+        })
+    }
+}
@@ -0,0 +1,140 @@
+//! Exit proofs of the account balances served by the REST API v0.1.
+//!
+//! Exit proof is a SNARK, and generating it takes minutes of CPU time, so the proofs are
+//! never generated by the request handlers. Handlers only store the requests into the
+//! database, and a single background task fulfills them one by one against the latest
+//! verified state, which is kept in memory and updated with the diffs of new blocks.
+
+// Built-in uses
+use std::sync::Arc;
+
+// External uses
+use tokio::sync::RwLock;
+
+// Workspace uses
+use zksync_prover_utils::exit_proof::create_exit_proof_fungible;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{helpers::apply_updates, AccountId, AccountMap, BlockNumber, TokenId};
+
+/// Maximum amount of the exit proof requests waiting for the generation.
+/// Requests of the new proofs are rejected once the queue is full.
+pub const MAX_PENDING_EXIT_PROOFS: u32 = 16;
+
+/// Accounts of the latest verified state.
+///
+/// The whole state is loaded from the database only once, then it's updated
+/// with the diffs of the blocks verified since the last update.
+#[derive(Debug, Clone, Default)]
+pub struct VerifiedAccounts(Arc<RwLock<Option<(BlockNumber, AccountMap)>>>);
+
+impl VerifiedAccounts {
+    /// Returns the number of the block the state corresponds to if the account exists in it.
+    /// Returns `None` if the account is not verified yet or the state is not loaded yet.
+    pub async fn account_block(&self, account_id: AccountId) -> Option<BlockNumber> {
+        let state = self.0.read().await;
+        let (block_number, accounts) = state.as_ref()?;
+        accounts.contains_key(&account_id).then(|| *block_number)
+    }
+
+    /// Returns `true` once the state is loaded from the database.
+    pub async fn is_loaded(&self) -> bool {
+        self.0.read().await.is_some()
+    }
+
+    /// Returns the copy of the state to generate the exit proof against,
+    /// `None` if the state is not loaded yet.
+    pub async fn snapshot(&self) -> Option<(BlockNumber, AccountMap)> {
+        self.0.read().await.clone()
+    }
+
+    /// Brings the state up to the last verified block.
+    pub async fn update(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let current_block = match self.0.read().await.as_ref() {
+            Some((block_number, _)) => *block_number,
+            None => {
+                let state = storage.chain().state_schema().load_verified_state().await?;
+                *self.0.write().await = Some(state);
+                return Ok(());
+            }
+        };
+
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        if last_verified_block <= current_block {
+            return Ok(());
+        }
+        let diff = storage
+            .chain()
+            .state_schema()
+            .load_state_diff(current_block, Some(last_verified_block))
+            .await?;
+
+        // The state is only updated by a single task, so it couldn't change in the meantime.
+        let mut state = self.0.write().await;
+        let (block_number, accounts) = state.as_mut().expect("State is loaded");
+        if let Some((_, updates)) = diff {
+            apply_updates(accounts, updates);
+        }
+        *block_number = last_verified_block;
+        Ok(())
+    }
+}
+
+/// Brings the verified state up to date and generates the proof of the oldest pending request.
+///
+/// Requests made against the older blocks are fulfilled against the latest verified state,
+/// so that the proof is valid for the root the contract has at the moment.
+pub async fn generate_pending_exit_proof(
+    connection_pool: &ConnectionPool,
+    verified_accounts: &VerifiedAccounts,
+) -> anyhow::Result<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    verified_accounts.update(&mut storage).await?;
+
+    let request = match storage
+        .chain()
+        .block_schema()
+        .get_pending_exit_proof()
+        .await?
+    {
+        Some(request) => request,
+        None => return Ok(()),
+    };
+    let account_id = AccountId(request.account_id as u32);
+    let token_id = TokenId(request.token_id as u32);
+    let (block_number, accounts) = verified_accounts
+        .snapshot()
+        .await
+        .expect("State is loaded by the update");
+    if request.block_number < *block_number as i64 {
+        storage
+            .chain()
+            .block_schema()
+            .request_exit_proof(account_id, token_id, block_number)
+            .await?;
+    }
+    // Account ids are never reused, so the account of the accepted request stays in the state.
+    let owner = accounts
+        .get(&account_id)
+        .map(|account| account.address)
+        .ok_or_else(|| anyhow::format_err!("Verified account {} not found", *account_id))?;
+    // Proof generation takes minutes, the connection shouldn't be held for that long.
+    drop(storage);
+
+    let (proof, amount) = tokio::task::spawn_blocking(move || {
+        create_exit_proof_fungible(accounts, account_id, owner, token_id)
+    })
+    .await??;
+
+    connection_pool
+        .access_storage()
+        .await?
+        .chain()
+        .block_schema()
+        .store_exit_proof(account_id, token_id, block_number, &proof, amount)
+        .await?;
+    Ok(())
+}
@@ -1,4 +1,7 @@
 pub mod api_decl;
 pub mod api_impl;
 pub mod caches;
+pub mod error;
+pub mod exit_proofs;
 pub mod types;
+pub mod ws;
@@ -1,7 +1,53 @@
 //! Requests and responses used by the REST API.
 
+use crate::api_server::rest::network_status::NetworkStatus;
+use actix_web::web::Bytes;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use num::{rational::Ratio, BigUint};
+use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
-use zksync_types::{Account, AccountId};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use zksync_api_types::v02::{
+    account::Account as ApiAccount,
+    pagination::{PaginationDirection, PaginationQuery},
+};
+use zksync_config::ZkSyncConfig;
+use zksync_crypto::proof::EncodedSingleProof;
+use zksync_storage::{
+    chain::{
+        account::{
+            records::{Account2FA, AccountLastUpdates},
+            StoredAccountState,
+        },
+        block::{
+            records::{
+                BlockTransactionItem, StorageBlockDetails, StorageExitProof,
+                StorageExpandedBlockDetails, StorageProofLatencyBucket,
+            },
+            BlockDetailsExpansion, BlockRangeFilter, ProofLatencyBucketSize,
+        },
+        operations_ext::{
+            records::{
+                StorageChangePubKeyTx, StorageDeposit, StorageForcedExit, TransactionsHistoryItem,
+            },
+            ForcedExitsFilter,
+        },
+        stats::StorageFeeTokenUsage,
+    },
+    tokens::records::TokenWithStatus,
+};
+use zksync_types::{
+    helpers::to_checksum_address,
+    tx::{EthSignData, TxHash},
+    Account, AccountId, Address, BlockNumber, Fee, Nonce, PubKeyHash, SignedZkSyncTx, Token,
+    TokenId, TokenKind, TokenLike, TokenRiskLevel, ZkSyncTx,
+};
+use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, SyncBlockPrefix};
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -9,33 +55,1856 @@ pub struct TestnetConfigResponse {
     pub contract_address: String,
 }
 
+/// Role of the L1 contract in the rollup deployment.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractRole {
+    /// Main zkSync contract (proxy), the one users interact with.
+    Main,
+    Governance,
+    Verifier,
+    /// Contract initiating the upgrades of the proxies.
+    UpgradeGatekeeper,
+    /// Contract holding the part of the main contract logic which doesn't fit into its size.
+    AdditionalZksync,
+    /// Account sending the forced exit requests on behalf of the users.
+    ForcedExit,
+    /// Implementations the proxies delegate the calls to.
+    MainTarget,
+    GovernanceTarget,
+    VerifierTarget,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractInfo {
+    pub role: ContractRole,
+    pub address: String,
+}
+
+/// Optional features supported by the node.
+#[derive(Debug, Serialize)]
+pub struct CapabilitiesResponse {
+    /// Whether the JSON RPC API is served over HTTP.
+    pub rpc_api: bool,
+    /// Whether the JSON RPC API with subscriptions is served over WebSocket.
+    pub websocket_api: bool,
+    /// Whether the web3 compatible JSON RPC API is served.
+    pub web3_api: bool,
+    /// Whether the forced exit requests API is enabled.
+    pub forced_exit_requests: bool,
+    /// Whether requests to the REST API are rate limited.
+    pub rate_limits: bool,
+    /// Whether clients may pass API keys in the `X-API-Key` header to get the separate rate limit.
+    pub api_keys: bool,
+    /// Endpoints available with the admin token in the `Authorization` header.
+    pub admin_endpoints: Vec<&'static str>,
+    /// Endpoint streaming the network status and the transactions of the subscribed accounts.
+    pub ws_endpoint: &'static str,
+}
+
+/// Response of the `/status` endpoint.
+#[derive(Debug, Serialize)]
+pub struct NetworkStatusResponse {
+    #[serde(flatten)]
+    pub status: NetworkStatus,
+    /// Root hash of the genesis state, lets the clients check they're connected
+    /// to the expected zkSync deployment.
+    pub genesis_block_hash: String,
+}
+
+/// Server configuration parameters the clients may rely on, e.g. to build batches of
+/// the acceptable size. Only the public parameters are exposed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerConfig {
+    pub chain_id: u32,
+    /// Maximum size of the block in chunks.
+    pub max_block_chunk_size: usize,
+    pub max_tx_per_batch: u64,
+    /// Maximum amount of the different signers of the transactions in a batch.
+    pub max_authors_per_batch: u64,
+    /// Types of the L2 transactions accepted by the server.
+    pub supported_tx_types: Vec<&'static str>,
+    /// Age an account must reach before it's allowed to be the target of `ForcedExit`.
+    pub forced_exit_minimum_account_age_secs: u64,
+    /// Whether the `ChangePubKey` transactions must pay the fee.
+    pub enforce_pubkey_change_fee: bool,
+}
+
+impl ServerConfig {
+    const SUPPORTED_TX_TYPES: &'static [&'static str] = &[
+        "Transfer",
+        "Withdraw",
+        "ChangePubKey",
+        "ForcedExit",
+        "MintNFT",
+        "WithdrawNFT",
+        "Swap",
+    ];
+
+    pub fn new(config: &ZkSyncConfig) -> Self {
+        let common = &config.api.common;
+        Self {
+            chain_id: config.api.web3.chain_id,
+            max_block_chunk_size: config
+                .chain
+                .state_keeper
+                .block_chunk_sizes
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default(),
+            max_tx_per_batch: common.max_number_of_transactions_per_batch,
+            max_authors_per_batch: common.max_number_of_authors_per_batch,
+            supported_tx_types: Self::SUPPORTED_TX_TYPES.to_vec(),
+            forced_exit_minimum_account_age_secs: common.forced_exit_minimum_account_age_secs,
+            enforce_pubkey_change_fee: common.enforce_pubkey_change_fee,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct WithdrawalProcessingTimeResponse {
     pub normal: u64,
     pub fast: u64,
 }
 
+/// Gas price policy of the operator for the L1 transactions (commit, verify and execute).
+#[derive(Debug, Serialize)]
+pub struct GasPolicyResponse {
+    /// Average gas price of the recently sent L1 transactions, which new transactions are sent with.
+    /// `None` until enough statistics is gathered, the network gas price is used in that case.
+    pub target_gas_price_wei: Option<String>,
+    /// Maximum gas price the operator is going to pay. While the network gas price is above it,
+    /// L1 transactions stay pending, which delays the blocks finalization.
+    pub gas_price_limit_wei: String,
+    /// The limit is recalculated with this interval as the average gas price scaled by `limit_scale_factor`.
+    pub limit_update_interval_secs: u64,
+    pub limit_scale_factor: f64,
+}
+
+/// Kind of the token, so that clients know how to interact with it.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    /// Native token which has no L1 contract.
+    Eth,
+    Erc20,
+    Nft,
+}
+
+impl From<&Token> for TokenType {
+    fn from(token: &Token) -> Self {
+        if token.id == TokenId(0) {
+            Self::Eth
+        } else if token.is_nft {
+            Self::Nft
+        } else {
+            Self::Erc20
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuspendTokenRequest {
+    /// Reason shown to the clients in the token list.
+    pub reason: Option<String>,
+}
+
+/// Suspension status of the token after the admin request.
+#[derive(Debug, Serialize)]
+pub struct TokenSuspensionResponse {
+    pub token_id: TokenId,
+    pub is_suspended: bool,
+    pub suspension_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenDecimalsResponse {
+    pub decimals: u8,
+}
+
+/// Token with the same fields as `Token` has, but with the contract address
+/// in the EIP-55 checksum encoding, so that it can be compared with the addresses
+/// from the other sources as is.
+#[derive(Debug, Serialize)]
+pub struct TokenResponse {
+    pub id: TokenId,
+    pub address: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub kind: TokenKind,
+    pub is_nft: bool,
+    pub token_type: TokenType,
+    /// Risk level of the token configured by the server operator.
+    pub risk_level: TokenRiskLevel,
+    /// Whether the token is suspended by governance, so that it must not be used in transactions.
+    pub is_suspended: bool,
+    /// Reason of the suspension for the display purposes, only set for the suspended tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspension_reason: Option<String>,
+}
+
+impl TokenResponse {
+    pub fn new(status: TokenWithStatus) -> Self {
+        let TokenWithStatus {
+            token,
+            risk_level,
+            is_suspended,
+            suspension_reason,
+        } = status;
+        Self {
+            token_type: TokenType::from(&token),
+            id: token.id,
+            address: to_checksum_address(&token.address),
+            symbol: token.symbol,
+            decimals: token.decimals,
+            kind: token.kind,
+            is_nft: token.is_nft,
+            risk_level,
+            is_suspended,
+            suspension_reason: suspension_reason.filter(|_| is_suspended),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct AccountStateResponse {
     // None if account is not created yet.
     pub id: Option<AccountId>,
+    /// Index of the account leaf in the Merkle tree of accounts, which is the same as the account ID.
+    /// `None` if the account is not allocated an ID yet.
+    pub merkle_leaf_index: Option<u32>,
     pub commited: Account,
     pub verified: Account,
+    /// Whether the account doesn't exist in the zkSync network yet.
+    pub is_new_account: bool,
+    /// Whether the account has set its public key via `ChangePubKey`, so it's able to sign
+    /// transactions with the L2 key. Accounts without it use the legacy signature scheme.
+    pub pubkey_registered: bool,
+    /// Estimated one-time fee for the account creation in ETH wei,
+    /// only set for the new accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_fee_hint: Option<String>,
+    /// Unix timestamp (in seconds) of the last committed operation changing the account state.
+    pub committed_at: Option<i64>,
+    /// Unix timestamp (in seconds) of the last verified operation changing the account state.
+    pub verified_at: Option<i64>,
+    /// Block of the last committed operation changing the account state.
+    pub committed_block: Option<BlockNumber>,
+    /// Block of the last verified operation changing the account state.
+    pub verified_block: Option<BlockNumber>,
+    /// Whether transactions of the account must be additionally signed with the Ethereum key,
+    /// `None` for the new accounts.
+    pub two_fa: Option<TwoFactorAuth>,
+    /// Public key hash allowed to sign transactions without the Ethereum signature,
+    /// only set if 2FA is disabled for this public key hash only.
+    pub two_fa_pub_key_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TwoFactorAuth {
+    Required,
+    Disabled,
+    /// Transactions signed with the `two_fa_pub_key_hash` don't need the Ethereum signature,
+    /// while the rest of them still do.
+    DisabledForPubKeyHash,
+}
+
+impl AccountStateResponse {
+    /// Creates a response from the stored account state and the last committed
+    /// and verified account updates.
+    pub fn new(
+        state: StoredAccountState,
+        init_fee_hint: Option<String>,
+        last_updates: AccountLastUpdates,
+    ) -> Self {
+        let id = state.committed.as_ref().map(|(id, _)| *id);
+        let pubkey_registered = state.committed.as_ref().map_or(false, |(_, account)| {
+            account.pub_key_hash != PubKeyHash::zero()
+        });
+        Self {
+            id,
+            merkle_leaf_index: id.map(|id| *id),
+            commited: state
+                .committed
+                .map(|(_, account)| account)
+                .unwrap_or_default(),
+            verified: state
+                .verified
+                .map(|(_, account)| account)
+                .unwrap_or_default(),
+            is_new_account: id.is_none(),
+            pubkey_registered,
+            init_fee_hint,
+            committed_at: last_updates.committed_at.map(|time| time.timestamp()),
+            verified_at: last_updates.verified_at.map(|time| time.timestamp()),
+            committed_block: last_updates.committed_block,
+            verified_block: last_updates.verified_block,
+            two_fa: None,
+            two_fa_pub_key_hash: None,
+        }
+    }
+
+    /// Sets the 2FA setting of the existing account.
+    pub fn with_2fa(mut self, two_fa: Account2FA) -> Self {
+        let (two_fa, pub_key_hash) = match two_fa {
+            Account2FA::Required => (TwoFactorAuth::Required, None),
+            Account2FA::Disabled => (TwoFactorAuth::Disabled, None),
+            Account2FA::DisabledForPubKeyHash(pub_key_hash) => {
+                (TwoFactorAuth::DisabledForPubKeyHash, Some(pub_key_hash))
+            }
+        };
+        self.two_fa = Some(two_fa);
+        self.two_fa_pub_key_hash = pub_key_hash.map(|hash| hash.as_hex());
+        self
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ForcedExitsQuery {
+    /// All the forced exits are returned by default.
+    pub status: Option<ForcedExitsStatusFilter>,
+    /// Number of the newer forced exits to skip.
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForcedExitsStatusFilter {
+    Pending,
+    Completed,
+    All,
+}
+
+impl From<ForcedExitsStatusFilter> for ForcedExitsFilter {
+    fn from(filter: ForcedExitsStatusFilter) -> Self {
+        match filter {
+            ForcedExitsStatusFilter::Pending => Self::Pending,
+            ForcedExitsStatusFilter::Completed => Self::Completed,
+            ForcedExitsStatusFilter::All => Self::All,
+        }
+    }
+}
+
+/// L2 status of the `ForcedExit` transaction.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForcedExitStatus {
+    /// Transaction is executed, but its block is not verified yet.
+    Pending,
+    /// Block containing the transaction is verified.
+    Completed,
+    Rejected,
+}
+
+/// `ForcedExit` transaction initiated by the account.
+#[derive(Debug, Serialize)]
+pub struct ForcedExitEntry {
+    pub tx_hash: String,
+    pub target: String,
+    pub token: TokenId,
+    /// Withdrawn amount, not set for the rejected transactions.
+    pub amount: Option<String>,
+    pub status: ForcedExitStatus,
+    pub block_number: BlockNumber,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StorageForcedExit> for ForcedExitEntry {
+    fn from(forced_exit: StorageForcedExit) -> Self {
+        let status = if !forced_exit.success {
+            ForcedExitStatus::Rejected
+        } else if forced_exit.verified {
+            ForcedExitStatus::Completed
+        } else {
+            ForcedExitStatus::Pending
+        };
+        Self {
+            tx_hash: forced_exit.tx_hash,
+            target: forced_exit.target,
+            token: TokenId(forced_exit.token as u32),
+            amount: forced_exit.amount,
+            status,
+            block_number: BlockNumber(forced_exit.block_number as u32),
+            fail_reason: forced_exit.fail_reason,
+            created_at: forced_exit.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AccountDepositsQuery {
+    pub limit: Option<u32>,
+}
+
+/// Executed deposit to the account.
+#[derive(Debug, Serialize)]
+pub struct DepositEntry {
+    /// Hash of the Ethereum transaction that submitted the deposit.
+    pub eth_hash: String,
+    pub from: String,
+    pub token: TokenId,
+    /// Whether ETH was deposited rather than an ERC-20 token.
+    pub is_eth: bool,
+    pub amount: String,
+    pub block_number: BlockNumber,
+    pub eth_block: u64,
+    /// Number of Ethereum blocks mined since the deposit, `None` if the current Ethereum
+    /// block is not known yet.
+    pub l1_confirmations: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DepositEntry {
+    pub fn new(deposit: StorageDeposit, current_eth_block: Option<u64>) -> Self {
+        let token = TokenId(deposit.token as u32);
+        let eth_block = deposit.eth_block as u64;
+        Self {
+            eth_hash: deposit.eth_hash,
+            from: deposit.from,
+            token,
+            is_eth: token.0 == 0,
+            amount: deposit.amount,
+            block_number: BlockNumber(deposit.block_number as u32),
+            eth_block,
+            l1_confirmations: current_eth_block
+                .map(|current_block| current_block.saturating_sub(eth_block)),
+            created_at: deposit.created_at,
+        }
+    }
+}
+
+/// Ethereum confirmations of the priority operation.
+#[derive(Debug, Serialize)]
+pub struct PriorityOpConfirmationsResponse {
+    /// Ethereum block the operation was submitted in.
+    pub l1_block: u64,
+    /// Latest Ethereum block seen by the server, `None` if it's not known yet.
+    pub current_l1_block: Option<u64>,
+    /// Number of Ethereum blocks mined since the operation, `None` if the current
+    /// Ethereum block is not known yet.
+    pub confirmations: Option<u64>,
+    /// Number of confirmations required for the operation to be processed on L2.
+    pub required: u64,
+    /// Whether the operation got enough confirmations. Always `true` for the executed operations.
+    pub confirmed: bool,
+}
+
+#[derive(Deserialize)]
+pub struct AccountTimelineQuery {
+    pub limit: Option<u32>,
+    /// `next_cursor` of the previous page, the newest events are returned if unset.
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountTimelineResponse {
+    pub events: Vec<AccountTimelineEvent>,
+    /// Cursor of the next page, `None` if this page is the last one.
+    pub next_cursor: Option<String>,
+}
+
+/// Kind of the change of the account state, as seen by the account itself.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountEventType {
+    TransferIn,
+    TransferOut,
+    Deposit,
+    /// Both the L2 withdrawals (of the fungible tokens and NFTs) and the full exits.
+    Withdrawal,
+    ChangePubkey,
+    /// The account is either the initiator or the target of the `ForcedExit`.
+    ForcedExit,
+    NftMint,
+    /// NFT is either sent or received by the account.
+    NftTransfer,
+    Swap,
+}
+
+/// Entry of the account timeline returned by the `/account/{address}/timeline` endpoint.
+#[derive(Debug, Serialize)]
+pub struct AccountTimelineEvent {
+    pub event_type: AccountEventType,
+    /// The transaction or the priority operation, in the same format as in the account history.
+    pub data: serde_json::Value,
+    pub block_number: u32,
+    /// Unix timestamp (in seconds) of the operation execution.
+    pub created_at: i64,
+    pub tx_hash: Option<String>,
+    /// ID of the operation (in the `<block>,<index in block>` format), used as the page cursor.
+    pub tx_id: String,
+}
+
+impl AccountTimelineEvent {
+    /// Classifies the account history item. Returns `None` for the failed transactions,
+    /// which don't change the account state, and for the operations of the unknown types.
+    pub fn from_history_item(item: TransactionsHistoryItem, address: Address) -> Option<Self> {
+        if item.success == Some(false) {
+            return None;
+        }
+        let tx = &item.tx;
+        // Token IDs of the fungible tokens are replaced with their symbols in the history,
+        // while the NFTs are still referenced by their IDs.
+        let is_nft = tx["token"].is_u64();
+        let event_type = match tx["type"].as_str()? {
+            "Transfer" => {
+                let from: Option<Address> = serde_json::from_value(tx["from"].clone()).ok();
+                match (is_nft, from == Some(address)) {
+                    (true, _) => AccountEventType::NftTransfer,
+                    (false, true) => AccountEventType::TransferOut,
+                    (false, false) => AccountEventType::TransferIn,
+                }
+            }
+            "Deposit" => AccountEventType::Deposit,
+            "Withdraw" | "WithdrawNFT" | "FullExit" => AccountEventType::Withdrawal,
+            "ChangePubKey" => AccountEventType::ChangePubkey,
+            "ForcedExit" => AccountEventType::ForcedExit,
+            "MintNFT" => AccountEventType::NftMint,
+            "Swap" => AccountEventType::Swap,
+            _ => return None,
+        };
+        let block_number = item.tx_id.split(',').next()?.parse().ok()?;
+
+        Some(Self {
+            event_type,
+            data: item.tx,
+            block_number,
+            created_at: item.created_at.timestamp(),
+            tx_hash: item.hash,
+            tx_id: item.tx_id,
+        })
+    }
+}
+
+/// Filter of the account history endpoints by the token and the amount; the amount bounds
+/// are only allowed along with the token, since amounts of different tokens aren't comparable.
+/// Dust transfers are detected with the token prices stored by the ticker.
+#[derive(Debug, Deserialize)]
+pub struct TxHistoryFilterQuery {
+    pub token: Option<String>,
+    /// Lower bound of the amount as a decimal string, inclusive.
+    pub min_amount: Option<String>,
+    /// Upper bound of the amount as a decimal string, inclusive.
+    pub max_amount: Option<String>,
+    /// Whether to hide the transfers worth less than the configured dust threshold.
+    pub hide_dust: Option<bool>,
+}
+
+/// Result of the address validation.
+#[derive(Debug, Serialize)]
+pub struct AddressValidationResponse {
+    pub valid: bool,
+    /// Checksummed representation of the address, only set for the valid addresses.
+    pub canonical: Option<String>,
+}
+
+/// Committed and verified nonces of the account, both are zero for the accounts
+/// that don't exist yet.
+#[derive(Debug, Serialize)]
+pub struct AccountNonceResponse {
+    pub committed: Nonce,
+    pub verified: Nonce,
+    /// Last committed block as of the latest network status update.
+    pub current_block: BlockNumber,
+}
+
+/// Whether the account nonce has been used by an executed transaction.
+#[derive(Debug, Serialize)]
+pub struct NonceUsedResponse {
+    pub nonce: Nonce,
+    pub used: bool,
+    /// Hash of the transaction which used the nonce.
+    pub tx_hash: Option<TxHash>,
+}
+
+/// The most recent `ChangePubKey` transaction of the account.
+#[derive(Debug, Serialize)]
+pub struct ChangePubKeyResponse {
+    pub tx_hash: TxHash,
+    pub block_number: BlockNumber,
+    pub block_index: Option<u32>,
+    pub new_pk_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StorageChangePubKeyTx> for ChangePubKeyResponse {
+    fn from(tx: StorageChangePubKeyTx) -> Self {
+        Self {
+            tx_hash: TxHash::from_slice(&tx.tx_hash).unwrap(),
+            block_number: BlockNumber(tx.block_number as u32),
+            block_index: tx.block_index.map(|index| index as u32),
+            new_pk_hash: tx.new_pk_hash,
+            created_at: tx.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountsByIdRequest {
+    pub ids: Vec<AccountId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AggregateBalanceRequest {
+    pub addresses: Vec<String>,
+}
+
+/// Non-zero balances summed over the accounts, by token ID.
+#[derive(Debug, Serialize)]
+pub struct AggregateBalanceResponse {
+    pub committed: BTreeMap<TokenId, String>,
+    pub verified: BTreeMap<TokenId, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountsHistoryRequest {
+    pub addresses: Vec<String>,
+    pub limit: u32,
+    /// ID of the last transaction of the previous page (in the `<block>,<index in block>` format).
+    pub cursor: Option<String>,
+}
+
+/// Public data of the block, exactly as it is sent to L1 in the commit operation.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockPubdataResponse {
+    pub block_number: BlockNumber,
+    /// `0x`-prefixed hex-encoded pubdata bytes.
+    pub pubdata: String,
+}
+
+/// Account that received the fees collected in the block.
+#[derive(Debug, Serialize)]
+pub struct BlockFeeRecipientResponse {
+    pub block_number: BlockNumber,
+    pub fee_account_id: AccountId,
+    /// `None` if the fee account is not found in the stored accounts.
+    pub fee_account_address: Option<Address>,
+}
+
+/// Transaction executed in the block exactly as it was submitted by the user,
+/// including the zkSync signature and the Ethereum signature (if any).
+#[derive(Debug, Serialize)]
+pub struct SignedTxResponse {
+    pub block_number: BlockNumber,
+    pub block_index: Option<u32>,
+    pub tx_hash: TxHash,
+    pub tx: ZkSyncTx,
+    pub eth_sign_data: Option<EthSignData>,
+}
+
+/// Proof required to withdraw the account balance via an exit on L1,
+/// generated against the state of the last verified block.
+#[derive(Debug, Serialize)]
+pub struct ExitProofResponse {
+    pub account: AccountId,
+    pub token: TokenId,
+    /// Verified block the proof is generated against.
+    pub block_number: BlockNumber,
+    /// Balance to be withdrawn, `None` until the proof is generated.
+    pub amount: Option<String>,
+    pub proof: Option<EncodedSingleProof>,
+}
+
+impl ExitProofResponse {
+    pub fn new(exit_proof: StorageExitProof) -> Result<Self, serde_json::Error> {
+        let proof = exit_proof.proof.map(serde_json::from_value).transpose()?;
+        Ok(Self {
+            account: AccountId(exit_proof.account_id as u32),
+            token: TokenId(exit_proof.token_id as u32),
+            block_number: BlockNumber(exit_proof.block_number as u32),
+            amount: exit_proof.amount.map(|amount| amount.to_string()),
+            proof,
+        })
+    }
+}
+
+/// Merkle proof of a single balance of the account, see `AccountProofResponse`.
+#[derive(Debug, Serialize)]
+pub struct BalanceProof {
+    pub token_id: TokenId,
+    pub balance: String,
+    /// Sibling hashes from the balance leaf up to the account balance tree root.
+    pub balance_path: Vec<String>,
+}
+
+/// Merkle proofs of the account balances against the state root of a verified block.
+#[derive(Debug, Serialize)]
+pub struct AccountProofResponse {
+    pub account_id: AccountId,
+    pub block_number: BlockNumber,
+    /// Root hash of the account tree, equal to the state root of the block.
+    pub root_hash: String,
+    /// Sibling hashes from the account leaf up to the account tree root.
+    pub account_path: Vec<String>,
+    /// Proofs of the non-zero balances of the account, ordered by the token ID.
+    pub balances: Vec<BalanceProof>,
+}
+
+/// State of the account in a verified block.
+#[derive(Debug, Serialize)]
+pub struct AccountStateAtResponse {
+    pub block_number: BlockNumber,
+    pub account: ApiAccount,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivitySpanResponse {
+    /// Time of the first operation of the account, `None` if there were no operations.
+    pub first_tx_at: Option<DateTime<Utc>>,
+    /// Time of the last operation of the account, `None` if there were no operations.
+    pub last_tx_at: Option<DateTime<Utc>>,
+    pub tx_count: u64,
+}
+
+/// Receipt of a transaction or a priority operation along with the amount
+/// of the verified blocks built on top of the block containing it.
+#[derive(Debug, Serialize)]
+pub struct ReceiptWithConfirmations<T> {
+    #[serde(flatten)]
+    pub receipt: T,
+    /// Zero for the operations from the blocks that are not verified yet.
+    pub confirmations: u32,
+}
+
+impl<T> ReceiptWithConfirmations<T> {
+    pub fn new(receipt: T, block_number: Option<u32>, last_verified: BlockNumber) -> Self {
+        let confirmations = block_number
+            .map(|block_number| (*last_verified).saturating_sub(block_number))
+            .unwrap_or_default();
+        Self {
+            receipt,
+            confirmations,
+        }
+    }
+}
+
+/// Transaction of the block along with the position of its operation in the block public data.
+#[derive(Debug, Serialize)]
+pub struct BlockTransactionResponse {
+    #[serde(flatten)]
+    pub tx: BlockTransactionItem,
+    /// Offset of the operation in the block public data, in chunks.
+    /// Not set for the rejected transactions, since they aren't included into the public data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_offset: Option<u32>,
+    /// Amount of chunks occupied by the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<u32>,
+}
+
+/// Entry of the account transactions history with the creation time
+/// optionally converted to the timezone requested via the `X-Timezone` header.
+#[derive(Debug, Serialize)]
+pub struct TxHistoryItemResponse {
+    #[serde(flatten)]
+    pub item: TransactionsHistoryItem,
+    /// RFC 3339 representation of `created_at` in the requested timezone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+impl TxHistoryItemResponse {
+    pub fn localize(
+        history: Vec<TransactionsHistoryItem>,
+        timezone: Option<Tz>,
+    ) -> Vec<TxHistoryItemResponse> {
+        history
+            .into_iter()
+            .map(|item| Self {
+                created_at_local: timezone
+                    .map(|timezone| item.created_at.with_timezone(&timezone).to_rfc3339()),
+                item,
+            })
+            .collect()
+    }
+}
+
+/// Entry of the account history with the value of the transaction in dollars at the time
+/// its block was committed, `null` if the value is unknown.
+#[derive(Debug, Serialize)]
+pub struct TxHistoryItemWithValue {
+    #[serde(flatten)]
+    pub item: TxHistoryItemResponse,
+    pub usd_value_at_time: Option<f64>,
+}
+
+/// Transactions of the account history sent in the same batch.
+#[derive(Debug, Serialize)]
+pub struct BatchHistoryItem {
+    pub batch_hash: String,
+    pub txs: Vec<TxHistoryItemResponse>,
+    /// Sums of the fees paid by the batch transactions by the fee token id,
+    /// since the transactions of a batch may pay the fees in different tokens.
+    pub total_fees: BTreeMap<u32, String>,
+    /// Unix timestamp of the earliest transaction of the batch.
+    pub submitted_at: i64,
+}
+
+impl BatchHistoryItem {
+    fn new(batch_hash: String, txs: Vec<TxHistoryItemResponse>) -> Self {
+        let mut total_fees = BTreeMap::<u32, BigUint>::new();
+        for item in &txs {
+            let tx = &item.item.tx;
+            let fee = tx
+                .get("fee")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|fee| fee.parse::<BigUint>().ok());
+            // `feeToken` is only set for the transactions which may pay the fee
+            // in a token other than the transferred one.
+            let fee_token = tx
+                .get("feeToken")
+                .or_else(|| tx.get("token"))
+                .and_then(serde_json::Value::as_u64);
+            if let (Some(fee), Some(fee_token)) = (fee, fee_token) {
+                *total_fees.entry(fee_token as u32).or_default() += fee;
+            }
+        }
+        let submitted_at = txs
+            .iter()
+            .map(|item| item.item.created_at.timestamp())
+            .min()
+            .unwrap_or_default();
+
+        Self {
+            batch_hash,
+            txs,
+            total_fees: total_fees
+                .into_iter()
+                .map(|(token, fee)| (token, fee.to_string()))
+                .collect(),
+            submitted_at,
+        }
+    }
+}
+
+/// Entry of the account transactions history where the transactions
+/// of a batch are merged into a single entry.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum GroupedTxHistoryItem {
+    Batch(BatchHistoryItem),
+    Tx(TxHistoryItemResponse),
+}
+
+impl GroupedTxHistoryItem {
+    /// Merges the transactions with the same batch hash, keeping the position
+    /// of the first transaction of every batch in the history.
+    pub fn group(history: Vec<TxHistoryItemResponse>) -> Vec<Self> {
+        enum Entry {
+            Batch(String, Vec<TxHistoryItemResponse>),
+            Tx(TxHistoryItemResponse),
+        }
+
+        let mut entries = Vec::with_capacity(history.len());
+        let mut batch_positions = HashMap::new();
+        for item in history {
+            let batch_hash = match &item.item.batch_hash {
+                Some(batch_hash) => batch_hash.clone(),
+                None => {
+                    entries.push(Entry::Tx(item));
+                    continue;
+                }
+            };
+            match batch_positions.get(&batch_hash) {
+                Some(&position) => {
+                    if let Entry::Batch(_, txs) = &mut entries[position] {
+                        txs.push(item);
+                    }
+                }
+                None => {
+                    batch_positions.insert(batch_hash.clone(), entries.len());
+                    entries.push(Entry::Batch(batch_hash, vec![item]));
+                }
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                Entry::Batch(batch_hash, txs) => {
+                    Self::Batch(BatchHistoryItem::new(batch_hash, txs))
+                }
+                Entry::Tx(item) => Self::Tx(item),
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TxHistoryQuery {
     pub tx_id: Option<String>,
-    pub limit: Option<u64>,
+    /// The configured default page size is used if not specified.
+    pub limit: Option<u32>,
+    /// Hash of the transaction to load the older ones than, alternative to `tx_id`.
+    /// Only supported for the older transactions.
+    pub before_hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TxHistoryCursorQuery {
+    /// Cursor returned with the previous page, the history starts from the first transaction
+    /// of the account if not specified.
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// Opaque position of the transaction in the account history, the `(block number, block index)` pair
+/// encoded as a hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryCursor(pub u64, pub u64);
+
+impl HistoryCursor {
+    /// Parses the `tx_id` of the history item, which is the comma-separated block number and index.
+    pub fn from_tx_id(tx_id: &str) -> Option<Self> {
+        let (block_number, block_index) = tx_id.split_once(',')?;
+        Some(Self(block_number.parse().ok()?, block_index.parse().ok()?))
+    }
+
+    pub fn encode(self) -> String {
+        let mut bytes = self.0.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.1.to_be_bytes());
+        hex::encode(bytes)
+    }
+}
+
+impl FromStr for HistoryCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s)?;
+        anyhow::ensure!(bytes.len() == 16, "Cursor should be 16 bytes long");
+        let mut block_number = [0u8; 8];
+        let mut block_index = [0u8; 8];
+        block_number.copy_from_slice(&bytes[..8]);
+        block_index.copy_from_slice(&bytes[8..]);
+        Ok(Self(
+            u64::from_be_bytes(block_number),
+            u64::from_be_bytes(block_index),
+        ))
+    }
+}
+
+/// Page of the account history loaded by the cursor.
+#[derive(Debug, Serialize)]
+pub struct TxHistoryPage {
+    pub transactions: Vec<TxHistoryItemResponse>,
+    /// Cursor to load the transactions following the page with. Equals to the requested one
+    /// if there are no new transactions yet, so that the history can be polled with it.
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct HandleBlocksQuery {
+    /// Order of the blocks, descending by default.
+    pub order: Option<BlocksOrder>,
+    /// Last block to return, for the descending order only.
     pub max_block: Option<u32>,
+    /// First block to return, for the ascending order only.
+    pub from_block: Option<u32>,
     pub limit: Option<u32>,
+    pub min_tx_count: Option<u32>,
+    pub max_tx_count: Option<u32>,
+    /// ID of the token the fee bounds are applied to, required along with them.
+    pub fee_token: Option<u32>,
+    /// Decimal string representation of the fee amount.
+    pub min_total_fee: Option<String>,
+    /// Decimal string representation of the fee amount.
+    pub max_total_fee: Option<String>,
+    /// Whether to report the number of unique senders of the block operations.
+    pub include_unique_senders: Option<bool>,
+}
+
+/// Order of the blocks returned by the `/blocks` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlocksOrder {
+    Asc,
+    Desc,
+}
+
+impl HandleBlocksQuery {
+    /// Validates the parameters of the query other than the `limit` one and converts them
+    /// into the storage pagination query and filter.
+    pub fn validate(
+        &self,
+        limit: u32,
+    ) -> Result<(PaginationQuery<BlockNumber>, BlockRangeFilter), anyhow::Error> {
+        Ok((self.pagination_query(limit)?, self.block_range_filter()?))
+    }
+
+    /// Converts the requested start of the range and the order into the storage pagination query.
+    pub fn pagination_query(
+        &self,
+        limit: u32,
+    ) -> Result<PaginationQuery<BlockNumber>, anyhow::Error> {
+        let (from, direction) = match self.order.unwrap_or(BlocksOrder::Desc) {
+            BlocksOrder::Desc => {
+                anyhow::ensure!(
+                    self.from_block.is_none(),
+                    "from_block is only supported for the ascending order"
+                );
+                anyhow::ensure!(self.max_block != Some(0), "max_block must be positive");
+                (
+                    self.max_block.unwrap_or(999_999_999),
+                    PaginationDirection::Older,
+                )
+            }
+            BlocksOrder::Asc => {
+                anyhow::ensure!(
+                    self.max_block.is_none(),
+                    "max_block is only supported for the descending order"
+                );
+                (self.from_block.unwrap_or(0), PaginationDirection::Newer)
+            }
+        };
+        Ok(PaginationQuery {
+            from: BlockNumber(from),
+            limit,
+            direction,
+        })
+    }
+
+    /// Validates the filtering parameters of the query and converts them into
+    /// the storage filter.
+    pub fn block_range_filter(&self) -> Result<BlockRangeFilter, anyhow::Error> {
+        fn parse_fee(fee: &Option<String>) -> Result<Option<BigUint>, anyhow::Error> {
+            fee.as_ref()
+                .map(|fee| {
+                    BigUint::from_str(fee)
+                        .map_err(|_| anyhow::anyhow!("Fee must be a decimal string: '{}'", fee))
+                })
+                .transpose()
+        }
+
+        let filter = BlockRangeFilter {
+            min_tx_count: self.min_tx_count,
+            max_tx_count: self.max_tx_count,
+            fee_token: self.fee_token.map(TokenId),
+            min_total_fee: parse_fee(&self.min_total_fee)?,
+            max_total_fee: parse_fee(&self.max_total_fee)?,
+        };
+
+        if let (Some(min), Some(max)) = (filter.min_tx_count, filter.max_tx_count) {
+            anyhow::ensure!(
+                min <= max,
+                "min_tx_count must not be greater than max_tx_count"
+            );
+        }
+        // Fees paid in different tokens are not comparable.
+        anyhow::ensure!(
+            filter.fee_token.is_some()
+                || (filter.min_total_fee.is_none() && filter.max_total_fee.is_none()),
+            "fee_token is required for the fee bounds"
+        );
+        if let (Some(min), Some(max)) = (&filter.min_total_fee, &filter.max_total_fee) {
+            anyhow::ensure!(
+                min <= max,
+                "min_total_fee must not be greater than max_total_fee"
+            );
+        }
+
+        Ok(filter)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RejectedTxsQuery {
+    pub limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct MempoolTxsQuery {
+    pub limit: Option<u32>,
+}
+
+/// Transaction awaiting for the execution in the mempool.
+#[derive(Debug, Serialize)]
+pub struct MempoolTxEntry {
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    pub from: Address,
+    pub nonce: Nonce,
+    /// Decimal string representation of the fee, `None` for the transactions without fee.
+    pub fee: Option<String>,
+    pub fee_token: Option<TokenId>,
+    /// Time the transaction was received by the server.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SignedZkSyncTx> for MempoolTxEntry {
+    fn from(tx: SignedZkSyncTx) -> Self {
+        let fee_info = tx.tx.get_fee_info();
+        Self {
+            tx_hash: tx.tx.hash(),
+            tx_type: tx.tx.variance_name(),
+            from: tx.tx.account(),
+            nonce: tx.tx.nonce(),
+            fee: fee_info.as_ref().map(|(_, _, _, fee)| fee.to_string()),
+            fee_token: fee_info.and_then(|(_, token, _, _)| match token {
+                TokenLike::Id(token_id) => Some(token_id),
+                _ => None,
+            }),
+            created_at: tx.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BlockTransactionsQuery {
+    /// Type of the operations to return, e.g. `deposit` or `transfer`.
+    #[serde(rename = "type")]
+    pub op_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TxTypeBreakdownQuery {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+}
+
+/// Amounts of the operations of each type executed in the requested range of blocks.
+#[derive(Debug, Serialize)]
+pub struct TxTypeBreakdownResponse {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+    /// Operation type, e.g. `Transfer` or `Deposit`, mapped to the amount of such operations.
+    pub counts: BTreeMap<String, u64>,
+}
+
+/// Depth of the priority operations queue.
+#[derive(Debug, Serialize)]
+pub struct PriorityOpsStatsResponse {
+    /// Priority operations received from L1, both executed and pending ones.
+    pub total: u64,
+    /// Operations executed in the blocks, equal to the serial ID of the next expected operation.
+    pub processed: u64,
+    /// Operations received from L1 and awaiting for the execution.
+    pub pending: u64,
+    /// Part of the pending operations that doesn't have enough L1 confirmations yet.
+    pub unconfirmed: u64,
+}
+
+/// Progress of the proof of the block containing the withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalProofStatus {
+    /// Proof job isn't taken by any prover yet.
+    Queued,
+    Generating,
+    Generated,
+    /// Transaction publishing the proof is sent to L1.
+    SubmittedToL1,
+    /// Block is executed on L1, funds can be withdrawn.
+    Verified,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WithdrawalProofStatusResponse {
+    /// ID of the prover job of the block, `None` until the job is added to the queue.
+    pub circuit_job_id: Option<String>,
+    pub proof_status: WithdrawalProofStatus,
+    /// Unix timestamp (in seconds) when the proof is expected to be generated, based on
+    /// the average proof time. `None` if the proof is already generated or there is no estimation.
+    pub estimated_completion: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct FeeTokenUsageQuery {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+}
+
+impl FeeTokenUsageQuery {
+    const MAX_BLOCKS: u32 = 10_000;
+
+    /// Checks that the requested range of blocks is not empty and not too large.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        anyhow::ensure!(
+            self.from_block <= self.to_block,
+            "from_block must not be greater than to_block"
+        );
+        anyhow::ensure!(
+            *self.to_block - *self.from_block < Self::MAX_BLOCKS,
+            "Range must contain at most {} blocks",
+            Self::MAX_BLOCKS
+        );
+        Ok(())
+    }
+}
+
+/// Fees paid in a single token by the transactions of the requested range of blocks.
+#[derive(Debug, Serialize)]
+pub struct FeeTokenUsage {
+    pub token_id: TokenId,
+    pub symbol: String,
+    pub tx_count: u64,
+    /// Sum of the fees in the smallest units of the token.
+    pub total_fee: String,
+}
+
+impl From<StorageFeeTokenUsage> for FeeTokenUsage {
+    fn from(usage: StorageFeeTokenUsage) -> Self {
+        Self {
+            token_id: TokenId(usage.token_id as u32),
+            symbol: usage.symbol,
+            tx_count: usage.tx_count as u64,
+            total_fee: usage.total_fee.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeeTokenUsageResponse {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+    /// Tokens the fees were paid in, ordered by the token ID.
+    pub tokens: Vec<FeeTokenUsage>,
+}
+
+#[derive(Deserialize)]
+pub struct BlockTimesQuery {
+    /// Time window, one of `1h`, `24h` or `7d`.
+    pub window: Option<String>,
+}
+
+impl BlockTimesQuery {
+    const DEFAULT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+    /// Supported time windows. The set is fixed, so that every window has a single cache entry.
+    const WINDOWS: &'static [(&'static str, Duration)] = &[
+        ("1h", Duration::from_secs(60 * 60)),
+        ("24h", Duration::from_secs(24 * 60 * 60)),
+        ("7d", Duration::from_secs(7 * 24 * 60 * 60)),
+    ];
+
+    /// Parses the requested time window.
+    pub fn window(&self) -> Result<Duration, anyhow::Error> {
+        let window = match &self.window {
+            Some(window) => window,
+            None => return Ok(Self::DEFAULT_WINDOW),
+        };
+        Self::WINDOWS
+            .iter()
+            .find(|(name, _)| name == window)
+            .map(|(_, duration)| *duration)
+            .ok_or_else(|| {
+                anyhow::format_err!("Unknown time window, expected one of `1h`, `24h` or `7d`")
+            })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProverLatencyQuery {
+    pub from_block: u32,
+    pub to_block: u32,
+    /// Blocks are grouped by the hour of the commitment by default.
+    pub bucket_size: Option<LatencyBucketSize>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LatencyBucketSize {
+    Hour,
+    Day,
+}
+
+impl From<LatencyBucketSize> for ProofLatencyBucketSize {
+    fn from(bucket_size: LatencyBucketSize) -> Self {
+        match bucket_size {
+            LatencyBucketSize::Hour => Self::Hour,
+            LatencyBucketSize::Day => Self::Day,
+        }
+    }
+}
+
+impl ProverLatencyQuery {
+    const MAX_BLOCKS: u32 = 10_000;
+
+    /// Checks that the requested range of blocks is not empty and not too large.
+    pub fn validate(&self) -> Result<ProofLatencyBucketSize, anyhow::Error> {
+        anyhow::ensure!(
+            self.from_block <= self.to_block,
+            "from_block must not be greater than to_block"
+        );
+        anyhow::ensure!(
+            self.to_block - self.from_block < Self::MAX_BLOCKS,
+            "Range must contain at most {} blocks",
+            Self::MAX_BLOCKS
+        );
+        Ok(self.bucket_size.unwrap_or(LatencyBucketSize::Hour).into())
+    }
+}
+
+/// Proof generation latency of the blocks committed within the period.
+/// The latency is measured from the block commitment till its proof is stored.
+#[derive(Debug, Serialize)]
+pub struct LatencyBucket {
+    /// Unix timestamp (in seconds) of the period start.
+    pub period_start: i64,
+    pub avg_proof_ms: f64,
+    pub max_proof_ms: f64,
+    pub min_proof_ms: f64,
+    pub block_count: u32,
+}
+
+impl From<StorageProofLatencyBucket> for LatencyBucket {
+    fn from(bucket: StorageProofLatencyBucket) -> Self {
+        Self {
+            period_start: bucket.period_start.timestamp(),
+            avg_proof_ms: bucket.avg_proof_ms,
+            max_proof_ms: bucket.max_proof_ms,
+            min_proof_ms: bucket.min_proof_ms,
+            block_count: bucket.block_count as u32,
+        }
+    }
+}
+
+/// Amount of the durations falling into a histogram bucket.
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockTimesBucket {
+    /// Inclusive upper bound of the bucket in seconds, `None` for the last bucket.
+    pub up_to_secs: Option<u64>,
+    pub count: u64,
+}
+
+impl BlockTimesBucket {
+    /// Builds the histogram of the durations given the upper bounds of the buckets.
+    /// Durations greater than the last bound fall into the additional unbounded bucket.
+    fn histogram(bounds: &[u64], durations: &[u64]) -> Vec<Self> {
+        let mut histogram: Vec<_> = bounds
+            .iter()
+            .map(|&up_to_secs| Some(up_to_secs))
+            .chain(std::iter::once(None))
+            .map(|up_to_secs| Self {
+                up_to_secs,
+                count: 0,
+            })
+            .collect();
+        for &duration in durations {
+            let bucket = bounds
+                .iter()
+                .position(|&up_to_secs| duration <= up_to_secs)
+                .unwrap_or(bounds.len());
+            histogram[bucket].count += 1;
+        }
+        histogram
+    }
+}
+
+fn average_secs(durations: &[u64]) -> Option<f64> {
+    if durations.is_empty() {
+        None
+    } else {
+        Some(durations.iter().sum::<u64>() as f64 / durations.len() as f64)
+    }
+}
+
+/// Distribution of the intervals between the consecutive blocks created within the window.
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockTimesResponse {
+    pub window_secs: u64,
+    /// Amount of the intervals the histogram is built from.
+    pub intervals: u64,
+    /// `None` if less than two blocks were created within the window.
+    pub avg_interval_secs: Option<f64>,
+    /// `None` if less than two blocks were created within the window.
+    pub max_interval_secs: Option<u64>,
+    pub histogram: Vec<BlockTimesBucket>,
+}
+
+impl BlockTimesResponse {
+    /// Upper bounds of the histogram buckets in seconds.
+    const BUCKETS: [u64; 8] = [5, 10, 30, 60, 120, 300, 600, 1800];
+
+    pub fn new(window: Duration, intervals: &[u64]) -> Self {
+        Self {
+            window_secs: window.as_secs(),
+            intervals: intervals.len() as u64,
+            avg_interval_secs: average_secs(intervals),
+            max_interval_secs: intervals.iter().copied().max(),
+            histogram: BlockTimesBucket::histogram(&Self::BUCKETS, intervals),
+        }
+    }
+}
+
+/// Distribution of the times between the commitment and the execution of the blocks
+/// executed within the window, i.e. how long it takes to prove the blocks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProvingTimesResponse {
+    pub window_secs: u64,
+    /// Amount of the executed blocks the histogram is built from.
+    pub blocks: u64,
+    /// `None` if no blocks were executed within the window.
+    pub avg_proving_time_secs: Option<f64>,
+    /// `None` if no blocks were executed within the window.
+    pub median_proving_time_secs: Option<u64>,
+    /// `None` if no blocks were executed within the window.
+    pub max_proving_time_secs: Option<u64>,
+    pub histogram: Vec<BlockTimesBucket>,
+}
+
+impl ProvingTimesResponse {
+    /// Upper bounds of the histogram buckets in seconds.
+    const BUCKETS: [u64; 8] = [300, 600, 1800, 3600, 7200, 14400, 43200, 86400];
+
+    pub fn new(window: Duration, proving_times: &[u64]) -> Self {
+        let mut sorted_times = proving_times.to_vec();
+        sorted_times.sort_unstable();
+        Self {
+            window_secs: window.as_secs(),
+            blocks: proving_times.len() as u64,
+            avg_proving_time_secs: average_secs(proving_times),
+            median_proving_time_secs: sorted_times.get(sorted_times.len() / 2).copied(),
+            max_proving_time_secs: sorted_times.last().copied(),
+            histogram: BlockTimesBucket::histogram(&Self::BUCKETS, proving_times),
+        }
+    }
+}
+
+/// Stats response along with the time it was computed at.
+#[derive(Debug, Clone)]
+pub struct CachedStats<T> {
+    pub response: T,
+    pub loaded_at: Instant,
+}
+
+pub type CachedBlockTimes = CachedStats<BlockTimesResponse>;
+pub type CachedProvingTimes = CachedStats<ProvingTimesResponse>;
+
+impl<T> CachedStats<T> {
+    /// Histogram doesn't change much within a minute even for the shortest windows.
+    const TTL: Duration = Duration::from_secs(60);
+
+    pub fn new(response: T) -> Self {
+        Self {
+            response,
+            loaded_at: Instant::now(),
+        }
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        self.loaded_at.elapsed() < Self::TTL
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FeeScheduleQuery {
+    pub token_id: TokenId,
+}
+
+/// Fee for a transfer in the requested token, paid to get the transaction
+/// committed within the `estimated_commitment_blocks` blocks.
+#[derive(Debug, Serialize)]
+pub struct FeeBreakdown {
+    pub total_fee: String,
+    pub gas_fee: String,
+    pub zkp_fee: String,
+    pub estimated_commitment_blocks: u32,
+}
+
+impl FeeBreakdown {
+    /// Scales the gas part of the fee by the multiplier, the zkp part doesn't depend
+    /// on the network congestion and is left as is.
+    pub fn new(
+        fee: &Fee,
+        gas_multiplier: &Ratio<BigUint>,
+        estimated_commitment_blocks: u32,
+    ) -> Self {
+        let gas_fee = (Ratio::from_integer(fee.gas_fee.clone()) * gas_multiplier)
+            .ceil()
+            .to_integer();
+        Self {
+            total_fee: (&gas_fee + &fee.zkp_fee).to_string(),
+            gas_fee: gas_fee.to_string(),
+            zkp_fee: fee.zkp_fee.to_string(),
+            estimated_commitment_blocks,
+        }
+    }
+}
+
+/// There is no fast tier: transfers don't seal the block regardless of the fee paid,
+/// only the fast withdrawals do.
+#[derive(Debug, Serialize)]
+pub struct FeeScheduleResponse {
+    pub slow: FeeBreakdown,
+    pub standard: FeeBreakdown,
+}
+
+#[derive(Deserialize)]
+pub struct BlockByIdQuery {
+    /// Comma-separated list of the additional data to embed into the response.
+    /// Supported values are `proof`, `l1_hashes` and `statistics`.
+    pub expand: Option<String>,
+}
+
+impl BlockByIdQuery {
+    pub fn expansion(&self) -> Result<BlockDetailsExpansion, anyhow::Error> {
+        let mut expansion = BlockDetailsExpansion::default();
+        let values = self.expand.as_deref().unwrap_or_default();
+        for value in values.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            match value {
+                "proof" => expansion.proof = true,
+                "l1_hashes" => expansion.l1_hashes = true,
+                "statistics" => expansion.statistics = true,
+                unknown => anyhow::bail!("Unknown expand value: '{}'", unknown),
+            }
+        }
+        Ok(expansion)
+    }
+}
+
+/// Identifier of the block in the `/blocks/{block_id}` path: either the block number
+/// or the hash of the block state root.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockId {
+    Number(BlockNumber),
+    StateRoot(Vec<u8>),
+}
+
+impl FromStr for BlockId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const HASH_STRING_SIZE: usize = 32 * 2; // 32 bytes, 2 symbols per byte.
+
+        if s.len() < HASH_STRING_SIZE {
+            return Ok(Self::Number(BlockNumber(s.parse()?)));
+        }
+        let hex_hash = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("sync-bl:"))
+            .unwrap_or(s);
+        let hash = hex::decode(hex_hash)?;
+        anyhow::ensure!(hash.len() == 32, "State root hash should be 32 bytes long");
+        Ok(Self::StateRoot(hash))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockStatistics {
+    /// Number of the successful transactions and the priority operations in the block.
+    pub tx_count: i64,
+    /// Sums of fees of the block transactions per fee token, ordered by the token ID.
+    pub fees: Vec<BlockTokenFee>,
+}
+
+/// Fees paid in a single token by the transactions of a block.
+#[derive(Debug, Serialize)]
+pub struct BlockTokenFee {
+    pub token_id: TokenId,
+    /// Sum of the fees in the smallest units of the token.
+    pub total_fee: String,
+}
+
+/// Block details with the optional data requested via the `expand` parameter.
+#[derive(Debug, Serialize)]
+pub struct BlockInfo {
+    #[serde(flatten)]
+    pub details: StorageBlockDetails,
+    /// Alias of `new_state_root`, i.e. the Merkle root of the accounts tree after the block.
+    /// zkSync blocks have no hash of their own, but the state root is unique for every block.
+    #[serde(with = "BytesToHexSerde::<SyncBlockPrefix>")]
+    pub block_hash: Vec<u8>,
+    /// State root of the previous block, linking the blocks into a chain. The genesis
+    /// state root is reported for the first block.
+    #[serde(with = "OptionBytesToHexSerde::<SyncBlockPrefix>")]
+    pub prev_block_hash: Option<Vec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l1_hashes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<BlockStatistics>,
+    /// Sum of fees paid in the block converted to ETH (in wei), `None` if token prices are unknown.
+    pub total_fee_eth_wei: Option<String>,
+    /// Average fee per operation in the block in USD, `None` if token prices are unknown
+    /// or the block has no operations.
+    pub avg_tx_fee_usd: Option<f64>,
+    /// Number of unique senders of both L1 and L2 operations in the block,
+    /// `None` unless requested via the `include_unique_senders` parameter.
+    pub unique_senders: Option<u32>,
+    /// Gas used by the L1 transaction committing the block, `None` until it's mined.
+    /// L1 transactions process several blocks at once, so the gas is shared by all of them.
+    pub commit_gas_used: Option<u64>,
+    /// Gas used by the L1 transaction publishing the block proof, `None` until it's mined.
+    pub verify_gas_used: Option<u64>,
+    /// Ethereum address that sent the L1 transaction executing the block, `None` until it's mined.
+    pub verifier_address: Option<String>,
+    #[serde(flatten)]
+    pub timings: BlockTimings,
+}
+
+/// Unix timestamps of the block commitment and verification, used for the latency analysis.
+///
+/// Fields are named differently from the `committed_at` and `verified_at` fields of the
+/// block details, since the latter are reported in the RFC 3339 format.
+#[derive(Debug, Serialize)]
+pub struct BlockTimings {
+    pub committed_at_timestamp: Option<i64>,
+    pub verified_at_timestamp: Option<i64>,
+    /// Seconds passed between the block commitment and verification, which includes both
+    /// the proof generation and the submission of the proof to L1.
+    /// `None` for the blocks that are not verified yet.
+    pub proof_time_secs: Option<u64>,
+}
+
+impl BlockTimings {
+    pub fn new(details: &StorageBlockDetails) -> Self {
+        let committed_at = details.committed_at.timestamp();
+        let verified_at = details
+            .verified_at
+            .map(|verified_at| verified_at.timestamp());
+        Self {
+            committed_at_timestamp: Some(committed_at),
+            verified_at_timestamp: verified_at,
+            proof_time_secs: verified_at
+                .map(|verified_at| verified_at.saturating_sub(committed_at).max(0) as u64),
+        }
+    }
+}
+
+/// Serialized response of the `/tokens` endpoint along with its `ETag`.
+/// Refreshed in background, see `ApiV01::spawn_token_list_updater`.
+#[derive(Debug, Clone)]
+pub struct CachedTokenList {
+    pub body: Bytes,
+    pub etag: String,
+}
+
+impl CachedTokenList {
+    pub fn new(tokens: &[TokenResponse]) -> serde_json::Result<Self> {
+        let body = serde_json::to_vec(tokens)?;
+        let etag = format!("\"{}\"", hex::encode(&*sha256(&body)));
+        Ok(Self {
+            body: body.into(),
+            etag,
+        })
+    }
+}
+
+/// Fee statistics of the block, see `ApiV01::get_blocks_fees`.
+#[derive(Debug, Clone, Default)]
+pub struct BlockFees {
+    pub total_fee_eth_wei: Option<String>,
+    pub avg_tx_fee_usd: Option<f64>,
+}
+
+/// Gas used by the L1 transactions of the block, see `ApiV01::get_blocks_gas_used`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockGasUsed {
+    pub commit_gas_used: Option<u64>,
+    pub verify_gas_used: Option<u64>,
+}
+
+impl From<StorageBlockDetails> for BlockInfo {
+    fn from(details: StorageBlockDetails) -> Self {
+        Self {
+            timings: BlockTimings::new(&details),
+            block_hash: details.new_state_root.clone(),
+            prev_block_hash: None,
+            details,
+            proof: None,
+            l1_hashes: None,
+            statistics: None,
+            total_fee_eth_wei: None,
+            avg_tx_fee_usd: None,
+            unique_senders: None,
+            commit_gas_used: None,
+            verify_gas_used: None,
+            verifier_address: None,
+        }
+    }
+}
+
+impl BlockInfo {
+    pub fn new(block: StorageExpandedBlockDetails, expansion: &BlockDetailsExpansion) -> Self {
+        let details = block.details();
+        let statistics = if expansion.statistics {
+            Some(BlockStatistics {
+                tx_count: block.tx_count.unwrap_or_default(),
+                fees: block
+                    .fee_token_ids
+                    .unwrap_or_default()
+                    .into_iter()
+                    .zip(block.fee_token_totals.unwrap_or_default())
+                    .map(|(token_id, total_fee)| BlockTokenFee {
+                        token_id: TokenId(token_id as u32),
+                        total_fee,
+                    })
+                    .collect(),
+            })
+        } else {
+            None
+        };
+        Self {
+            timings: BlockTimings::new(&details),
+            block_hash: details.new_state_root.clone(),
+            prev_block_hash: None,
+            details,
+            proof: block.proof,
+            l1_hashes: block
+                .l1_hashes
+                .or_else(|| expansion.l1_hashes.then(Vec::new)),
+            statistics,
+            total_fee_eth_wei: None,
+            avg_tx_fee_usd: None,
+            unique_senders: None,
+            commit_gas_used: None,
+            verify_gas_used: None,
+            verifier_address: None,
+        }
+    }
+
+    /// Fills the block fee statistics.
+    pub fn with_fees(self, fees: BlockFees) -> Self {
+        Self {
+            total_fee_eth_wei: fees.total_fee_eth_wei,
+            avg_tx_fee_usd: fees.avg_tx_fee_usd,
+            ..self
+        }
+    }
+
+    /// Fills the state root of the previous block.
+    pub fn with_prev_block_hash(self, prev_block_hash: Option<Vec<u8>>) -> Self {
+        Self {
+            prev_block_hash,
+            ..self
+        }
+    }
+
+    /// Fills the gas used by the L1 transactions of the block.
+    pub fn with_gas_used(self, gas_used: BlockGasUsed) -> Self {
+        Self {
+            commit_gas_used: gas_used.commit_gas_used,
+            verify_gas_used: gas_used.verify_gas_used,
+            ..self
+        }
+    }
+
+    /// Fills the sender of the L1 transaction executing the block.
+    pub fn with_verifier_address(self, verifier_address: Option<String>) -> Self {
+        Self {
+            verifier_address,
+            ..self
+        }
+    }
+
+    /// Fills the number of unique senders of the block operations.
+    pub fn with_unique_senders(self, unique_senders: Option<u32>) -> Self {
+        Self {
+            unique_senders,
+            ..self
+        }
+    }
 }
 
 #[derive(Deserialize)]
 pub struct BlockExplorerSearchQuery {
     pub query: String,
 }
+
+/// Entity found by the explorer search query other than a block. Blocks are reported
+/// without the `kind` tag, and numeric queries are only resolved as account IDs
+/// if there is no block with such a height.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SearchResult {
+    Account { id: AccountId, address: Address },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn history_item(tx: serde_json::Value, success: Option<bool>) -> TransactionsHistoryItem {
+        TransactionsHistoryItem {
+            tx_id: "5,3".into(),
+            hash: Some("sync-tx:0000".into()),
+            eth_block: None,
+            pq_id: None,
+            tx,
+            success,
+            fail_reason: None,
+            commited: true,
+            verified: false,
+            created_at: Utc::now(),
+            batch_id: None,
+            batch_hash: None,
+            eth_signature: None,
+            is_priority_op: false,
+            usd_value_at_time: None,
+        }
+    }
+
+    /// Checks that the operations are classified from the point of view of the account.
+    #[test]
+    fn timeline_event_from_history_item() {
+        let account = Address::repeat_byte(1);
+        let other = Address::repeat_byte(2);
+        let test_vector = vec![
+            (
+                json!({ "type": "Transfer", "from": account, "to": other, "token": "ETH" }),
+                AccountEventType::TransferOut,
+            ),
+            (
+                json!({ "type": "Transfer", "from": other, "to": account, "token": "ETH" }),
+                AccountEventType::TransferIn,
+            ),
+            // NFTs are referenced by their IDs.
+            (
+                json!({ "type": "Transfer", "from": account, "to": other, "token": 65536 }),
+                AccountEventType::NftTransfer,
+            ),
+            (json!({ "type": "Deposit" }), AccountEventType::Deposit),
+            (json!({ "type": "FullExit" }), AccountEventType::Withdrawal),
+            (
+                json!({ "type": "WithdrawNFT" }),
+                AccountEventType::Withdrawal,
+            ),
+            (
+                json!({ "type": "ChangePubKey" }),
+                AccountEventType::ChangePubkey,
+            ),
+            (
+                json!({ "type": "ForcedExit" }),
+                AccountEventType::ForcedExit,
+            ),
+            (json!({ "type": "MintNFT" }), AccountEventType::NftMint),
+            (json!({ "type": "Swap" }), AccountEventType::Swap),
+        ];
+        for (tx, expected_type) in test_vector {
+            let event = AccountTimelineEvent::from_history_item(
+                history_item(tx.clone(), Some(true)),
+                account,
+            )
+            .unwrap_or_else(|| panic!("{}", tx));
+            assert_eq!(event.event_type, expected_type, "{}", tx);
+            assert_eq!(event.block_number, 5);
+            assert_eq!(event.tx_id, "5,3");
+        }
+
+        // Priority operations have no success flag.
+        let deposit = history_item(json!({ "type": "Deposit" }), None);
+        assert!(AccountTimelineEvent::from_history_item(deposit, account).is_some());
+    }
+
+    /// Checks that the failed transactions and the unknown operations are not reported.
+    #[test]
+    fn timeline_event_skipped() {
+        let account = Address::repeat_byte(1);
+        let failed_transfer = history_item(
+            json!({ "type": "Transfer", "from": account, "token": "ETH" }),
+            Some(false),
+        );
+        assert!(AccountTimelineEvent::from_history_item(failed_transfer, account).is_none());
+
+        let close = history_item(json!({ "type": "Close" }), Some(true));
+        assert!(AccountTimelineEvent::from_history_item(close, account).is_none());
+    }
+}
@@ -0,0 +1,543 @@
+//! WebSocket notifications of the REST API v0.1.
+//!
+//! Clients connected to the `/ws` endpoint receive the network status on connection
+//! and on every update of it, so that they don't have to poll the `/status` endpoint.
+//! Additionally, clients may subscribe to the transactions of an account by sending the
+//! `{"subscribe": "account", "address": "0x..."}` message. Transactions affecting the
+//! account are sent once their block is committed, the subscription only covers the blocks
+//! committed after it's made.
+//!
+//! Status is updated periodically (see `SharedNetworkStatus::start_updater_detached`),
+//! so the notifications may be delayed by the update interval.
+//!
+//! Transactions of the subscribed accounts are loaded by a single `WsNotifier` shared by all
+//! the clients, which queries each subscribed account once per committed block regardless of
+//! the number of its subscribers. The amount of the connected clients is capped by
+//! `api.rest.max_ws_connections`.
+//!
+//! Unlike `zksync_event_listener`, which is a separately deployed server streaming the raw
+//! account state changes by account IDs, this endpoint is served by every API server and sends
+//! the transactions by addresses in the same format as the account history endpoints, so that
+//! the wallets can append them to the already loaded history.
+
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+// External uses
+use actix::prelude::*;
+use actix_web_actors::ws;
+use futures::{channel::mpsc, stream};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+
+// Workspace uses
+use zksync_storage::{chain::operations_ext::records::TransactionsHistoryItem, ConnectionPool};
+use zksync_types::{Address, BlockNumber};
+
+// Local uses
+use crate::api_server::rest::{network_status::NetworkStatus, v01::types::HistoryCursor};
+
+/// Maximum amount of the accounts a single client can subscribe to.
+const MAX_ACCOUNT_SUBSCRIPTIONS: usize = 16;
+/// Amount of the transactions of a single account loaded per query.
+const ACCOUNT_TXS_BATCH_SIZE: u64 = 100;
+
+/// Message sent by the client.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "subscribe", rename_all = "camelCase")]
+enum SubscribeRequest {
+    Account { address: Address },
+}
+
+/// Message sent to the client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Notification<'a> {
+    Status {
+        status: &'a NetworkStatus,
+    },
+    Subscribed {
+        address: Address,
+    },
+    AccountTx {
+        address: Address,
+        tx: &'a TransactionsHistoryItem,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Transaction of the subscribed account passed from the notifier to the client actors.
+#[derive(Debug, Clone)]
+struct AccountTx {
+    address: Address,
+    tx: Arc<TransactionsHistoryItem>,
+}
+
+#[derive(Debug)]
+struct AccountSubscriber {
+    sender: mpsc::UnboundedSender<AccountTx>,
+    /// Last block committed when the subscription was made, the transactions of this
+    /// and the earlier blocks are not sent to the subscriber.
+    start_block: u64,
+}
+
+#[derive(Debug)]
+struct AccountSubscription {
+    /// Position of the latest transaction sent to the subscribers.
+    cursor: (u64, u64),
+    subscribers: Vec<AccountSubscriber>,
+}
+
+/// Loads the transactions of the subscribed accounts and fans them out to the subscribers.
+/// Shared by all the API server workers.
+#[derive(Debug, Clone)]
+pub struct WsNotifier {
+    /// Permits for the connected clients.
+    connections: Arc<Semaphore>,
+    accounts: Arc<Mutex<HashMap<Address, AccountSubscription>>>,
+}
+
+impl WsNotifier {
+    pub fn new(max_connections: u32) -> Self {
+        Self {
+            connections: Arc::new(Semaphore::new(max_connections as usize)),
+            accounts: Arc::default(),
+        }
+    }
+
+    /// Creates the actor for the connecting client, `None` if there are too many connected ones.
+    pub fn connect(&self, status: watch::Receiver<NetworkStatus>) -> Option<StatusSubscriber> {
+        let permit = self.connections.clone().try_acquire_owned().ok()?;
+        Some(StatusSubscriber::new(status, self.clone(), permit))
+    }
+
+    /// Sends the transactions of the subscribed accounts on every committed block.
+    pub async fn run(
+        self,
+        mut status: watch::Receiver<NetworkStatus>,
+        connection_pool: ConnectionPool,
+    ) {
+        let mut last_committed = status.borrow().last_committed;
+        while status.changed().await.is_ok() {
+            let committed = status.borrow().last_committed;
+            if committed <= last_committed {
+                continue;
+            }
+            last_committed = committed;
+            // Transactions that failed to load are sent on the next update, since the cursors
+            // are only advanced for the sent ones.
+            if let Err(err) = self.notify_accounts(&connection_pool, last_committed).await {
+                vlog::warn!("Couldn't load the subscribed accounts txs: {}", err);
+            }
+        }
+    }
+
+    fn subscribe(
+        &self,
+        address: Address,
+        last_committed: BlockNumber,
+        subscriber: mpsc::UnboundedSender<AccountTx>,
+    ) {
+        let start_block = u64::from(*last_committed);
+        let mut accounts = self.accounts.lock().unwrap();
+        // The cursor of an already subscribed account may be behind the last committed block,
+        // such transactions are filtered out by `fan_out` for the new subscriber.
+        accounts
+            .entry(address)
+            .or_insert_with(|| AccountSubscription {
+                // Block index is stored as `INT`, its maximum value skips the whole block.
+                cursor: (start_block, i32::MAX as u64),
+                subscribers: Vec::new(),
+            })
+            .subscribers
+            .push(AccountSubscriber {
+                sender: subscriber,
+                start_block,
+            });
+    }
+
+    fn unsubscribe(
+        &self,
+        addresses: &HashSet<Address>,
+        subscriber: &mpsc::UnboundedSender<AccountTx>,
+    ) {
+        let mut accounts = self.accounts.lock().unwrap();
+        for address in addresses {
+            if let Some(subscription) = accounts.get_mut(address) {
+                subscription
+                    .subscribers
+                    .retain(|other| !other.sender.same_receiver(subscriber));
+                if subscription.subscribers.is_empty() {
+                    accounts.remove(address);
+                }
+            }
+        }
+    }
+
+    /// Returns the subscribed accounts with their cursors, dropping the disconnected subscribers.
+    fn active_accounts(&self) -> Vec<(Address, (u64, u64))> {
+        let mut accounts = self.accounts.lock().unwrap();
+        accounts.retain(|_, subscription| {
+            subscription
+                .subscribers
+                .retain(|subscriber| !subscriber.sender.is_closed());
+            !subscription.subscribers.is_empty()
+        });
+        accounts
+            .iter()
+            .map(|(address, subscription)| (*address, subscription.cursor))
+            .collect()
+    }
+
+    /// Sends the transactions to the subscribers of the account and advances its cursor.
+    fn fan_out(&self, address: Address, txs: Vec<TransactionsHistoryItem>, cursor: (u64, u64)) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let subscription = match accounts.get_mut(&address) {
+            Some(subscription) => subscription,
+            None => return,
+        };
+        for tx in txs {
+            let block_number = HistoryCursor::from_tx_id(&tx.tx_id)
+                .map(|HistoryCursor(block_number, _)| block_number)
+                .unwrap_or_default();
+            let tx = AccountTx {
+                address,
+                tx: Arc::new(tx),
+            };
+            for subscriber in &subscription.subscribers {
+                if block_number <= subscriber.start_block {
+                    continue;
+                }
+                // Disconnected subscribers are dropped by `active_accounts`.
+                subscriber.sender.unbounded_send(tx.clone()).ok();
+            }
+        }
+        subscription.cursor = subscription.cursor.max(cursor);
+    }
+
+    /// Sends the transactions of the subscribed accounts included in the blocks committed
+    /// since the previous notification. All the accounts are queried over a single connection.
+    async fn notify_accounts(
+        &self,
+        connection_pool: &ConnectionPool,
+        last_committed: BlockNumber,
+    ) -> anyhow::Result<()> {
+        let accounts = self.active_accounts();
+        if accounts.is_empty() {
+            return Ok(());
+        }
+
+        let mut storage = connection_pool.access_storage().await?;
+        for (address, mut cursor) in accounts {
+            loop {
+                let txs = storage
+                    .chain()
+                    .operations_ext_schema()
+                    .get_account_transactions_history_newer_than(
+                        &address,
+                        Some(cursor),
+                        ACCOUNT_TXS_BATCH_SIZE,
+                        &Default::default(),
+                    )
+                    .await?;
+                let is_full_batch = txs.len() as u64 == ACCOUNT_TXS_BATCH_SIZE;
+                let (txs, next_cursor) = committed_txs(txs, u64::from(*last_committed));
+                let next_cursor = match next_cursor {
+                    Some(next_cursor) => next_cursor,
+                    None => break,
+                };
+                self.fan_out(address, txs, next_cursor);
+                // The rest of the transactions are either in the pending block or not loaded yet.
+                if !is_full_batch {
+                    break;
+                }
+                cursor = next_cursor;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Takes the transactions of the committed blocks out of the loaded ones, along with the position
+/// of the latest of them. Transactions are expected to be ordered by their positions.
+fn committed_txs(
+    txs: Vec<TransactionsHistoryItem>,
+    last_committed: u64,
+) -> (Vec<TransactionsHistoryItem>, Option<(u64, u64)>) {
+    let mut cursor = None;
+    let mut committed = Vec::with_capacity(txs.len());
+    for tx in txs {
+        // Transactions of the pending block are stored before the block is committed.
+        match HistoryCursor::from_tx_id(&tx.tx_id) {
+            Some(HistoryCursor(block_number, block_index)) if block_number <= last_committed => {
+                cursor = Some((block_number, block_index));
+                committed.push(tx);
+            }
+            _ => break,
+        }
+    }
+    (committed, cursor)
+}
+
+/// The WebSocket actor. Created for each connected client.
+#[derive(Debug)]
+pub struct StatusSubscriber {
+    status: watch::Receiver<NetworkStatus>,
+    notifier: WsNotifier,
+    /// Sender of the subscribed accounts transactions, cloned to the notifier on subscription.
+    txs_sender: mpsc::UnboundedSender<AccountTx>,
+    /// Taken once the actor is started.
+    txs_receiver: Option<mpsc::UnboundedReceiver<AccountTx>>,
+    accounts: HashSet<Address>,
+    /// Frees the connection slot once the client is disconnected.
+    _permit: OwnedSemaphorePermit,
+}
+
+impl StatusSubscriber {
+    fn new(
+        status: watch::Receiver<NetworkStatus>,
+        notifier: WsNotifier,
+        permit: OwnedSemaphorePermit,
+    ) -> Self {
+        let (txs_sender, txs_receiver) = mpsc::unbounded();
+        Self {
+            status,
+            notifier,
+            txs_sender,
+            txs_receiver: Some(txs_receiver),
+            accounts: HashSet::new(),
+            _permit: permit,
+        }
+    }
+
+    fn notify(ctx: &mut <Self as Actor>::Context, notification: &Notification<'_>) {
+        let json = serde_json::to_string(notification).unwrap();
+        ctx.text(json);
+    }
+
+    fn subscribe(&mut self, request: SubscribeRequest, ctx: &mut <Self as Actor>::Context) {
+        let SubscribeRequest::Account { address } = request;
+        if !self.accounts.contains(&address) {
+            if self.accounts.len() >= MAX_ACCOUNT_SUBSCRIPTIONS {
+                let message = format!(
+                    "Can't subscribe to more than {} accounts",
+                    MAX_ACCOUNT_SUBSCRIPTIONS
+                );
+                Self::notify(ctx, &Notification::Error { message });
+                return;
+            }
+            let last_committed = self.status.borrow().last_committed;
+            self.notifier
+                .subscribe(address, last_committed, self.txs_sender.clone());
+            self.accounts.insert(address);
+        }
+        Self::notify(ctx, &Notification::Subscribed { address });
+    }
+}
+
+impl Actor for StatusSubscriber {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let status = self.status.borrow().clone();
+        Self::notify(ctx, &Notification::Status { status: &status });
+
+        let updates = stream::unfold(self.status.clone(), |mut status| async move {
+            status.changed().await.ok()?;
+            let current = status.borrow().clone();
+            Some((current, status))
+        });
+        ctx.add_stream(updates);
+        if let Some(txs) = self.txs_receiver.take() {
+            ctx.add_stream(txs);
+        }
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        self.notifier.unsubscribe(&self.accounts, &self.txs_sender);
+    }
+}
+
+impl StreamHandler<NetworkStatus> for StatusSubscriber {
+    fn handle(&mut self, status: NetworkStatus, ctx: &mut Self::Context) {
+        Self::notify(ctx, &Notification::Status { status: &status });
+    }
+}
+
+impl StreamHandler<AccountTx> for StatusSubscriber {
+    fn handle(&mut self, AccountTx { address, tx }: AccountTx, ctx: &mut Self::Context) {
+        Self::notify(ctx, &Notification::AccountTx { address, tx: &tx });
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for StatusSubscriber {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(request) => self.subscribe(request, ctx),
+                Err(err) => {
+                    let message = format!("Invalid subscription request: {}", err);
+                    Self::notify(ctx, &Notification::Error { message });
+                }
+            },
+            Ok(ws::Message::Close(reason)) => {
+                // Send back the close frame.
+                ctx.close(reason);
+                ctx.stop();
+            }
+            Err(err) => {
+                let reason = Some(ws::CloseReason {
+                    code: ws::CloseCode::Error,
+                    description: Some(err.to_string()),
+                });
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn history_item(tx_id: &str) -> TransactionsHistoryItem {
+        TransactionsHistoryItem {
+            tx_id: tx_id.into(),
+            hash: Some("sync-tx:0000".into()),
+            eth_block: None,
+            pq_id: None,
+            tx: json!({ "type": "Transfer" }),
+            success: Some(true),
+            fail_reason: None,
+            commited: true,
+            verified: false,
+            created_at: Utc::now(),
+            batch_id: None,
+            batch_hash: None,
+            eth_signature: None,
+            is_priority_op: false,
+            usd_value_at_time: None,
+        }
+    }
+
+    /// Checks that only the account subscriptions with valid addresses are accepted.
+    #[test]
+    fn subscribe_request_parsing() {
+        let address = Address::repeat_byte(0x11);
+        let request: SubscribeRequest =
+            serde_json::from_value(json!({ "subscribe": "account", "address": address })).unwrap();
+        assert_eq!(request, SubscribeRequest::Account { address });
+
+        let invalid_requests = vec![
+            json!({ "subscribe": "account" }),
+            json!({ "subscribe": "account", "address": "0x11" }),
+            json!({ "subscribe": "block", "address": address }),
+            json!({ "address": address }),
+        ];
+        for request in invalid_requests {
+            assert!(
+                serde_json::from_value::<SubscribeRequest>(request.clone()).is_err(),
+                "{}",
+                request
+            );
+        }
+    }
+
+    /// Checks that the transactions of the pending block are not sent and don't advance the cursor.
+    #[test]
+    fn committed_txs_cursor() {
+        let txs = vec![
+            history_item("5,1"),
+            history_item("5,3"),
+            history_item("6,0"),
+        ];
+        let (committed, cursor) = committed_txs(txs, 5);
+        let tx_ids: Vec<_> = committed.iter().map(|tx| tx.tx_id.as_str()).collect();
+        assert_eq!(tx_ids, vec!["5,1", "5,3"]);
+        assert_eq!(cursor, Some((5, 3)));
+
+        let (committed, cursor) = committed_txs(vec![history_item("6,0")], 5);
+        assert!(committed.is_empty());
+        assert_eq!(cursor, None);
+    }
+
+    /// Checks that the transactions are sent to every subscriber of the account once,
+    /// except for the ones committed before the subscription, and the disconnected
+    /// subscribers are dropped.
+    #[test]
+    fn notifier_fan_out() {
+        let notifier = WsNotifier::new(2);
+        let address = Address::repeat_byte(0x11);
+        let (first, mut first_txs) = mpsc::unbounded();
+        let (second, mut second_txs) = mpsc::unbounded();
+        let other_address = Address::repeat_byte(0x22);
+        let (other, mut other_txs) = mpsc::unbounded();
+        notifier.subscribe(address, BlockNumber(4), first);
+        notifier.subscribe(address, BlockNumber(5), second);
+        notifier.subscribe(other_address, BlockNumber(4), other);
+
+        // The cursor is set by the first subscriber and skips its whole block.
+        let mut accounts = notifier.active_accounts();
+        accounts.sort();
+        assert_eq!(
+            accounts,
+            vec![
+                (address, (4, i32::MAX as u64)),
+                (other_address, (4, i32::MAX as u64))
+            ]
+        );
+
+        // The second subscriber only receives the transactions of the blocks committed
+        // after its subscription.
+        notifier.fan_out(
+            address,
+            vec![history_item("5,1"), history_item("6,0")],
+            (6, 0),
+        );
+        let tx = first_txs.try_next().unwrap().unwrap();
+        assert_eq!(tx.address, address);
+        assert_eq!(tx.tx.tx_id, "5,1");
+        assert_eq!(first_txs.try_next().unwrap().unwrap().tx.tx_id, "6,0");
+        assert!(first_txs.try_next().is_err());
+        assert_eq!(second_txs.try_next().unwrap().unwrap().tx.tx_id, "6,0");
+        assert!(second_txs.try_next().is_err());
+        assert!(other_txs.try_next().is_err());
+
+        drop(second_txs);
+        let mut accounts = notifier.active_accounts();
+        accounts.sort();
+        assert_eq!(
+            accounts,
+            vec![(address, (6, 0)), (other_address, (4, i32::MAX as u64))]
+        );
+        assert_eq!(
+            notifier.accounts.lock().unwrap()[&address]
+                .subscribers
+                .len(),
+            1
+        );
+
+        // Accounts without the subscribers are not queried anymore.
+        drop(other_txs);
+        assert_eq!(notifier.active_accounts(), vec![(address, (6, 0))]);
+    }
+
+    /// Checks that the connections over the limit are rejected until a slot is freed.
+    #[test]
+    fn connections_limit() {
+        let notifier = WsNotifier::new(1);
+        let (_, status) = watch::channel(NetworkStatus::default());
+        let subscriber = notifier.connect(status.clone()).unwrap();
+        assert!(notifier.connect(status.clone()).is_none());
+        drop(subscriber);
+        assert!(notifier.connect(status).is_some());
+    }
+}
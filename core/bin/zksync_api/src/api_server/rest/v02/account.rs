@@ -7,6 +7,7 @@ use std::time::Instant;
 
 // External uses
 use actix_web::{web, Scope};
+use num::BigUint;
 
 // Workspace uses
 use zksync_api_types::v02::{
@@ -296,6 +297,7 @@ impl ApiAccountData {
         address: Address,
         token_like: Option<TokenLike>,
         second_address: Option<Address>,
+        (min_amount, max_amount): (Option<BigUint>, Option<BigUint>),
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let token = if let Some(token_like) = token_like {
@@ -316,6 +318,8 @@ impl ApiAccountData {
                 address,
                 token,
                 second_address,
+                min_amount,
+                max_amount,
             },
             limit: query.limit,
             direction: query.direction,
@@ -395,6 +399,33 @@ async fn account_full_info(
     res
 }
 
+/// Parses the bounds of the transferred amount. Amounts of different tokens
+/// are not comparable, so the bounds are only allowed along with the token filter.
+fn parse_amount_range(
+    min_amount: Option<&str>,
+    max_amount: Option<&str>,
+    has_token: bool,
+) -> Result<(Option<BigUint>, Option<BigUint>), Error> {
+    let parse = |amount: Option<&str>| {
+        amount
+            .map(BigUint::from_str)
+            .transpose()
+            .map_err(|_| Error::from(InvalidDataError::InvalidAmountFilter))
+    };
+    let min_amount = parse(min_amount)?;
+    let max_amount = parse(max_amount)?;
+
+    let has_bounds = min_amount.is_some() || max_amount.is_some();
+    let is_valid_range = match (&min_amount, &max_amount) {
+        (Some(min_amount), Some(max_amount)) => min_amount <= max_amount,
+        _ => true,
+    };
+    if (has_bounds && !has_token) || !is_valid_range {
+        return Err(Error::from(InvalidDataError::InvalidAmountFilter));
+    }
+    Ok((min_amount, max_amount))
+}
+
 async fn account_txs(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
@@ -420,10 +451,21 @@ async fn account_txs(
         None
     };
 
+    let amount_range = api_try!(parse_amount_range(
+        query.min_amount.as_deref(),
+        query.max_amount.as_deref(),
+        query.token.is_some(),
+    ));
     let token_like = query.token.map(|token| TokenLike::parse(&token));
 
     let res = data
-        .account_txs(pagination, address, token_like, second_address)
+        .account_txs(
+            pagination,
+            address,
+            token_like,
+            second_address,
+            amount_range,
+        )
         .await
         .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
@@ -557,7 +599,7 @@ mod tests {
             let transactions = storage
                 .chain()
                 .block_schema()
-                .get_block_transactions(block)
+                .get_block_transactions(block, None)
                 .await?;
 
             let tx = &transactions[1];
@@ -714,4 +756,22 @@ mod tests {
         server.stop().await;
         Ok(())
     }
+
+    #[test]
+    fn amount_range_parsing() {
+        assert_eq!(parse_amount_range(None, None, false).unwrap(), (None, None));
+        assert_eq!(
+            parse_amount_range(Some("10"), Some("200"), true).unwrap(),
+            (Some(BigUint::from(10u32)), Some(BigUint::from(200u32)))
+        );
+        assert_eq!(
+            parse_amount_range(None, Some("200"), true).unwrap(),
+            (None, Some(BigUint::from(200u32)))
+        );
+        // Amounts of different tokens can't be compared.
+        assert!(parse_amount_range(Some("10"), None, false).is_err());
+        assert!(parse_amount_range(Some("200"), Some("10"), true).is_err());
+        assert!(parse_amount_range(Some("-1"), None, true).is_err());
+        assert!(parse_amount_range(Some("1.5"), None, true).is_err());
+    }
 }
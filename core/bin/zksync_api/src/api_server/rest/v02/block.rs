@@ -283,7 +283,7 @@ mod tests {
             storage
                 .chain()
                 .block_schema()
-                .get_block_transactions(block_number)
+                .get_block_transactions(block_number, None)
                 .await?
         };
         assert!(expected_txs.len() >= 3);
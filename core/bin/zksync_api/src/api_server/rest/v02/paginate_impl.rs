@@ -231,6 +231,8 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
         let query = PaginationQuery {
             from: AccountTxsRequest {
                 tx_hash: ApiEither::from(tx_hash),
+                min_amount: query.from.min_amount.clone(),
+                max_amount: query.from.max_amount.clone(),
                 ..query.from
             },
             limit: query.limit,
@@ -251,6 +253,8 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
                 query.from.address,
                 query.from.token,
                 query.from.second_address,
+                query.from.min_amount.as_ref(),
+                query.from.max_amount.as_ref(),
             )
             .await
             .map_err(Error::storage)?;
@@ -13,6 +13,7 @@ use zksync_api_types::v02::{Request, Response, ResultStatus};
 
 // Local uses
 use super::{error::Error, SharedData};
+use crate::api_server::rest::json_response::JSON_CONTENT_TYPE;
 
 // This struct is needed to wrap all api responses is `Response` struct by implementing `Responder` trait for it.
 // We can't use simple `Result`, because `actix-web` has already `Responder` implementation for it.
@@ -63,7 +64,7 @@ impl<R: Serialize> Responder for ApiResult<R> {
         let body = serde_json::to_string(&response).expect("Should be correct serializable");
 
         HttpResponse::Ok()
-            .content_type("application/json")
+            .content_type(JSON_CONTENT_TYPE)
             .body(body)
     }
 }
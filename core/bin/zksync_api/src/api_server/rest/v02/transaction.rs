@@ -443,7 +443,7 @@ mod tests {
             let transactions = storage
                 .chain()
                 .block_schema()
-                .get_block_transactions(BlockNumber(1))
+                .get_block_transactions(BlockNumber(1), None)
                 .await?;
 
             TxHash::from_str(&transactions[0].tx_hash).unwrap()
@@ -11,6 +11,7 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    TokenSuspended = 106,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
@@ -87,6 +88,11 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::TokenSuspended(_) => Self {
+                code: RpcErrorCodes::TokenSuspended.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::MempoolCommunication(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
@@ -96,6 +96,8 @@ pub enum SubmitError {
     TxAdd(#[from] TxAddError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Token {0} is suspended.")]
+    TokenSuspended(TokenId),
     // Not all TxAddErrors would apply to Toggle2FA, but
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
@@ -503,6 +505,9 @@ impl TxSender {
             self.check_forced_exit(forced_exit).await?;
         }
 
+        self.check_tokens_not_suspended(std::iter::once(&tx))
+            .await?;
+
         // Resolve the token.
         let token = self.token_info_from_id(tx.token_id()).await?;
         let msg_to_sign = tx
@@ -667,6 +672,9 @@ impl TxSender {
             return Err(SubmitError::AccountCloseDisabled);
         }
 
+        self.check_tokens_not_suspended(txs.iter().map(|tx| &tx.tx))
+            .await?;
+
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
         let mut transaction_types = vec![];
@@ -1012,6 +1020,33 @@ impl TxSender {
             // TODO Make error more clean
             .ok_or_else(|| SubmitError::other("Token not found in the DB"))
     }
+
+    /// Rejects the transactions using any of the tokens suspended by governance.
+    async fn check_tokens_not_suspended<'a>(
+        &self,
+        txs: impl IntoIterator<Item = &'a ZkSyncTx>,
+    ) -> Result<(), SubmitError> {
+        let mut token_ids: Vec<TokenId> = txs.into_iter().flat_map(ZkSyncTx::tokens).collect();
+        token_ids.sort();
+        token_ids.dedup();
+
+        let suspended_tokens = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .tokens_schema()
+            .get_suspended_tokens(&token_ids)
+            .await
+            .map_err(SubmitError::internal)?;
+        match token_ids
+            .into_iter()
+            .find(|token_id| suspended_tokens.contains(token_id))
+        {
+            Some(token_id) => Err(SubmitError::TokenSuspended(token_id)),
+            None => Ok(()),
+        }
+    }
 }
 
 async fn send_verify_request_and_recv(
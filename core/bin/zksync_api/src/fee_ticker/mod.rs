@@ -6,7 +6,7 @@
 // Built-in deps
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::iter::FromIterator;
 use std::time::Duration;
 
@@ -54,6 +54,13 @@ pub mod validator;
 #[cfg(test)]
 pub(crate) mod tests;
 
+/// Estimates the one-time fee (in wei) charged for the account creation, which is
+/// the difference between the cost of transfers to the new and to the existing account.
+pub fn account_creation_fee_wei(gas_price_wei: BigUint) -> BigUint {
+    let creation_gas_cost = constants::BASE_TRANSFER_TO_NEW_COST - constants::BASE_TRANSFER_COST;
+    FeeTicker::risk_gas_price_estimate(gas_price_wei) * BigUint::from(creation_gas_cost)
+}
+
 /// Contains cost of zkSync operations in Wei.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GasOperationsCost {
@@ -196,6 +203,14 @@ pub struct FeeTicker {
     validator: FeeTokenValidator,
 }
 
+impl fmt::Debug for FeeTicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeeTicker")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
     ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::CREATE2),
 );
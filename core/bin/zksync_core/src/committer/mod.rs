@@ -273,6 +273,21 @@ async fn finish_block(request: BlockFinishRequest, pool: &ConnectionPool) {
         .await
         .expect("committer must commit the op into db");
 
+    // Keep the current token prices, so that the history is able to report
+    // the value of the block transactions at the time they were made.
+    transaction
+        .tokens_schema()
+        .store_block_token_prices(block_number)
+        .await
+        .expect("committer must store the token prices into db");
+
+    transaction
+        .chain()
+        .block_schema()
+        .store_block_fees(block_number)
+        .await
+        .expect("committer must store the block fees into db");
+
     transaction
         .commit()
         .await
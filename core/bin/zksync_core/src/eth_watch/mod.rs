@@ -7,6 +7,10 @@
 
 // Built-in deps
 use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use std::time::{Duration, Instant};
 
 // External uses
@@ -81,6 +85,9 @@ pub struct EthWatch<W: EthClient> {
     client: W,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     eth_state: ETHState,
+    /// Number of the latest processed Ethereum block, shared with the components reporting the server status.
+    /// Zero until the state is restored from Ethereum.
+    monitored_eth_block: Arc<AtomicU64>,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
     mode: WatcherMode,
@@ -91,11 +98,13 @@ impl<W: EthClient> EthWatch<W> {
         client: W,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
         number_of_confirmations_for_event: u64,
+        monitored_eth_block: Arc<AtomicU64>,
     ) -> Self {
         Self {
             client,
             mempool_tx_sender,
             eth_state: ETHState::default(),
+            monitored_eth_block,
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
         }
@@ -103,6 +112,8 @@ impl<W: EthClient> EthWatch<W> {
 
     /// Atomically replaces the stored Ethereum state.
     fn set_new_state(&mut self, new_state: ETHState) {
+        self.monitored_eth_block
+            .store(new_state.last_ethereum_block(), Ordering::Relaxed);
         self.eth_state = new_state;
     }
 
@@ -456,6 +467,7 @@ pub async fn start_eth_watch(
     contract_config: &ContractsConfig,
     eth_watcher_config: &ETHWatchConfig,
     mempool_req_sender: mpsc::Sender<MempoolTransactionRequest>,
+    monitored_eth_block: Arc<AtomicU64>,
 ) -> JoinHandle<()> {
     let eth_client = EthHttpClient::new(
         eth_gateway,
@@ -467,6 +479,7 @@ pub async fn start_eth_watch(
         eth_client,
         mempool_req_sender,
         eth_watcher_config.confirmations_for_eth_event,
+        monitored_eth_block,
     );
 
     eth_watch.restore_from_eth_using_latest_block_number().await;
@@ -1,6 +1,6 @@
 use std::cmp::max;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
 
 use web3::types::{Address, BlockNumber};
 
@@ -132,7 +132,7 @@ fn create_watcher<T: EthClient>(
     client: T,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 ) -> EthWatch<T> {
-    EthWatch::new(client, mempool_tx_sender, 1)
+    EthWatch::new(client, mempool_tx_sender, 1, Default::default())
 }
 
 async fn fake_mempool(
@@ -213,6 +213,7 @@ async fn test_operation_queues() {
     let mut watcher = create_watcher(client, sender);
     watcher.poll_eth_node().await.unwrap();
     assert_eq!(watcher.eth_state.last_ethereum_block(), 4);
+    assert_eq!(watcher.monitored_eth_block.load(Ordering::Relaxed), 4);
 
     let priority_queues = watcher.eth_state.priority_queue();
     let unconfirmed_queue = watcher.eth_state.unconfirmed_queue();
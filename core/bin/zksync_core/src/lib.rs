@@ -7,6 +7,7 @@ use crate::{
     token_handler::run_token_handler,
 };
 use futures::{channel::mpsc, future};
+use std::sync::{atomic::AtomicU64, Arc};
 use tokio::task::JoinHandle;
 use zksync_config::{ChainConfig, ZkSyncConfig};
 use zksync_eth_client::EthereumGateway;
@@ -104,6 +105,9 @@ pub async fn run_core(
     let (processed_tx_events_sender, processed_tx_events_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
 
+    // Latest Ethereum block processed by the Ethereum watcher, reported by the private API.
+    let monitored_eth_block = Arc::new(AtomicU64::new(0));
+
     let mempool_tx_handler_task = run_mempool_tx_handler(
         connection_pool.clone(),
         mempool_tx_request_receiver,
@@ -115,6 +119,7 @@ pub async fn run_core(
         connection_pool.clone(),
         read_only_connection_pool,
         eth_gateway.clone(),
+        monitored_eth_block.clone(),
         config.api.private.clone(),
     );
 
@@ -126,6 +131,7 @@ pub async fn run_core(
         &config.contracts,
         &config.eth_watch,
         mempool_tx_request_sender.clone(),
+        monitored_eth_block,
     )
     .await;
 
@@ -7,6 +7,10 @@
 //! All the incoming data is assumed to be correct and not double-checked
 //! for correctness.
 
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -28,16 +32,31 @@ struct AppState {
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
+    monitored_eth_block: Arc<AtomicU64>,
     status_cache: RwLock<Option<(CoreStatus, Instant)>>,
 }
 
+impl AppState {
+    fn monitored_eth_block(&self) -> Option<u64> {
+        match self.monitored_eth_block.load(Ordering::Relaxed) {
+            0 => None,
+            block => Some(block),
+        }
+    }
+}
+
 /// Health check.
 /// The core actor is expected have connection to web3 and both main/replica databases
 #[actix_web::get("/status")]
 async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
-    if let Some((status, data)) = data.status_cache.read().await.as_ref() {
-        if data.elapsed() < STATUS_INVALIDATION_PERIOD {
-            return Ok(HttpResponse::Ok().json(status.clone()));
+    // The monitored Ethereum block is cheap to obtain, so it's never taken from the cache.
+    if let Some((status, updated_at)) = data.status_cache.read().await.as_ref() {
+        if updated_at.elapsed() < STATUS_INVALIDATION_PERIOD {
+            let status = CoreStatus {
+                monitored_eth_block: data.monitored_eth_block(),
+                ..status.clone()
+            };
+            return Ok(HttpResponse::Ok().json(status));
         }
     }
 
@@ -57,6 +76,7 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
         main_database_available: main_database_status,
         replica_database_available: replica_database_status,
         web3_available: eth_status,
+        monitored_eth_block: data.monitored_eth_block(),
     };
     *status = Some((response.clone(), Instant::now()));
 
@@ -67,6 +87,7 @@ pub fn start_private_core_api(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
+    monitored_eth_block: Arc<AtomicU64>,
     config: PrivateApiConfig,
 ) -> JoinHandle<()> {
     let (panic_sender, mut panic_receiver) = mpsc::channel(1);
@@ -84,6 +105,7 @@ pub fn start_private_core_api(
                         connection_pool: connection_pool.clone(),
                         read_only_connection_pool: read_only_connection_pool.clone(),
                         eth_client: eth_client.clone(),
+                        monitored_eth_block: monitored_eth_block.clone(),
                         status_cache: Default::default(),
                     };
 
@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use std::str::FromStr;
 // External uses
 use num::BigUint;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
@@ -81,6 +81,8 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
+        from: Option<Address>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
@@ -256,6 +258,8 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
+        from: Option<Address>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -312,6 +316,18 @@ impl DatabaseInterface for Database {
             _ => {}
         }
 
+        if let Some(gas_used) = gas_used {
+            transaction
+                .ethereum_schema()
+                .save_eth_tx_gas_used(hash, gas_used.low_u64())
+                .await?;
+        }
+        if let Some(from) = from {
+            transaction
+                .ethereum_schema()
+                .save_eth_tx_sender(hash, from)
+                .await?;
+        }
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
         transaction.commit().await?;
 
@@ -4,7 +4,7 @@ use self::mock::{
     restored_eth_sender,
 };
 use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
-use web3::types::U64;
+use web3::types::{Address, U256, U64};
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
 
 const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
@@ -78,6 +78,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: Some(U256::from(150_000)),
+        from: Some(Address::repeat_byte(0x11)),
     };
     eth_sender
         .ethereum
@@ -91,6 +93,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: Some(U256::from(150_000)),
+        from: Some(Address::repeat_byte(0x11)),
     };
     eth_sender
         .ethereum
@@ -104,6 +108,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        from: Some(Address::repeat_byte(0x11)),
     };
     eth_sender
         .ethereum
@@ -117,6 +123,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        from: Some(Address::repeat_byte(0x11)),
     };
     eth_sender
         .ethereum
@@ -139,7 +147,10 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed {
+            gas_used: committed_response.gas_used,
+            from: committed_response.from,
+        }
     );
 
     // Pending operation (no enough confirmations).
@@ -34,4 +34,8 @@ pub struct CoreStatus {
     pub main_database_available: bool,
     pub replica_database_available: bool,
     pub web3_available: bool,
+    /// Number of the latest Ethereum block processed by the Ethereum watcher,
+    /// `None` if the watcher hasn't processed any block yet.
+    #[serde(default)]
+    pub monitored_eth_block: Option<u64>,
 }
@@ -114,4 +114,6 @@ pub struct IncomingAccountTxsQuery {
     pub direction: PaginationDirection,
     pub token: Option<String>,
     pub second_account: Option<String>,
+    pub min_amount: Option<String>,
+    pub max_amount: Option<String>,
 }
@@ -1,4 +1,5 @@
 use either::Either;
+use num::BigUint;
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
 use thiserror::Error;
@@ -138,4 +139,10 @@ pub struct AccountTxsRequest {
     pub tx_hash: ApiEither<TxHash>,
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
+    /// Lower bound of the transferred amount, inclusive.
+    #[serde(skip)]
+    pub min_amount: Option<BigUint>,
+    /// Upper bound of the transferred amount, inclusive.
+    #[serde(skip)]
+    pub max_amount: Option<BigUint>,
 }
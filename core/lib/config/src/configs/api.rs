@@ -128,6 +128,9 @@ pub struct CommonApiConfig {
 pub struct TokenConfig {
     /// The interval of updating tokens from database
     pub invalidate_token_cache_period_sec: u64,
+    /// Risk levels of the tokens in the `<token symbol>=<risk level>` format, e.g. `ETH=low`.
+    /// Allowed levels are `low`, `medium` and `high`, tokens not listed here have the `unknown` level.
+    pub risk_levels: Vec<String>,
 }
 
 impl TokenConfig {
@@ -192,12 +195,92 @@ pub struct RestApiConfig {
     pub port: u16,
     /// URL to access API server.
     pub url: String,
+    /// Patterns of the routes considered deprecated, e.g. `/api/v0.1/transactions/{tx_hash}`.
+    /// Responses of these routes carry the `Deprecation` and `Warning` headers.
+    pub deprecated_routes: Vec<String>,
+    /// Date after which the deprecated routes are going to be removed (in HTTP-date format).
+    /// Sent in the `Sunset` header of the deprecated routes responses if set.
+    pub deprecated_routes_sunset: Option<String>,
+    /// Cache policies for the routes in the `<route pattern>=<max age in seconds>` format,
+    /// e.g. `/api/v0.1/tokens=300`. Zero max age forbids caching of the route responses.
+    pub cache_control: Vec<String>,
+    /// Maximum amount of requests per second from a single IP address.
+    /// Requests are not limited if not set.
+    pub public_rate_limit_per_sec: Option<u32>,
+    /// Keys granting the higher rate limit when passed in the `X-API-Key` header.
+    pub api_keys: Vec<String>,
+    /// Maximum amount of requests per second with a single API key.
+    /// Requests with valid API keys are not limited if not set.
+    pub api_key_rate_limit_per_sec: Option<u32>,
+    /// Maximum amount of requests a client can make in a row before being throttled to its
+    /// per-second rate limit. Equals to the per-second limit of the client if not set.
+    pub rate_limit_burst: Option<u32>,
+    /// IP addresses of the reverse proxies allowed to pass the client address in the
+    /// `X-Forwarded-For` header. Clients are identified by the peer address if empty.
+    pub trusted_proxies: Vec<String>,
+    /// Maximum time a request handler waits for a database connection before responding
+    /// with `503 Service Unavailable`. Handlers wait for the pool with retries if not set.
+    pub connection_acquisition_timeout_ms: Option<u64>,
+    /// Transfers worth less than this amount of dollars (scaled by `SUBSIDY_USD_AMOUNTS_SCALE`)
+    /// are hidden from the account history on request. $0.01 is used if not set.
+    pub dust_threshold_usd_scaled: Option<u64>,
+    /// Amount of the items returned by the paginated endpoints if the `limit` is not specified.
+    /// 20 is used if not set.
+    pub default_page_limit: Option<u32>,
+    /// Maximum `limit` accepted by the paginated endpoints, 100 is used if not set.
+    pub max_page_limit: Option<u32>,
+    /// Interval of the token list cache refreshes, i.e. the maximum time it takes for
+    /// a new token to appear in the `/tokens` response. 60 seconds are used if not set.
+    pub token_list_refresh_interval_sec: Option<u64>,
+    /// Maximum amount of the clients connected to the `/api/v0.1/ws` endpoint at once,
+    /// the rest are rejected with `503 Service Unavailable`. 1000 is used if not set.
+    pub max_ws_connections: Option<u32>,
 }
 
 impl RestApiConfig {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn dust_threshold_usd(&self) -> Ratio<BigUint> {
+        const DEFAULT_DUST_THRESHOLD_USD_SCALED: u64 = 10_000;
+        scaled_u64_to_ratio(
+            self.dust_threshold_usd_scaled
+                .unwrap_or(DEFAULT_DUST_THRESHOLD_USD_SCALED),
+        )
+    }
+
+    pub fn connection_acquisition_timeout(&self) -> Option<Duration> {
+        self.connection_acquisition_timeout_ms
+            .map(Duration::from_millis)
+    }
+
+    pub fn max_page_limit(&self) -> u32 {
+        const DEFAULT_MAX_PAGE_LIMIT: u32 = 100;
+        self.max_page_limit.unwrap_or(DEFAULT_MAX_PAGE_LIMIT)
+    }
+
+    pub fn token_list_refresh_interval(&self) -> Duration {
+        const DEFAULT_TOKEN_LIST_REFRESH_INTERVAL_SEC: u64 = 60;
+        Duration::from_secs(
+            self.token_list_refresh_interval_sec
+                .unwrap_or(DEFAULT_TOKEN_LIST_REFRESH_INTERVAL_SEC),
+        )
+    }
+
+    pub fn max_ws_connections(&self) -> u32 {
+        const DEFAULT_MAX_WS_CONNECTIONS: u32 = 1000;
+        self.max_ws_connections
+            .unwrap_or(DEFAULT_MAX_WS_CONNECTIONS)
+    }
+
+    /// Default page size, never exceeds the maximum one.
+    pub fn default_page_limit(&self) -> u32 {
+        const DEFAULT_PAGE_LIMIT: u32 = 20;
+        self.default_page_limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .min(self.max_page_limit())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -272,6 +355,20 @@ mod tests {
             rest: RestApiConfig {
                 port: 3001,
                 url: "http://127.0.0.1:3001".into(),
+                deprecated_routes: vec!["/api/v0.1/transactions/{tx_hash}".into()],
+                deprecated_routes_sunset: Some("Mon, 01 Mar 2027 00:00:00 GMT".into()),
+                cache_control: vec!["/api/v0.1/status=0".into(), "/api/v0.1/tokens=300".into()],
+                public_rate_limit_per_sec: Some(10),
+                api_keys: vec!["sample".into()],
+                api_key_rate_limit_per_sec: None,
+                rate_limit_burst: Some(60),
+                trusted_proxies: vec!["127.0.0.1".into()],
+                connection_acquisition_timeout_ms: Some(500),
+                dust_threshold_usd_scaled: Some(10000),
+                default_page_limit: None,
+                max_page_limit: Some(200),
+                token_list_refresh_interval_sec: Some(60),
+                max_ws_connections: Some(1000),
             },
             json_rpc: JsonRpcConfig {
                 http_port: 3030,
@@ -297,6 +394,7 @@ mod tests {
             prometheus: PrometheusConfig { port: 3312 },
             token_config: TokenConfig {
                 invalidate_token_cache_period_sec: 10,
+                risk_levels: vec!["ETH=low".into(), "DAI=medium".into()],
             },
         }
     }
@@ -314,11 +412,24 @@ API_COMMON_SUBSIDY_NAME=PartnerName
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
+API_TOKEN_RISK_LEVELS="ETH=low,DAI=medium"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
 API_REST_PORT="3001"
 API_REST_URL="http://127.0.0.1:3001"
+API_REST_DEPRECATED_ROUTES="/api/v0.1/transactions/{tx_hash}"
+API_REST_DEPRECATED_ROUTES_SUNSET="Mon, 01 Mar 2027 00:00:00 GMT"
+API_REST_CACHE_CONTROL="/api/v0.1/status=0,/api/v0.1/tokens=300"
+API_REST_PUBLIC_RATE_LIMIT_PER_SEC="10"
+API_REST_API_KEYS="sample"
+API_REST_RATE_LIMIT_BURST="60"
+API_REST_TRUSTED_PROXIES="127.0.0.1"
+API_REST_CONNECTION_ACQUISITION_TIMEOUT_MS="500"
+API_REST_DUST_THRESHOLD_USD_SCALED="10000"
+API_REST_MAX_PAGE_LIMIT="200"
+API_REST_TOKEN_LIST_REFRESH_INTERVAL_SEC="60"
+API_REST_MAX_WS_CONNECTIONS="1000"
 API_JSON_RPC_HTTP_PORT="3030"
 API_JSON_RPC_HTTP_URL="http://127.0.0.1:3030"
 API_JSON_RPC_WS_PORT="3031"
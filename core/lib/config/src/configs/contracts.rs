@@ -19,6 +19,8 @@ pub struct ContractsConfig {
     pub deploy_factory_addr: Address,
     pub forced_exit_addr: Address,
     pub genesis_tx_hash: H256,
+    /// Root hash of the accounts tree in the genesis state.
+    pub genesis_root: H256,
     pub init_contract_version: u32,
     pub upgrade_eth_blocks: Vec<u64>,
 }
@@ -49,6 +51,7 @@ mod tests {
             genesis_tx_hash: hash(
                 "b99ebfea46cbe05a21cd80fe5597d97b204befc52a16303f579c607dc1ac2e2e",
             ),
+            genesis_root: hash("2d5ab622df708ab44944bb02377be85b6f27812e9ae520734873b7a193898ba4"),
             init_contract_version: 4,
             upgrade_eth_blocks: vec![1, 4294967296, 1152921504606846976],
         }
@@ -68,6 +71,7 @@ CONTRACTS_VERIFIER_ADDR="0xDAbb67b676F5b01FcC8997Cc8439846D0d8078ca"
 CONTRACTS_DEPLOY_FACTORY_ADDR="0xFC073319977e314F251EAE6ae6bE76B0B3BAeeCF"
 CONTRACTS_FORCED_EXIT_ADDR="0x9c7AeE886D6FcFc14e37784f143a6dAccEf50Db7"
 CONTRACTS_GENESIS_TX_HASH="0xb99ebfea46cbe05a21cd80fe5597d97b204befc52a16303f579c607dc1ac2e2e"
+CONTRACTS_GENESIS_ROOT="0x2d5ab622df708ab44944bb02377be85b6f27812e9ae520734873b7a193898ba4"
 CONTRACTS_INIT_CONTRACT_VERSION=4
 CONTRACTS_UPGRADE_ETH_BLOCKS="1,4294967296,1152921504606846976"
         "#;
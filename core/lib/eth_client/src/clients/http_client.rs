@@ -442,6 +442,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 };
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
+                let from = receipt.as_ref().map(|receipt| receipt.from);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -454,6 +456,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
+                    from,
                 }))
             }
             _ => Ok(None),
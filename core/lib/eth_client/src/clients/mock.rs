@@ -83,6 +83,8 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
+            from: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,6 +97,8 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
+            from: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
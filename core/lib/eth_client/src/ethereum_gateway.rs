@@ -31,6 +31,10 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction, if reported by the node.
+    pub gas_used: Option<U256>,
+    /// Sender of the transaction.
+    pub from: Option<Address>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS ix_executed_transactions_batch_id;
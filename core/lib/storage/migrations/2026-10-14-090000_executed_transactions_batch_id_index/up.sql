@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS ix_executed_transactions_batch_id ON public.executed_transactions USING btree (batch_id);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS token_risk_levels (
+    token_id INTEGER NOT NULL PRIMARY KEY REFERENCES tokens(id) ON UPDATE CASCADE ON DELETE CASCADE,
+    risk_level TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS suspended_tokens (
+    token_id INTEGER NOT NULL PRIMARY KEY REFERENCES tokens(id) ON UPDATE CASCADE ON DELETE CASCADE,
+    reason TEXT,
+    suspended_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+ALTER TABLE eth_operations DROP COLUMN IF EXISTS gas_used;
@@ -0,0 +1,7 @@
+-- Token prices in USD at the time the blocks were committed.
+CREATE TABLE token_prices_historical (
+    block_number BIGINT NOT NULL,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    PRIMARY KEY (block_number, token_id)
+);
@@ -0,0 +1,2 @@
+-- Sender of the final transaction of the operation, set once the transaction is confirmed.
+ALTER TABLE eth_operations ADD COLUMN IF NOT EXISTS from_address BYTEA;
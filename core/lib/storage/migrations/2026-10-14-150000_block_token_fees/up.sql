@@ -0,0 +1,18 @@
+-- Sums of the fees paid by the successful transactions of the block, per fee token.
+CREATE TABLE block_token_fees (
+    block_number BIGINT NOT NULL,
+    token_id INTEGER NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    PRIMARY KEY (block_number, token_id)
+);
+
+INSERT INTO block_token_fees (block_number, token_id, total_fee)
+SELECT
+    block_number,
+    COALESCE((tx->>'feeToken')::integer, (tx->>'token')::integer),
+    SUM((tx->>'fee')::numeric)
+FROM executed_transactions
+WHERE success = true
+    AND tx->>'fee' IS NOT NULL
+    AND block_number IN (SELECT number FROM blocks)
+GROUP BY 1, 2;
@@ -0,0 +1,14 @@
+-- Exit proofs of the account balances requested via the API, generated in background.
+-- Rows without the proof are the pending requests.
+CREATE TABLE exit_proofs (
+    account_id BIGINT NOT NULL,
+    token_id INTEGER NOT NULL,
+    -- Verified block the proof is generated against.
+    block_number BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    proof JSONB,
+    amount NUMERIC,
+    PRIMARY KEY (account_id, token_id)
+);
+
+CREATE INDEX exit_proofs_pending_idx ON exit_proofs (created_at) WHERE proof IS NULL;
@@ -0,0 +1,31 @@
+-- Fees paid in the blocks converted with the token prices at the time the blocks were committed.
+-- Fees are `NULL` if the price of any of the tokens the fees are paid in is not known.
+CREATE TABLE block_fees (
+    block_number BIGINT PRIMARY KEY,
+    total_fee_usd NUMERIC,
+    total_fee_eth_wei NUMERIC
+);
+
+INSERT INTO block_fees (block_number, total_fee_usd, total_fee_eth_wei)
+SELECT
+    blocks.number,
+    fees.total_fee_usd,
+    TRUNC(fees.total_fee_usd * power(10::numeric, 18) / eth_prices.usd_price)
+FROM blocks
+CROSS JOIN LATERAL (
+    SELECT
+        CASE WHEN COUNT(*) = COUNT(token_prices_historical.usd_price) THEN COALESCE(SUM(
+            block_token_fees.total_fee * token_prices_historical.usd_price / power(10::numeric, tokens.decimals)
+        ), 0) END AS total_fee_usd
+    FROM block_token_fees
+    INNER JOIN tokens ON tokens.id = block_token_fees.token_id
+    LEFT JOIN token_prices_historical
+        ON token_prices_historical.block_number = block_token_fees.block_number
+        AND token_prices_historical.token_id = block_token_fees.token_id
+        AND token_prices_historical.usd_price > 0
+    WHERE block_token_fees.block_number = blocks.number
+) fees
+LEFT JOIN token_prices_historical eth_prices
+    ON eth_prices.block_number = blocks.number
+    AND eth_prices.token_id = 0
+    AND eth_prices.usd_price > 0;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS blocks_timestamp_index;
@@ -0,0 +1,2 @@
+-- Lets the block statistics of the recent time windows avoid scanning the whole table.
+CREATE INDEX IF NOT EXISTS blocks_timestamp_index ON blocks (timestamp);
@@ -0,0 +1 @@
+ALTER TABLE block_token_fees DROP COLUMN IF EXISTS tx_count;
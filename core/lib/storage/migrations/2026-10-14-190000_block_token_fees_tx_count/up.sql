@@ -0,0 +1,15 @@
+-- Amount of the successful transactions of the block that paid a non-zero fee in the token.
+ALTER TABLE block_token_fees ADD COLUMN tx_count BIGINT NOT NULL DEFAULT 0;
+
+UPDATE block_token_fees SET tx_count = counts.tx_count
+FROM (
+    SELECT
+        block_number,
+        COALESCE((tx->>'feeToken')::integer, (tx->>'token')::integer) AS token_id,
+        COUNT(*) AS tx_count
+    FROM executed_transactions
+    WHERE success = true AND (tx->>'fee')::numeric > 0
+    GROUP BY 1, 2
+) counts
+WHERE block_token_fees.block_number = counts.block_number
+    AND block_token_fees.token_id = counts.token_id;
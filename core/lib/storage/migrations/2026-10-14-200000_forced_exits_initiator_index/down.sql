@@ -0,0 +1 @@
+DROP INDEX IF EXISTS executed_transactions_forced_exit_initiator_index;
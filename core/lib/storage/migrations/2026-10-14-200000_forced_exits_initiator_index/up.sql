@@ -0,0 +1,5 @@
+-- The initiator of the `ForcedExit` is neither its `from_account` nor one of the affected
+-- accounts in `tx_filters`, so the forced exits are looked up by the initiator ID in the tx.
+CREATE INDEX IF NOT EXISTS executed_transactions_forced_exit_initiator_index
+    ON executed_transactions (((tx->>'initiatorAccountId')::bigint), sequence_number)
+    WHERE tx->>'type' = 'ForcedExit';
@@ -1,4 +1,5 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::time::Instant;
 // External imports
 use num::{BigUint, Zero};
@@ -6,7 +7,8 @@ use sqlx::{types::BigDecimal, Acquire};
 // Workspace imports
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+    Account, AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash,
+    TokenId,
 };
 // Local imports
 use self::records::*;
@@ -154,7 +156,54 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(account_type)
     }
 
+    /// Loads the 2FA setting of the account. Accounts without a stored type are
+    /// considered to be `Owned` ones, so 2FA is required for them.
+    pub async fn get_account_2fa(&mut self, account_id: AccountId) -> QueryResult<Account2FA> {
+        let mut two_fa = self.get_accounts_2fa(&[account_id]).await?;
+        Ok(two_fa.remove(&account_id).unwrap_or(Account2FA::Required))
+    }
+
+    /// Same as `get_account_2fa`, but for several accounts at once.
+    /// Only the accounts with a stored type are included in the result.
+    pub async fn get_accounts_2fa(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<HashMap<AccountId, Account2FA>> {
+        let start = Instant::now();
+        let account_ids: Vec<i64> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        let records = sqlx::query!(
+            r#"
+            SELECT eth_account_types.account_id, eth_account_types.account_type as "account_type!: DbAccountType",
+                no_2fa_pub_key_hash.pub_key_hash as "pub_key_hash?"
+            FROM eth_account_types
+            LEFT JOIN no_2fa_pub_key_hash ON no_2fa_pub_key_hash.account_id = eth_account_types.account_id
+            WHERE eth_account_types.account_id = ANY($1)
+            "#,
+            &account_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let two_fa = records
+            .into_iter()
+            .map(|record| {
+                let pub_key_hash = match record.account_type {
+                    DbAccountType::No2FA => record
+                        .pub_key_hash
+                        .map(|hash| PubKeyHash::from_hex(&hash).unwrap()),
+                    _ => None,
+                };
+                let account_type = EthAccountType::from_db(record.account_type, pub_key_hash);
+                (AccountId(record.account_id as u32), account_type.into())
+            })
+            .collect();
+
+        metrics::histogram!("sql.chain.account.get_accounts_2fa", start.elapsed());
+        Ok(two_fa)
+    }
+
     /// Obtains both committed and verified state for the account by its ID.
+    /// Account ID is also the index of the account leaf in the Merkle tree.
     pub async fn account_state_by_id(
         &mut self,
         account_id: AccountId,
@@ -193,6 +242,176 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(result.is_some())
     }
 
+    /// Loads the states of the accounts with the given IDs.
+    /// Accounts that don't exist in the network are not included in the result.
+    ///
+    /// Same as `account_state_by_id`, but the states of all the accounts are loaded
+    /// with a fixed number of queries.
+    pub async fn account_states_by_ids(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<HashMap<AccountId, StoredAccountState>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let account_ids: Vec<i64> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        let creator_ids: Vec<i32> = account_ids.iter().map(|id| *id as i32).collect();
+
+        // `accounts` table is updated only after the block verification,
+        // so it contains the verified states.
+        let accounts = sqlx::query_as!(
+            StorageAccount,
+            "SELECT * FROM accounts WHERE id = ANY($1)",
+            &account_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mut balances: HashMap<i64, Vec<StorageBalance>> = HashMap::new();
+        let stored_balances = sqlx::query_as!(
+            StorageBalance,
+            "SELECT * FROM balances WHERE account_id = ANY($1)",
+            &account_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        for balance in stored_balances {
+            balances
+                .entry(balance.account_id)
+                .or_default()
+                .push(balance);
+        }
+        let nfts = sqlx::query_as!(
+            StorageNFT,
+            "
+                SELECT nft.*, tokens.symbol FROM nft
+                INNER JOIN tokens
+                ON tokens.id = nft.token_id
+                WHERE creator_account_id = ANY($1)
+            ",
+            &creator_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut verified_accounts: HashMap<AccountId, Account> = accounts
+            .iter()
+            .map(|account| {
+                let balances = balances.remove(&account.id).unwrap_or_default();
+                restore_account(account, balances)
+            })
+            .collect();
+        for nft in nfts {
+            let creator_id = AccountId(nft.creator_account_id as u32);
+            if let Some(account) = verified_accounts.get_mut(&creator_id) {
+                account
+                    .minted_nfts
+                    .insert(TokenId(nft.token_id as u32), nft.into());
+            }
+        }
+
+        let last_verified_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?
+            .0 as i64;
+
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &account_ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &account_ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &account_ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mint_nft_updates = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "
+                SELECT * FROM mint_nft_updates
+                WHERE creator_account_id = ANY($1) AND block_number > $2
+            ",
+            &creator_ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        // Diffs are applied the same way as in `last_committed_state_for_account`,
+        // the order of the diffs of each account is kept after the sorting.
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        let mut committed_accounts: HashMap<AccountId, Option<Account>> = verified_accounts
+            .iter()
+            .map(|(id, account)| (*id, Some(account.clone())))
+            .collect();
+        for (account_id, update) in account_diff
+            .into_iter()
+            .map(Into::into)
+            .collect::<AccountUpdates>()
+        {
+            let account = committed_accounts.entry(account_id).or_default();
+            *account = Account::apply_update(account.take(), update);
+        }
+
+        let account_states = committed_accounts
+            .into_iter()
+            .filter_map(|(account_id, committed)| {
+                let committed = committed?;
+                let verified = verified_accounts.remove(&account_id);
+                Some((
+                    account_id,
+                    StoredAccountState {
+                        committed: Some((account_id, committed)),
+                        verified: verified.map(|account| (account_id, account)),
+                    },
+                ))
+            })
+            .collect();
+
+        metrics::histogram!("sql.chain.account.account_states_by_ids", start.elapsed());
+        Ok(account_states)
+    }
+
     /// Obtains both committed and verified state for the account by its address.
     pub async fn account_state_by_address(
         &mut self,
@@ -327,6 +546,98 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(((last_block, account), account_state))
     }
 
+    /// Loads the state of the account as of the given block by applying the account diffs
+    /// up to that block, along with the number of the block the account was last updated in.
+    /// Returns `None` if the account didn't exist in that block.
+    pub async fn account_state_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<(BlockNumber, Account)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mint_nft_updates = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "
+                SELECT * FROM mint_nft_updates
+                WHERE creator_account_id = $1 AND block_number <= $2
+            ",
+            *account_id as i32,
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        let last_update_block = account_diff
+            .last()
+            .map(|diff| BlockNumber(diff.block_number() as u32));
+        // The account is built from scratch, starting from its creation.
+        let account = account_diff
+            .into_iter()
+            .map(|diff| <(AccountId, AccountUpdate)>::from(diff).1)
+            .fold(None, Account::apply_update);
+
+        metrics::histogram!("sql.chain.account.account_state_at_block", start.elapsed());
+        Ok(last_update_block.zip(account))
+    }
+
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
     /// which was both committed and verified).
     pub async fn last_verified_state_for_account(
@@ -430,6 +741,127 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(account_id)
     }
 
+    /// Same as `account_id_by_address`, but for several addresses at once.
+    /// Addresses without an account are not included in the result.
+    pub async fn account_ids_by_addresses(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashMap<Address, AccountId>> {
+        let start = Instant::now();
+        let addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let account_ids = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (address) address, account_id FROM account_creates
+                WHERE address = ANY($1) AND is_create = $2
+                ORDER BY address, block_number DESC
+            "#,
+            &addresses,
+            true
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            let address = Address::from_slice(&record.address);
+            (address, AccountId(record.account_id as u32))
+        })
+        .collect();
+
+        metrics::histogram!(
+            "sql.chain.account.account_ids_by_addresses",
+            start.elapsed()
+        );
+        Ok(account_ids)
+    }
+
+    /// Loads the times and the block numbers of the last successful operations affecting
+    /// the account state: the committed one and the verified one.
+    pub async fn get_account_last_updates(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<AccountLastUpdates> {
+        let mut updates = self.get_accounts_last_updates(&[address]).await?;
+        Ok(updates.remove(&address).unwrap_or_default())
+    }
+
+    /// Same as `get_account_last_updates`, but for several accounts at once.
+    /// Accounts without the successful operations are not included in the result.
+    pub async fn get_accounts_last_updates(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashMap<Address, AccountLastUpdates>> {
+        let start = Instant::now();
+        let addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        // Each of the account columns is matched separately, so that the indices are used.
+        let records = sqlx::query!(
+            r#"
+            WITH account_ops AS (
+                SELECT from_account AS address, block_number, created_at FROM executed_transactions
+                WHERE from_account = ANY($1) AND success = true
+                UNION ALL
+                SELECT to_account AS address, block_number, created_at FROM executed_transactions
+                WHERE to_account = ANY($1) AND success = true
+                UNION ALL
+                SELECT primary_account_address AS address, block_number, created_at FROM executed_transactions
+                WHERE primary_account_address = ANY($1) AND success = true
+                UNION ALL
+                SELECT from_account AS address, block_number, created_at FROM executed_priority_operations
+                WHERE from_account = ANY($1)
+                UNION ALL
+                SELECT to_account AS address, block_number, created_at FROM executed_priority_operations
+                WHERE to_account = ANY($1)
+            ), last_verified AS (
+                SELECT MAX(execute_aggregated_blocks_binding.block_number) AS block_number
+                FROM aggregate_operations
+                    INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                WHERE aggregate_operations.confirmed = true
+            )
+            SELECT
+                address AS "address!",
+                MAX(created_at) AS "committed_at?",
+                MAX(created_at) FILTER (
+                    WHERE block_number <= (SELECT block_number FROM last_verified)
+                ) AS "verified_at?",
+                MAX(block_number) AS "committed_block?",
+                MAX(block_number) FILTER (
+                    WHERE block_number <= (SELECT block_number FROM last_verified)
+                ) AS "verified_block?"
+            FROM account_ops
+            GROUP BY address
+            "#,
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.account.get_accounts_last_updates",
+            start.elapsed()
+        );
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let updates = AccountLastUpdates {
+                    committed_at: record.committed_at,
+                    verified_at: record.verified_at,
+                    committed_block: record
+                        .committed_block
+                        .map(|block_number| BlockNumber(block_number as u32)),
+                    verified_block: record
+                        .verified_block
+                        .map(|block_number| BlockNumber(block_number as u32)),
+                };
+                (Address::from_slice(&record.address), updates)
+            })
+            .collect())
+    }
+
     pub async fn account_address_by_id(
         &mut self,
         account_id: AccountId,
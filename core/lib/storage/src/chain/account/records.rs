@@ -1,8 +1,9 @@
 // Workspace imports
 use zksync_api_types::v02::account::EthAccountType as ApiEthAccountType;
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::{types::BigDecimal, FromRow};
-use zksync_types::{AccountId, Address, PubKeyHash, TokenId, H256, NFT};
+use zksync_types::{AccountId, Address, BlockNumber, PubKeyHash, TokenId, H256, NFT};
 
 #[derive(Debug, FromRow)]
 pub(crate) struct StorageAccount {
@@ -13,6 +14,16 @@ pub(crate) struct StorageAccount {
     pub pubkey_hash: Vec<u8>,
 }
 
+/// Balance of a single token of the account in the verified state,
+/// along with the account address.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageAccountBalance {
+    pub account_id: i64,
+    pub address: Vec<u8>,
+    pub coin_id: i32,
+    pub balance: BigDecimal,
+}
+
 #[derive(Debug, FromRow)]
 pub(crate) struct StorageAccountCreation {
     pub account_id: i64,
@@ -106,6 +117,40 @@ pub enum EthAccountType {
     No2FA(Option<PubKeyHash>),
 }
 
+/// Last successful operations affecting the account state: the committed one and the
+/// verified one. The fields are `None` if there is no such an operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AccountLastUpdates {
+    pub committed_at: Option<DateTime<Utc>>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub committed_block: Option<BlockNumber>,
+    pub verified_block: Option<BlockNumber>,
+}
+
+/// 2FA setting of the account, derived from its [`EthAccountType`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Account2FA {
+    /// Transactions of the account must be additionally signed with the Ethereum key.
+    Required,
+    /// Transactions of the account don't need the Ethereum signature.
+    Disabled,
+    /// Only the transactions signed with the given public key hash don't need
+    /// the Ethereum signature, the rest of them still do.
+    DisabledForPubKeyHash(PubKeyHash),
+}
+
+impl From<EthAccountType> for Account2FA {
+    fn from(account_type: EthAccountType) -> Self {
+        match account_type {
+            EthAccountType::Owned => Self::Required,
+            // CREATE2 accounts have no Ethereum signer.
+            EthAccountType::CREATE2 => Self::Disabled,
+            EthAccountType::No2FA(None) => Self::Disabled,
+            EthAccountType::No2FA(Some(pub_key_hash)) => Self::DisabledForPubKeyHash(pub_key_hash),
+        }
+    }
+}
+
 impl From<EthAccountType> for ApiEthAccountType {
     fn from(account_type: EthAccountType) -> ApiEthAccountType {
         match account_type {
@@ -1,6 +1,8 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 // External imports
+use num::BigUint;
 // Workspace imports
 use zksync_api_types::{
     v02::{
@@ -9,17 +11,20 @@ use zksync_api_types::{
     },
     Either,
 };
-use zksync_crypto::convert::FeConvert;
+use zksync_crypto::{convert::FeConvert, proof::EncodedSingleProof};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
     event::block::BlockStatus,
-    AccountId, BlockNumber, Fr, ZkSyncOp, H256, U256,
+    AccountId, BlockNumber, Fr, TokenId, ZkSyncOp, H256, U256,
 };
+use zksync_utils::biguint_to_big_decimal;
 // Local imports
 use self::records::{
-    BlockTransactionItem, StorageBlock, StorageBlockDetails, StorageBlockMetadata,
-    StoragePendingBlock, StorageRootHash, TransactionItem,
+    BlockTransactionItem, StorageBlock, StorageBlockDetails, StorageBlockFees, StorageBlockGasUsed,
+    StorageBlockMetadata, StorageBlockTokenFee, StorageBlockVerifier, StorageExitProof,
+    StorageExpandedBlockDetails, StoragePendingBlock, StorageProofLatencyBucket, StorageRootHash,
+    TransactionItem,
 };
 use crate::{
     chain::operations::{
@@ -36,6 +41,71 @@ use crate::{
 pub(crate) mod conversion;
 pub mod records;
 
+/// Optional constraints on the block characteristics used by
+/// `BlockSchema::load_filtered_block_page`.
+///
+/// All the bounds are inclusive, and absent bounds are not checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockRangeFilter {
+    /// Minimal amount of operations executed in the block.
+    pub min_tx_count: Option<u32>,
+    /// Maximal amount of operations executed in the block.
+    pub max_tx_count: Option<u32>,
+    /// Token the fee bounds are applied to. Fees paid in different tokens
+    /// are not comparable, so the bounds are only checked along with the token.
+    pub fee_token: Option<TokenId>,
+    /// Minimal sum of fees of the transactions executed in the block.
+    pub min_total_fee: Option<BigUint>,
+    /// Maximal sum of fees of the transactions executed in the block.
+    pub max_total_fee: Option<BigUint>,
+}
+
+impl BlockRangeFilter {
+    /// Returns `true` if filter doesn't impose any constraints.
+    pub fn is_empty(&self) -> bool {
+        self.min_tx_count.is_none()
+            && self.max_tx_count.is_none()
+            && self.min_total_fee.is_none()
+            && self.max_total_fee.is_none()
+    }
+}
+
+/// Length of the periods the proof generation latency statistics is grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofLatencyBucketSize {
+    Hour,
+    Day,
+}
+
+impl ProofLatencyBucketSize {
+    /// Name of the period accepted by the `DATE_TRUNC` function.
+    fn as_date_trunc_field(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+}
+
+/// Set of the additional data to be loaded together with the block details
+/// by `BlockSchema::load_expanded_block_details`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockDetailsExpansion {
+    /// Load the block proof.
+    pub proof: bool,
+    /// Load hashes of all the Ethereum transactions related to the block.
+    pub l1_hashes: bool,
+    /// Load the operations count and the sums of fees per fee token of the block.
+    pub statistics: bool,
+}
+
+impl BlockDetailsExpansion {
+    /// Returns `true` if no additional data is requested.
+    pub fn is_empty(&self) -> bool {
+        !(self.proof || self.l1_hashes || self.statistics)
+    }
+}
+
 /// Block schema is a primary sidechain storage controller.
 ///
 /// Besides block getters/setters, it provides an `execute_operation` method,
@@ -226,9 +296,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     }
 
     /// Retrieves both L1 and L2 operations stored in the block with the given number.
+    ///
+    /// If `op_type` is provided, only operations of this type (e.g. `Deposit` or `Transfer`)
+    /// are returned. Type is compared case-insensitively.
     pub async fn get_block_transactions(
         &mut self,
         block: BlockNumber,
+        op_type: Option<&str>,
     ) -> QueryResult<Vec<BlockTransactionItem>> {
         let start = Instant::now();
         let block_txs = sqlx::query_as!(
@@ -275,9 +349,11 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                     created_at as "created_at!",
                     batch_id as "batch_id?"
                 FROM everything
+                WHERE $2::text IS NULL OR LOWER(op->>'type') = $2
                 ORDER BY sequence_number DESC
             "#,
-            i64::from(*block)
+            i64::from(*block),
+            op_type.map(str::to_lowercase)
         )
         .fetch_all(self.0.conn())
         .await?;
@@ -286,6 +362,40 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_txs)
     }
 
+    /// Loads the transactions that were submitted for the block but rejected
+    /// during its execution, in the order they were processed.
+    ///
+    /// Priority operations can't be rejected, so only L2 transactions are returned.
+    pub async fn get_rejected_txs(
+        &mut self,
+        block: BlockNumber,
+    ) -> QueryResult<Vec<BlockTransactionItem>> {
+        let start = Instant::now();
+        let rejected_txs = sqlx::query_as!(
+            BlockTransactionItem,
+            r#"
+                SELECT
+                    '0x' || encode(tx_hash, 'hex') as "tx_hash!",
+                    block_number,
+                    tx as op,
+                    block_index,
+                    success,
+                    fail_reason,
+                    created_at,
+                    batch_id
+                FROM executed_transactions
+                WHERE block_number = $1 AND success = false
+                ORDER BY sequence_number ASC
+            "#,
+            i64::from(*block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.get_rejected_txs", start.elapsed());
+        Ok(rejected_txs)
+    }
+
     /// Given the block number, loads all the operations that were executed in that block.
     pub async fn get_block_executed_ops(
         &mut self,
@@ -413,6 +523,469 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(details)
     }
 
+    /// Loads the block headers for the given amount of blocks in the descending order,
+    /// skipping the blocks that do not satisfy the provided filter.
+    pub async fn load_filtered_block_range_desc(
+        &mut self,
+        max_block: BlockNumber,
+        limit: u32,
+        filter: &BlockRangeFilter,
+    ) -> QueryResult<Vec<StorageBlockDetails>> {
+        let query = PaginationQuery {
+            from: max_block,
+            limit,
+            direction: PaginationDirection::Older,
+        };
+        self.load_filtered_block_page(&query, filter).await
+    }
+
+    /// Loads the block headers for the given amount of blocks in the ascending order,
+    /// skipping the blocks that do not satisfy the provided filter.
+    pub async fn load_filtered_block_range_asc(
+        &mut self,
+        min_block: BlockNumber,
+        limit: u32,
+        filter: &BlockRangeFilter,
+    ) -> QueryResult<Vec<StorageBlockDetails>> {
+        let query = PaginationQuery {
+            from: min_block,
+            limit,
+            direction: PaginationDirection::Newer,
+        };
+        self.load_filtered_block_page(&query, filter).await
+    }
+
+    /// Loads the block headers for the given pagination query,
+    /// skipping the blocks that do not satisfy the provided filter.
+    ///
+    /// Operations count of the block includes both successful transactions and priority
+    /// operations, while total fee is a sum of fees of successful transactions paid in
+    /// the filter fee token.
+    pub async fn load_filtered_block_page(
+        &mut self,
+        query: &PaginationQuery<BlockNumber>,
+        filter: &BlockRangeFilter,
+    ) -> QueryResult<Vec<StorageBlockDetails>> {
+        if filter.is_empty() {
+            return self.load_block_page(query).await;
+        }
+
+        let start = Instant::now();
+        let (comparison, order) = match query.direction {
+            PaginationDirection::Newer => (">=", "ASC"),
+            PaginationDirection::Older => ("<=", "DESC"),
+        };
+        // The base part of the query is the same as in `load_block_range_desc`, but
+        // the filter bounds are additionally applied to the per-block statistics.
+        // Statistics are only computed for the blocks the range scan reaches,
+        // fee sums are precomputed per token in the `block_token_fees` table.
+        let sql = format!(
+            "
+            WITH aggr_comm AS (
+                SELECT 
+                    aggregate_operations.created_at, 
+                    eth_operations.final_hash, 
+                    commit_aggregated_blocks_binding.block_number 
+                FROM aggregate_operations
+                    INNER JOIN commit_aggregated_blocks_binding ON aggregate_operations.id = commit_aggregated_blocks_binding.op_id
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true 
+            ),
+            aggr_exec as (
+                 SELECT 
+                    aggregate_operations.created_at, 
+                    eth_operations.final_hash, 
+                    execute_aggregated_blocks_binding.block_number 
+                FROM aggregate_operations
+                    INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true 
+            )
+            SELECT
+                blocks.number AS block_number,
+                blocks.root_hash AS new_state_root,
+                blocks.block_size AS block_size,
+                committed.final_hash AS commit_tx_hash,
+                verified.final_hash AS verify_tx_hash,
+                committed.created_at AS committed_at,
+                verified.created_at AS verified_at
+            FROM blocks
+                     INNER JOIN aggr_comm committed ON blocks.number = committed.block_number
+                     LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
+                     CROSS JOIN LATERAL (
+                         SELECT
+                             (SELECT COUNT(*) FROM executed_transactions
+                              WHERE block_number = blocks.number AND success = true)
+                             + (SELECT COUNT(*) FROM executed_priority_operations
+                              WHERE block_number = blocks.number) AS tx_count,
+                             COALESCE((SELECT total_fee FROM block_token_fees
+                              WHERE block_number = blocks.number AND token_id = $7), 0) AS total_fee
+                     ) block_stats
+            WHERE
+                blocks.number {} $1
+                AND ($3::bigint IS NULL OR block_stats.tx_count >= $3)
+                AND ($4::bigint IS NULL OR block_stats.tx_count <= $4)
+                AND ($5::numeric IS NULL OR block_stats.total_fee >= $5)
+                AND ($6::numeric IS NULL OR block_stats.total_fee <= $6)
+            ORDER BY blocks.number {}
+            LIMIT $2
+            ",
+            comparison, order,
+        );
+        let details: Vec<StorageBlockDetails> = sqlx::query_as(&sql)
+            .bind(i64::from(*query.from))
+            .bind(i64::from(query.limit))
+            .bind(filter.min_tx_count.map(i64::from))
+            .bind(filter.max_tx_count.map(i64::from))
+            .bind(filter.min_total_fee.clone().map(biguint_to_big_decimal))
+            .bind(filter.max_total_fee.clone().map(biguint_to_big_decimal))
+            .bind(filter.fee_token.map(|token| *token as i32))
+            .fetch_all(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.chain.block.load_filtered_block_page", start.elapsed());
+        Ok(details)
+    }
+
+    /// Loads the sums of fees paid by the successful transactions of the given blocks,
+    /// grouped by the block and the token fee was paid in.
+    ///
+    /// Blocks without any fees paid are not included into the result.
+    pub async fn load_blocks_fees_by_token(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<Vec<StorageBlockTokenFee>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let fees = sqlx::query_as!(
+            StorageBlockTokenFee,
+            r#"
+            SELECT block_number, token_id, total_fee FROM block_token_fees
+            WHERE block_number = ANY($1)
+            "#,
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.load_blocks_fees_by_token", start.elapsed());
+        Ok(fees)
+    }
+
+    /// Converts the fees paid in the block with the token prices stored at the time of the block,
+    /// see `TokensSchema::store_block_token_prices`, and stores the result, so that the fees
+    /// reported for the block never change.
+    pub async fn store_block_fees(&mut self, block_number: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO block_fees (block_number, total_fee_usd, total_fee_eth_wei)
+            SELECT
+                blocks.number,
+                fees.total_fee_usd,
+                TRUNC(fees.total_fee_usd * power(10::numeric, 18) / eth_prices.usd_price)
+            FROM blocks
+            CROSS JOIN LATERAL (
+                SELECT
+                    CASE WHEN COUNT(*) = COUNT(token_prices_historical.usd_price) THEN COALESCE(SUM(
+                        block_token_fees.total_fee * token_prices_historical.usd_price / power(10::numeric, tokens.decimals)
+                    ), 0) END AS total_fee_usd
+                FROM block_token_fees
+                INNER JOIN tokens ON tokens.id = block_token_fees.token_id
+                LEFT JOIN token_prices_historical
+                    ON token_prices_historical.block_number = block_token_fees.block_number
+                    AND token_prices_historical.token_id = block_token_fees.token_id
+                    AND token_prices_historical.usd_price > 0
+                WHERE block_token_fees.block_number = blocks.number
+            ) fees
+            LEFT JOIN token_prices_historical eth_prices
+                ON eth_prices.block_number = blocks.number
+                AND eth_prices.token_id = 0
+                AND eth_prices.usd_price > 0
+            WHERE blocks.number = $1
+            ON CONFLICT (block_number) DO UPDATE
+            SET total_fee_usd = EXCLUDED.total_fee_usd, total_fee_eth_wei = EXCLUDED.total_fee_eth_wei
+            "#,
+            i64::from(*block_number)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.store_block_fees", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fees of the given blocks stored by `store_block_fees`.
+    /// Blocks without the stored fees are not included into the result.
+    pub async fn load_blocks_fees(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<HashMap<BlockNumber, StorageBlockFees>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let fees = sqlx::query_as!(
+            StorageBlockFees,
+            "SELECT * FROM block_fees WHERE block_number = ANY($1)",
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|fees| (BlockNumber(fees.block_number as u32), fees))
+        .collect();
+
+        metrics::histogram!("sql.chain.block.load_blocks_fees", start.elapsed());
+        Ok(fees)
+    }
+
+    /// Loads the gas used by the confirmed L1 transactions committing the given blocks and publishing
+    /// their proofs. Blocks without the confirmed commit transaction are omitted, gas is `None` if the
+    /// transaction is not confirmed yet or was confirmed before the gas started to be stored.
+    pub async fn load_blocks_gas_used(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<Vec<StorageBlockGasUsed>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let gas_used = sqlx::query_as!(
+            StorageBlockGasUsed,
+            r#"
+            WITH aggr_comm AS (
+                SELECT
+                    eth_operations.gas_used,
+                    commit_aggregated_blocks_binding.block_number
+                FROM aggregate_operations
+                    INNER JOIN commit_aggregated_blocks_binding ON aggregate_operations.id = commit_aggregated_blocks_binding.op_id
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true
+                    AND commit_aggregated_blocks_binding.block_number = ANY($1)
+            ),
+            aggr_proof AS (
+                SELECT
+                    eth_operations.gas_used,
+                    block_numbers.block_number
+                FROM UNNEST($1::bigint[]) AS block_numbers(block_number)
+                    INNER JOIN aggregate_operations ON block_numbers.block_number
+                        BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true
+                    AND aggregate_operations.action_type = 'PublishProofBlocksOnchain'
+            )
+            SELECT
+                committed.block_number AS "block_number!",
+                committed.gas_used AS "commit_gas_used?",
+                verified.gas_used AS "verify_gas_used?"
+            FROM aggr_comm committed
+                LEFT JOIN aggr_proof verified ON committed.block_number = verified.block_number
+            "#,
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.load_blocks_gas_used", start.elapsed());
+        Ok(gas_used)
+    }
+
+    /// Loads the senders of the confirmed L1 transactions publishing the proofs of the given blocks.
+    /// Blocks that are not verified yet or were verified before the senders started to be stored
+    /// are omitted.
+    pub async fn load_blocks_verifiers(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<Vec<StorageBlockVerifier>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let verifiers = sqlx::query_as!(
+            StorageBlockVerifier,
+            r#"
+            SELECT
+                block_numbers.block_number AS "block_number!",
+                eth_operations.from_address AS "verifier_address!"
+            FROM UNNEST($1::bigint[]) AS block_numbers(block_number)
+                INNER JOIN aggregate_operations ON block_numbers.block_number
+                    BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+            WHERE aggregate_operations.confirmed = true
+                AND aggregate_operations.action_type = 'PublishProofBlocksOnchain'
+                AND eth_operations.from_address IS NOT NULL
+            "#,
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.load_blocks_verifiers", start.elapsed());
+        Ok(verifiers)
+    }
+
+    /// Loads the proof generation latency statistics of the blocks in the given range (inclusive),
+    /// grouped by the periods the blocks were committed in. The latency of the block is the time
+    /// passed between the creation of its commit operation and the storing of its proof,
+    /// blocks without proofs are not taken into account.
+    pub async fn get_proof_latency_buckets(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        bucket_size: ProofLatencyBucketSize,
+    ) -> QueryResult<Vec<StorageProofLatencyBucket>> {
+        let start = Instant::now();
+        let buckets = sqlx::query_as!(
+            StorageProofLatencyBucket,
+            r#"
+            SELECT
+                DATE_TRUNC($3, committed.created_at) AS "period_start!",
+                AVG(latency.proof_ms) AS "avg_proof_ms!",
+                MAX(latency.proof_ms) AS "max_proof_ms!",
+                MIN(latency.proof_ms) AS "min_proof_ms!",
+                COUNT(*) AS "block_count!"
+            FROM commit_aggregated_blocks_binding
+                INNER JOIN aggregate_operations committed
+                    ON committed.id = commit_aggregated_blocks_binding.op_id
+                INNER JOIN proofs
+                    ON proofs.block_number = commit_aggregated_blocks_binding.block_number
+                CROSS JOIN LATERAL (
+                    SELECT (EXTRACT(EPOCH FROM (proofs.created_at - committed.created_at)) * 1000)::float8
+                        AS proof_ms
+                ) latency
+            WHERE commit_aggregated_blocks_binding.block_number BETWEEN $1 AND $2
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+            bucket_size.as_date_trunc_field()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.get_proof_latency_buckets", start.elapsed());
+        Ok(buckets)
+    }
+
+    /// Loads the block details together with the additional data requested in the expansion.
+    ///
+    /// The query is built dynamically: every requested expansion adds a single column set
+    /// and at most one `JOIN` to the base query, while the data for the expansions that
+    /// were not requested is returned as `NULL`.
+    pub async fn load_expanded_block_details(
+        &mut self,
+        block_number: BlockNumber,
+        expansion: &BlockDetailsExpansion,
+    ) -> QueryResult<Option<StorageExpandedBlockDetails>> {
+        let start = Instant::now();
+
+        let mut columns = Vec::new();
+        let mut joins = Vec::new();
+
+        if expansion.proof {
+            columns.push("proofs.proof AS proof");
+            joins.push("LEFT JOIN proofs ON proofs.block_number = blocks.number");
+        } else {
+            columns.push("NULL::jsonb AS proof");
+        }
+
+        if expansion.l1_hashes {
+            columns.push("l1.hashes AS l1_hashes");
+            joins.push(
+                "LEFT JOIN (
+                    SELECT array_agg('0x' || encode(eth_tx_hashes.tx_hash, 'hex') ORDER BY eth_tx_hashes.id) AS hashes
+                    FROM aggregate_operations
+                        INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                        INNER JOIN eth_tx_hashes ON eth_tx_hashes.eth_op_id = eth_aggregated_ops_binding.eth_op_id
+                    WHERE aggregate_operations.from_block <= $1 AND aggregate_operations.to_block >= $1
+                ) l1 ON true",
+            );
+        } else {
+            columns.push("NULL::text[] AS l1_hashes");
+        }
+
+        if expansion.statistics {
+            columns.push(
+                "stats.tx_count AS tx_count, stats.fee_token_ids AS fee_token_ids, \
+                 stats.fee_token_totals AS fee_token_totals",
+            );
+            // Fees are paid in tokens with different decimals, so they are reported per token
+            // from the sums stored with the block, see `save_block`.
+            joins.push(
+                "LEFT JOIN LATERAL (
+                    SELECT
+                        (SELECT COUNT(*) FROM executed_transactions WHERE block_number = $1 AND success = true)
+                            + (SELECT COUNT(*) FROM executed_priority_operations WHERE block_number = $1) AS tx_count,
+                        array_agg(block_token_fees.token_id ORDER BY block_token_fees.token_id) AS fee_token_ids,
+                        array_agg(block_token_fees.total_fee::text ORDER BY block_token_fees.token_id) AS fee_token_totals
+                    FROM block_token_fees
+                    WHERE block_token_fees.block_number = blocks.number AND block_token_fees.total_fee > 0
+                ) stats ON true",
+            );
+        } else {
+            columns.push(
+                "NULL::bigint AS tx_count, NULL::integer[] AS fee_token_ids, \
+                 NULL::text[] AS fee_token_totals",
+            );
+        }
+
+        // The base part of the query is the same as in `load_block_range_desc`.
+        let query = format!(
+            "
+            WITH aggr_comm AS (
+                SELECT 
+                    aggregate_operations.created_at, 
+                    eth_operations.final_hash, 
+                    commit_aggregated_blocks_binding.block_number 
+                FROM aggregate_operations
+                    INNER JOIN commit_aggregated_blocks_binding ON aggregate_operations.id = commit_aggregated_blocks_binding.op_id
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true 
+            ),
+            aggr_exec as (
+                 SELECT 
+                    aggregate_operations.created_at, 
+                    eth_operations.final_hash, 
+                    execute_aggregated_blocks_binding.block_number 
+                FROM aggregate_operations
+                    INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                    INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                    INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                WHERE aggregate_operations.confirmed = true 
+            )
+            SELECT
+                blocks.number AS block_number,
+                blocks.root_hash AS new_state_root,
+                blocks.block_size AS block_size,
+                committed.final_hash AS commit_tx_hash,
+                verified.final_hash AS verify_tx_hash,
+                committed.created_at AS committed_at,
+                verified.created_at AS verified_at,
+                {}
+            FROM blocks
+                INNER JOIN aggr_comm committed ON blocks.number = committed.block_number
+                LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
+                {}
+            WHERE blocks.number = $1
+            ",
+            columns.join(",\n                "),
+            joins.join("\n                "),
+        );
+
+        let details: Option<StorageExpandedBlockDetails> = sqlx::query_as(&query)
+            .bind(i64::from(*block_number))
+            .fetch_optional(self.0.conn())
+            .await?;
+
+        metrics::histogram!(
+            "sql.chain.block.load_expanded_block_details",
+            start.elapsed()
+        );
+        Ok(details)
+    }
+
     /// Loads the block headers for the given amount of blocks in the ascending order.
     pub async fn load_block_range_asc(
         &mut self,
@@ -495,20 +1068,20 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     }
 
     /// Helper method for `find_block_by_height_or_hash`. It checks whether
-    /// provided string can be interpreted like a hash, and if so, returns the
-    /// hexadecimal string without prefix.
-    fn try_parse_hex(&self, query: &str) -> Option<String> {
+    /// provided string can be interpreted like a 32-byte hash (either a raw
+    /// hexadecimal string or the one prefixed with `0x` or `sync-bl:`), and if so,
+    /// returns the decoded hash bytes.
+    fn try_parse_hex(&self, query: &str) -> Option<Vec<u8>> {
         const HASH_STRING_SIZE: usize = 32 * 2; // 32 bytes, 2 symbols per byte.
 
-        if let Some(query) = query.strip_prefix("0x") {
-            Some(query.into())
-        } else if let Some(query) = query.strip_prefix("sync-bl:") {
-            Some(query.into())
-        } else if query.len() == HASH_STRING_SIZE && hex::decode(query).is_ok() {
-            Some(query.into())
-        } else {
-            None
+        let hex_query = query
+            .strip_prefix("0x")
+            .or_else(|| query.strip_prefix("sync-bl:"))
+            .unwrap_or(query);
+        if hex_query.len() != HASH_STRING_SIZE {
+            return None;
         }
+        hex::decode(hex_query).ok()
     }
 
     /// Performs a database search with an uncertain query, which can be either of:
@@ -524,13 +1097,8 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     ) -> Option<StorageBlockDetails> {
         let start = Instant::now();
         // If the input looks like hash, add the hash lookup part.
-        let hash_bytes = if let Some(hex_query) = self.try_parse_hex(&query) {
-            // It must be a hexadecimal value, so unwrap is safe.
-            hex::decode(hex_query).unwrap()
-        } else {
-            // Not a hash, provide an empty vector.
-            vec![]
-        };
+        // Otherwise, provide an empty vector.
+        let hash_bytes = self.try_parse_hex(&query).unwrap_or_default();
 
         // If the input can be interpreted as integer, add the block number lookup part.
         let block_number = if let Ok(int_query) = query.parse::<i64>() {
@@ -933,6 +1501,29 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
 
+        // Sum up the fees of the block transactions, so that the blocks could be filtered
+        // by the paid fees without scanning the transactions. Most of the transactions store
+        // the fee token in the `feeToken` field, while for `Transfer`, `Withdraw` and `ForcedExit`
+        // it's the same as the `token` one.
+        sqlx::query!(
+            r#"
+            INSERT INTO block_token_fees (block_number, token_id, total_fee, tx_count)
+            SELECT
+                block_number,
+                COALESCE((tx->>'feeToken')::integer, (tx->>'token')::integer),
+                SUM((tx->>'fee')::numeric),
+                COUNT(*) FILTER (WHERE (tx->>'fee')::numeric > 0)
+            FROM executed_transactions
+            WHERE success = true AND block_number = $1 AND tx->>'fee' IS NOT NULL
+            GROUP BY 1, 2
+            ON CONFLICT (block_number, token_id) DO UPDATE
+            SET total_fee = EXCLUDED.total_fee, tx_count = EXCLUDED.tx_count
+            "#,
+            new_block.number
+        )
+        .execute(transaction.conn())
+        .await?;
+
         transaction.commit().await?;
 
         metrics::histogram!("sql.chain.block.save_block", start.elapsed());
@@ -1383,6 +1974,78 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_txs)
     }
 
+    /// Returns the amounts of the successful L2 transactions and L1 operations
+    /// executed in each of the given blocks. Blocks without operations are not included.
+    pub async fn load_blocks_tx_counts(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<HashMap<BlockNumber, u32>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let tx_counts = sqlx::query!(
+            r#"
+            SELECT block_number AS "block_number!", COUNT(*) AS "tx_count!"
+            FROM (
+                SELECT block_number FROM executed_transactions
+                WHERE success = true AND block_number = ANY($1)
+                UNION ALL
+                SELECT block_number FROM executed_priority_operations
+                WHERE block_number = ANY($1)
+            ) ops
+            GROUP BY block_number
+            "#,
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| (BlockNumber(row.block_number as u32), row.tx_count as u32))
+        .collect();
+
+        metrics::histogram!("sql.chain.block.load_blocks_tx_counts", start.elapsed());
+        Ok(tx_counts)
+    }
+
+    /// Returns the numbers of unique senders of both L1 and L2 operations in the provided blocks.
+    /// Failed transactions are not taken into account, blocks without operations are omitted.
+    pub async fn load_blocks_unique_senders(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<HashMap<BlockNumber, u32>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let unique_senders = sqlx::query!(
+            r#"
+            SELECT block_number AS "block_number!", COUNT(DISTINCT from_account) AS "unique_senders!"
+            FROM (
+                SELECT block_number, from_account FROM executed_transactions
+                WHERE success = true AND block_number = ANY($1)
+                UNION ALL
+                SELECT block_number, from_account FROM executed_priority_operations
+                WHERE block_number = ANY($1)
+            ) ops
+            GROUP BY block_number
+            "#,
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                BlockNumber(row.block_number as u32),
+                row.unique_senders as u32,
+            )
+        })
+        .collect();
+
+        metrics::histogram!(
+            "sql.chain.block.load_blocks_unique_senders",
+            start.elapsed()
+        );
+        Ok(unique_senders)
+    }
+
     /// Returns count of both L1 and L2 operations stored in the block
     pub async fn get_block_transactions_count(
         &mut self,
@@ -1539,6 +2202,58 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_number)
     }
 
+    /// Loads the root hashes of the state after the given blocks. Blocks that don't exist
+    /// are omitted, the genesis state is reported as the block 0 one.
+    pub async fn load_state_roots(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<HashMap<BlockNumber, Vec<u8>>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let records = sqlx::query!(
+            "SELECT number, root_hash FROM blocks WHERE number = ANY($1)",
+            &blocks
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let state_roots = records
+            .into_iter()
+            .map(|record| (BlockNumber(record.number as u32), record.root_hash))
+            .collect();
+
+        metrics::histogram!("sql.chain.block.load_state_roots", start.elapsed());
+        Ok(state_roots)
+    }
+
+    /// Returns the latest block not greater than `max_block` created at or before the given
+    /// Unix timestamp (in seconds). `None` if there are no such blocks.
+    pub async fn get_last_block_before_timestamp(
+        &mut self,
+        timestamp: i64,
+        max_block: BlockNumber,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+                SELECT number FROM blocks
+                WHERE number <= $1 AND timestamp <= $2
+                ORDER BY number DESC
+                LIMIT 1
+            "#,
+            i64::from(*max_block),
+            timestamp
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+        let block_number = record.map(|r| BlockNumber(r.number as u32));
+
+        metrics::histogram!(
+            "sql.chain.block.get_last_block_before_timestamp",
+            start.elapsed()
+        );
+        Ok(block_number)
+    }
+
     pub async fn get_block_transactions_hashes(
         &mut self,
         block_number: BlockNumber,
@@ -1575,4 +2290,113 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         );
         Ok(hashes)
     }
+
+    /// Requests the exit proof of the account balance against the given verified block.
+    /// Returns `false` if the proof for this or a later block is already requested.
+    ///
+    /// A request for a later block than the stored one erases the stored proof,
+    /// so that it's generated again against the latest verified state.
+    pub async fn request_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let requested = sqlx::query!(
+            r#"
+            INSERT INTO exit_proofs (account_id, token_id, block_number)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (account_id, token_id) DO UPDATE
+            SET block_number = $3, created_at = now(), proof = NULL, amount = NULL
+            WHERE exit_proofs.block_number < $3
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            i64::from(*block_number)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+
+        metrics::histogram!("sql.chain.block.request_exit_proof", start.elapsed());
+        Ok(requested)
+    }
+
+    /// Loads the requested exit proof of the account balance, which may be not generated yet.
+    pub async fn get_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> QueryResult<Option<StorageExitProof>> {
+        let start = Instant::now();
+        let exit_proof = sqlx::query_as!(
+            StorageExitProof,
+            "SELECT * FROM exit_proofs WHERE account_id = $1 AND token_id = $2",
+            i64::from(*account_id),
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.get_exit_proof", start.elapsed());
+        Ok(exit_proof)
+    }
+
+    /// Loads the oldest exit proof request which is not fulfilled yet.
+    pub async fn get_pending_exit_proof(&mut self) -> QueryResult<Option<StorageExitProof>> {
+        let start = Instant::now();
+        let exit_proof = sqlx::query_as!(
+            StorageExitProof,
+            "SELECT * FROM exit_proofs WHERE proof IS NULL ORDER BY created_at LIMIT 1",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.get_pending_exit_proof", start.elapsed());
+        Ok(exit_proof)
+    }
+
+    /// Returns the number of the exit proof requests which are not fulfilled yet.
+    pub async fn count_pending_exit_proofs(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count =
+            sqlx::query!(r#"SELECT COUNT(*) AS "count!" FROM exit_proofs WHERE proof IS NULL"#)
+                .fetch_one(self.0.conn())
+                .await?
+                .count;
+
+        metrics::histogram!("sql.chain.block.count_pending_exit_proofs", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Stores the generated exit proof of the account balance. The proof is discarded
+    /// if the request was renewed for a later block in the meantime.
+    pub async fn store_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+        proof: &EncodedSingleProof,
+        amount: BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE exit_proofs SET proof = $4, amount = $5
+            WHERE account_id = $1 AND token_id = $2 AND block_number = $3
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            i64::from(*block_number),
+            serde_json::to_value(proof).expect("Proof is serializable"),
+            biguint_to_big_decimal(amount)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.store_exit_proof", start.elapsed());
+        Ok(())
+    }
 }
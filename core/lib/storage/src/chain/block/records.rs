@@ -2,7 +2,7 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
-use sqlx::FromRow;
+use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 use zksync_types::{event::block::BlockDetails, BlockNumber};
 use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, SyncBlockPrefix, ZeroxPrefix};
@@ -75,6 +75,101 @@ pub struct StorageBlockDetails {
     pub verified_at: Option<DateTime<Utc>>,
 }
 
+/// Block details extended with the data requested via `BlockDetailsExpansion`.
+/// Fields related to the expansions that were not requested are always `None`.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageExpandedBlockDetails {
+    pub block_number: i64,
+    pub new_state_root: Vec<u8>,
+    pub block_size: i64,
+    pub commit_tx_hash: Option<Vec<u8>>,
+    pub verify_tx_hash: Option<Vec<u8>>,
+    pub committed_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+    /// Block proof, if it's already generated.
+    pub proof: Option<Value>,
+    /// Hashes of all the Ethereum transactions sent for the aggregated operations
+    /// the block is included into, in the order they were sent.
+    pub l1_hashes: Option<Vec<String>>,
+    /// Amount of operations executed in the block.
+    pub tx_count: Option<i64>,
+    /// IDs of the tokens the fees of the successful transactions were paid in, ordered by the ID.
+    /// `None` if the block has no fees paid.
+    pub fee_token_ids: Option<Vec<i32>>,
+    /// Sums of the fees paid in the tokens from `fee_token_ids`, in the smallest token units.
+    pub fee_token_totals: Option<Vec<String>>,
+}
+
+impl StorageExpandedBlockDetails {
+    /// Returns the plain block details without the expanded data.
+    pub fn details(&self) -> StorageBlockDetails {
+        StorageBlockDetails {
+            block_number: self.block_number,
+            new_state_root: self.new_state_root.clone(),
+            block_size: self.block_size,
+            commit_tx_hash: self.commit_tx_hash.clone(),
+            verify_tx_hash: self.verify_tx_hash.clone(),
+            committed_at: self.committed_at,
+            verified_at: self.verified_at,
+        }
+    }
+}
+
+/// Statistics of the proof generation latency of the blocks committed within a single period,
+/// see `BlockSchema::get_proof_latency_buckets`.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageProofLatencyBucket {
+    pub period_start: DateTime<Utc>,
+    pub avg_proof_ms: f64,
+    pub max_proof_ms: f64,
+    pub min_proof_ms: f64,
+    pub block_count: i64,
+}
+
+/// Gas used by the L1 transactions committing the block and publishing its proof. Transactions
+/// process several blocks at once, so the gas is reported for the whole transaction.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageBlockGasUsed {
+    pub block_number: i64,
+    pub commit_gas_used: Option<i64>,
+    pub verify_gas_used: Option<i64>,
+}
+
+/// Sender of the L1 transaction executing the block.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageBlockVerifier {
+    pub block_number: i64,
+    pub verifier_address: Vec<u8>,
+}
+
+/// Sum of fees paid in a single token by the successful transactions of the block.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageBlockTokenFee {
+    pub block_number: i64,
+    pub token_id: i32,
+    pub total_fee: BigDecimal,
+}
+
+/// Fees paid in the block converted with the token prices at the time the block was committed.
+/// Fees are `None` if the price of any of the tokens is not known.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageBlockFees {
+    pub block_number: i64,
+    pub total_fee_usd: Option<BigDecimal>,
+    pub total_fee_eth_wei: Option<BigDecimal>,
+}
+
+/// Exit proof of the account balance, `proof` and `amount` are `None` until it's generated.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageExitProof {
+    pub account_id: i64,
+    pub token_id: i32,
+    pub block_number: i64,
+    pub created_at: DateTime<Utc>,
+    pub proof: Option<Value>,
+    pub amount: Option<BigDecimal>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct BlockTransactionItem {
     pub tx_hash: String,
@@ -117,6 +117,38 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(txs.into())
     }
 
+    /// Loads the transactions awaiting for the execution in the order they were received,
+    /// the transactions of the reverted blocks are not included.
+    pub async fn load_pending_txs(&mut self, limit: u32) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            MempoolTx,
+            "SELECT * FROM mempool_txs WHERE reverted = false ORDER BY id LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(SignedZkSyncTx::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        metrics::histogram!("sql.chain.mempool.load_pending_txs", start.elapsed());
+        Ok(txs)
+    }
+
+    /// Returns the number of the transactions loaded by `load_pending_txs` without the limit.
+    pub async fn get_pending_txs_count(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count =
+            sqlx::query!(r#"SELECT COUNT(*) as "count!" FROM mempool_txs WHERE reverted = false"#)
+                .fetch_one(self.0.conn())
+                .await?
+                .count;
+
+        metrics::histogram!("sql.chain.mempool.get_pending_txs_count", start.elapsed());
+        Ok(count as u32)
+    }
+
     pub async fn remove_reverted_block(&mut self, block_number: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -548,6 +580,28 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .map(|op| op.into());
         Ok(op)
     }
+
+    /// Returns the priority operation with the given serial ID if it's awaiting for the execution.
+    pub async fn get_pending_operation_by_serial_id(
+        &mut self,
+        serial_id: SerialId,
+    ) -> QueryResult<Option<PriorityOp>> {
+        let op = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+                SELECT serial_id,data,deadline_block,eth_hash,
+                       tx_hash,eth_block,eth_block_index,created_at 
+                FROM mempool_priority_operations 
+                WHERE serial_id = $1
+            "#,
+            serial_id as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|op| op.into());
+        Ok(op)
+    }
+
     pub async fn get_pending_deposits(&mut self, address: Address) -> QueryResult<Vec<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
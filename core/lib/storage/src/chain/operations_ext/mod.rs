@@ -1,8 +1,10 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 
 // External imports
 use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use sqlx::types::BigDecimal;
 
 // Workspace imports
 use zksync_api_types::{
@@ -17,21 +19,25 @@ use zksync_api_types::{
 };
 use zksync_crypto::params;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, TokenId,
-    ZkSyncOp, ZkSyncTx, H256,
+    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, Nonce, Token,
+    TokenId, ZkSyncOp, ZkSyncTx, H256,
 };
+use zksync_utils::{biguint_to_big_decimal, ratio_to_big_decimal};
 
 // Local imports
 use self::records::{
-    AccountCreatedAt, InBlockBatchTx, PriorityOpReceiptResponse, StorageTxData, StorageTxReceipt,
+    AccountActivitySpan, AccountCreatedAt, InBlockBatchTx, PriorityOpReceiptResponse,
+    StorageChangePubKeyTx, StorageDeposit, StorageForcedExit, StorageTxData, StorageTxReceipt,
     TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse, Web3TxData, Web3TxReceipt,
 };
 use crate::chain::operations_ext::records::SequenceNumberRecord;
 use crate::{
     chain::{
-        block::records::TransactionItem,
+        block::records::{BlockTransactionItem, TransactionItem},
         operations::{records::StoredExecutedPriorityOperation, OperationsSchema},
     },
+    ethereum::EthereumSchema,
+    tokens::STORED_USD_PRICE_PRECISION,
     QueryResult, StorageProcessor,
 };
 use itertools::Itertools;
@@ -39,6 +45,98 @@ use itertools::Itertools;
 pub(crate) mod conversion;
 pub mod records;
 
+/// Amount transferred by the operation the `tx_filters` row refers to.
+/// `NULL` for operations without an amount, e.g. `ChangePubKey`.
+const TX_FILTERS_AMOUNT: &str = "(CASE WHEN tx_filters.is_priority THEN (
+        SELECT operation->'priority_op'->>'amount' FROM executed_priority_operations
+        WHERE executed_priority_operations.sequence_number = tx_filters.sequence_number
+    ) ELSE (
+        SELECT tx->>'amount' FROM executed_transactions
+        WHERE executed_transactions.sequence_number = tx_filters.sequence_number
+    ) END)::numeric";
+
+/// Builds a condition restricting the transferred amount to the bounds passed as
+/// the `$min_param` and `$max_param` query parameters. Absent bounds are not checked.
+fn amount_range_condition(
+    min_amount: Option<&BigUint>,
+    max_amount: Option<&BigUint>,
+    min_param: usize,
+    max_param: usize,
+) -> String {
+    let mut condition = String::new();
+    if min_amount.is_some() {
+        condition.push_str(&format!(" AND {} >= ${}", TX_FILTERS_AMOUNT, min_param));
+    }
+    if max_amount.is_some() {
+        condition.push_str(&format!(" AND {} <= ${}", TX_FILTERS_AMOUNT, max_param));
+    }
+    condition
+}
+
+/// Statuses of the `ForcedExit` transactions to load, see `get_forced_exits_by_initiator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedExitsFilter {
+    /// Executed transactions from the blocks that are not verified yet.
+    Pending,
+    /// Executed transactions from the verified blocks.
+    Completed,
+    /// All the transactions, including the rejected ones.
+    All,
+}
+
+/// Filter of the account history by the token and the amount of the operations.
+///
+/// Amounts of different tokens are not comparable, so the amount bounds are expected
+/// to be set along with the token. Operations without an amount of their own, e.g.
+/// `ChangePubKey` or `Swap`, don't match the amount bounds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountHistoryFilter {
+    pub token: Option<TokenId>,
+    /// Lower bound of the amount, inclusive.
+    pub min_amount: Option<BigUint>,
+    /// Upper bound of the amount, inclusive.
+    pub max_amount: Option<BigUint>,
+    /// Transfers worth less than this many dollars at the price stored by the ticker
+    /// are excluded. Transfers of the tokens without a stored price are kept.
+    pub dust_threshold_usd: Option<Ratio<BigUint>>,
+}
+
+impl AccountHistoryFilter {
+    /// Returns `true` if the operation with the given token and amount passes the filter.
+    /// Used for the operations that aren't stored in the database yet, e.g. pending deposits,
+    /// so the dust threshold of the transfers is not checked.
+    pub fn matches(&self, token: TokenId, amount: &BigUint) -> bool {
+        self.token
+            .map_or(true, |filter_token| filter_token == token)
+            && self
+                .min_amount
+                .as_ref()
+                .map_or(true, |min_amount| amount >= min_amount)
+            && self
+                .max_amount
+                .as_ref()
+                .map_or(true, |max_amount| amount <= max_amount)
+    }
+
+    fn token_param(&self) -> Option<i32> {
+        self.token.map(|token| *token as i32)
+    }
+
+    fn min_amount_param(&self) -> Option<BigDecimal> {
+        self.min_amount.clone().map(biguint_to_big_decimal)
+    }
+
+    fn max_amount_param(&self) -> Option<BigDecimal> {
+        self.max_amount.clone().map(biguint_to_big_decimal)
+    }
+
+    fn dust_threshold_param(&self) -> Option<BigDecimal> {
+        self.dust_threshold_usd
+            .as_ref()
+            .map(|threshold| ratio_to_big_decimal(threshold, STORED_USD_PRICE_PRECISION))
+    }
+}
+
 /// Direction to perform search of transactions to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SearchDirection {
@@ -360,16 +458,33 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     .map(|operation| operation.confirmed)
                     .unwrap_or_default();
 
+                // Ethereum sender confirms the transaction only after it gets the required
+                // amount of L1 confirmations, so having the final hash means the operation is finalized.
+                let block_number = BlockNumber(stored_executed_prior_op.block_number as u32);
+                let verify_eth_tx_hash = if verified {
+                    EthereumSchema(self.0)
+                        .aggregated_op_final_hash(block_number)
+                        .await?
+                } else {
+                    None
+                };
+
                 Ok(PriorityOpReceiptResponse {
                     committed: true,
                     verified,
                     prover_run: None,
+                    verified_in_l2_block: verified.then(|| *block_number),
+                    finalized: verify_eth_tx_hash.is_some(),
+                    verify_eth_tx_hash: verify_eth_tx_hash.map(|hash| format!("{:#x}", hash)),
                 })
             }
             None => Ok(PriorityOpReceiptResponse {
                 committed: false,
                 verified: false,
                 prover_run: None,
+                verified_in_l2_block: None,
+                verify_eth_tx_hash: None,
+                finalized: false,
             }),
         };
 
@@ -665,6 +780,97 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(first_history_entry.map(|entry| entry.created_at))
     }
 
+    /// Loads the date and time of the first and the last operations of the account
+    /// along with the total number of its operations. Timestamps are `None` if there
+    /// were no operations associated with provided address.
+    pub async fn account_activity_span(
+        &mut self,
+        address: &Address,
+    ) -> QueryResult<AccountActivitySpan> {
+        let start = Instant::now();
+        // Same as in `account_created_on`, both executed transactions and executed priority
+        // operations are taken into account.
+        let activity_span = sqlx::query_as!(
+            AccountActivitySpan,
+            r#"
+            SELECT
+                MIN(created_at) AS "first_tx_at?",
+                MAX(created_at) AS "last_tx_at?",
+                COUNT(*) AS "tx_count!"
+            FROM (
+                SELECT created_at FROM executed_transactions
+                WHERE from_account = $1 OR to_account = $1 OR primary_account_address = $1
+                UNION ALL
+                SELECT created_at FROM executed_priority_operations
+                WHERE from_account = $1 OR to_account = $1
+            ) t
+            "#,
+            address.as_ref(),
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.account_activity_span",
+            start.elapsed()
+        );
+        Ok(activity_span)
+    }
+
+    /// Loads the hash of the successfully executed transaction which used the given nonce
+    /// of the account. Rejected transactions don't increment the nonce, so they're not taken into account.
+    pub async fn get_tx_hash_by_nonce(
+        &mut self,
+        address: &Address,
+        nonce: Nonce,
+    ) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT tx_hash FROM executed_transactions
+            WHERE primary_account_address = $1 AND nonce = $2 AND success = true
+            LIMIT 1
+            "#,
+            address.as_ref(),
+            i64::from(*nonce),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_tx_hash_by_nonce",
+            start.elapsed()
+        );
+        Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
+    }
+
+    /// Loads the most recent successful `ChangePubKey` transaction of the account.
+    pub async fn get_latest_change_pubkey(
+        &mut self,
+        address: &Address,
+    ) -> QueryResult<Option<StorageChangePubKeyTx>> {
+        let start = Instant::now();
+        let record = sqlx::query_as!(
+            StorageChangePubKeyTx,
+            r#"
+            SELECT tx_hash, block_number, block_index, tx->>'newPkHash' AS "new_pk_hash!", created_at
+            FROM executed_transactions
+            WHERE primary_account_address = $1 AND success = true AND tx->>'type' = 'ChangePubKey'
+            ORDER BY block_number DESC, block_index DESC
+            LIMIT 1
+            "#,
+            address.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_latest_change_pubkey",
+            start.elapsed()
+        );
+        Ok(record)
+    }
+
     /// Loads the range of the transactions applied to the account starting
     /// from the block with number $(offset) up to $(offset + limit).
     pub async fn get_account_transactions_history(
@@ -672,6 +878,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         address: &Address,
         offset: u64,
         limit: u64,
+        filter: &AccountHistoryFilter,
     ) -> QueryResult<Vec<TransactionsHistoryItem>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -699,7 +906,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 WHERE aggregate_operations.confirmed = true
             ), tx_hashes AS (
                 SELECT DISTINCT tx_hash FROM tx_filters
-                WHERE address = $1
+                WHERE address = $1 AND ($4::integer IS NULL OR token = $4)
             ), transactions AS (
                 SELECT
                     *
@@ -715,10 +922,25 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         sequence_number,
-                        batch_id
+                        batch_id,
+                        eth_sign_data->'signature'->>'signature' AS eth_signature,
+                        false as is_priority_op,
+                        (tx->>'token')::integer AS token_id,
+                        (tx->>'amount')::numeric AS amount
                     FROM tx_hashes
                     INNER JOIN executed_transactions
                         ON tx_hashes.tx_hash = executed_transactions.tx_hash
+                    WHERE TRUE
+                        AND ($5::numeric IS NULL OR (tx->>'amount')::numeric >= $5)
+                        AND ($6::numeric IS NULL OR (tx->>'amount')::numeric <= $6)
+                        AND ($7::numeric IS NULL OR NOT EXISTS (
+                            SELECT 1 FROM ticker_price
+                            INNER JOIN tokens ON tokens.id = ticker_price.token_id
+                            WHERE tx->>'type' = 'Transfer'
+                                AND ticker_price.token_id = (tx->>'token')::integer
+                                AND (tx->>'amount')::numeric * ticker_price.usd_price
+                                    < $7 * power(10::numeric, tokens.decimals)
+                        ))
                     union all
                     select
                         concat_ws(',', block_number, block_index) as tx_id,
@@ -731,13 +953,23 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         sequence_number,
-                        Null::bigint as batch_id
+                        Null::bigint as batch_id,
+                        Null::text as eth_signature,
+                        true as is_priority_op,
+                        (operation->'priority_op'->>'token')::integer AS token_id,
+                        (operation->'priority_op'->>'amount')::numeric AS amount
                     from
                         executed_priority_operations
                     where
-                        from_account = $1
-                        or
-                        to_account = $1) t
+                        (
+                            from_account = $1
+                            or
+                            to_account = $1
+                        )
+                        AND ($4::integer IS NULL OR (operation->'priority_op'->>'token')::integer = $4)
+                        AND ($5::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric >= $5)
+                        AND ($6::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric <= $6)
+                    ) t
                 order by
                     block_number desc, created_at desc
                 offset
@@ -756,51 +988,30 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 true as "commited!",
                 coalesce(verified.confirmed, false) as "verified!",
                 created_at as "created_at!",
-                batch_id as "batch_id?"
+                transactions.batch_id as "batch_id?",
+                'sync-tx:' || encode(txs_batches_hashes.batch_hash, 'hex') as "batch_hash?",
+                eth_signature as "eth_signature?",
+                is_priority_op as "is_priority_op!",
+                transactions.amount * token_prices_historical.usd_price
+                    / power(10::numeric, tokens.decimals) as "usd_value_at_time?"
             from transactions
+            LEFT JOIN txs_batches_hashes ON txs_batches_hashes.batch_id = transactions.batch_id
             LEFT JOIN aggr_exec verified ON transactions.block_number = verified.block_number
+            LEFT JOIN token_prices_historical
+                ON token_prices_historical.block_number = transactions.block_number
+                AND token_prices_historical.token_id = transactions.token_id
+            LEFT JOIN tokens ON tokens.id = transactions.token_id
             order by transactions.block_number desc, sequence_number desc
             "#,
-            address.as_ref(), offset as i64, limit as i64
+            address.as_ref(), offset as i64, limit as i64,
+            filter.token_param(), filter.min_amount_param(), filter.max_amount_param(),
+            filter.dust_threshold_param()
         ).fetch_all(transaction.conn())
         .await?;
 
         if !tx_history.is_empty() {
             let tokens = transaction.tokens_schema().load_tokens().await?;
-            for tx_item in &mut tx_history {
-                let tx_info = match tx_item.tx["type"].as_str().unwrap_or("NONE") {
-                    "NONE" => {
-                        vlog::warn!("Tx history item type not found, tx: {:?}", tx_item);
-                        continue;
-                    }
-                    "Deposit" | "FullExit" => tx_item.tx.get_mut("priority_op"),
-                    _ => Some(&mut tx_item.tx),
-                };
-
-                let tx_info = if let Some(tx_info) = tx_info {
-                    tx_info
-                } else {
-                    vlog::warn!("tx_info not found for tx: {:?}", tx_item);
-                    continue;
-                };
-
-                if let Some(tok_val) = tx_info.get_mut("token") {
-                    if let Some(token_id) = tok_val.as_u64() {
-                        if token_id < params::MIN_NFT_TOKEN_ID as u64 {
-                            let token_id = TokenId(token_id as u32);
-                            let token_symbol = tokens
-                                .get(&token_id)
-                                .map(|t| t.symbol.clone())
-                                .unwrap_or_else(|| "UNKNOWN".to_string());
-                            *tok_val =
-                                serde_json::to_value(token_symbol).expect("json string to value");
-                        } else {
-                            *tok_val =
-                                serde_json::to_value(token_id).expect("json string to value");
-                        }
-                    };
-                };
-            }
+            replace_token_ids_with_symbols(&mut tx_history, &tokens);
         }
 
         transaction.commit().await?;
@@ -826,6 +1037,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         tx_id: (u64, u64),
         direction: SearchDirection,
         limit: u64,
+        filter: &AccountHistoryFilter,
     ) -> QueryResult<Vec<TransactionsHistoryItem>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -873,7 +1085,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                WHERE aggregate_operations.confirmed = true 
             ), tx_hashes AS (
                 SELECT DISTINCT tx_hash FROM tx_filters
-                WHERE address = $1
+                WHERE address = $1 AND ($8::integer IS NULL OR token = $8)
             ), transactions as (
                 select
                     *
@@ -889,12 +1101,24 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         sequence_number,
-                        batch_id
+                        batch_id,
+                        eth_sign_data->'signature'->>'signature' as eth_signature,
+                        false as is_priority_op
                     from tx_hashes
                     inner join executed_transactions
                         on tx_hashes.tx_hash = executed_transactions.tx_hash
                     where
-                        block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6)
+                        (block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6))
+                        AND ($9::numeric IS NULL OR (tx->>'amount')::numeric >= $9)
+                        AND ($10::numeric IS NULL OR (tx->>'amount')::numeric <= $10)
+                        AND ($11::numeric IS NULL OR NOT EXISTS (
+                            SELECT 1 FROM ticker_price
+                            INNER JOIN tokens ON tokens.id = ticker_price.token_id
+                            WHERE tx->>'type' = 'Transfer'
+                                AND ticker_price.token_id = (tx->>'token')::integer
+                                AND (tx->>'amount')::numeric * ticker_price.usd_price
+                                    < $11 * power(10::numeric, tokens.decimals)
+                        ))
                     union all
                     select
                         concat_ws(',', block_number, block_index) as tx_id,
@@ -907,7 +1131,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         block_number,
                         created_at,
                         sequence_number,
-                        Null::bigint as batch_id
+                        Null::bigint as batch_id,
+                        Null::text as eth_signature,
+                        true as is_priority_op
                     from 
                         executed_priority_operations
                     where 
@@ -918,6 +1144,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         )
                         and
                         (block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6))
+                        AND ($8::integer IS NULL OR (operation->'priority_op'->>'token')::integer = $8)
+                        AND ($9::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric >= $9)
+                        AND ($10::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric <= $10)
                     ) t
                 order by
                     sequence_number desc
@@ -935,8 +1164,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 true as "commited!",
                 coalesce(verified.confirmed, false) as "verified!",
                 created_at as "created_at!",
-                batch_id as "batch_id?"
+                transactions.batch_id as "batch_id?",
+                'sync-tx:' || encode(txs_batches_hashes.batch_hash, 'hex') as "batch_hash?",
+                eth_signature as "eth_signature?",
+                is_priority_op as "is_priority_op!",
+                NULL::numeric as "usd_value_at_time?"
             from transactions
+            LEFT JOIN txs_batches_hashes ON txs_batches_hashes.batch_id = transactions.batch_id
             left join aggr_comm committed on
                 committed.block_number = transactions.block_number AND committed.confirmed = true
             left join aggr_exec verified on
@@ -947,51 +1181,516 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             block_id as i64,
             block_number_start_idx, block_number_end_idx,
             tx_number_start_idx, tx_number_end_idx,
-            limit as i64
+            limit as i64,
+            filter.token_param(), filter.min_amount_param(), filter.max_amount_param(),
+            filter.dust_threshold_param()
         ).fetch_all(transaction.conn())
         .await?;
 
         if !tx_history.is_empty() {
             let tokens = transaction.tokens_schema().load_tokens().await?;
-            for tx_item in &mut tx_history {
-                let tx_info = match tx_item.tx["type"].as_str().unwrap_or("NONE") {
-                    "NONE" => {
-                        vlog::warn!("Tx history item type not found, tx: {:?}", tx_item);
-                        continue;
-                    }
-                    "Deposit" | "FullExit" => tx_item.tx.get_mut("priority_op"),
-                    _ => Some(&mut tx_item.tx),
-                };
+            replace_token_ids_with_symbols(&mut tx_history, &tokens);
+        }
 
-                let tx_info = if let Some(tx_info) = tx_info {
-                    tx_info
-                } else {
-                    vlog::warn!("tx_info not found for tx: {:?}", tx_item);
-                    continue;
-                };
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_transactions_history_from",
+            start.elapsed()
+        );
+        Ok(tx_history)
+    }
 
-                if let Some(tok_val) = tx_info.get_mut("token") {
-                    if let Some(token_id) = tok_val.as_u64() {
-                        if token_id < params::MIN_NFT_TOKEN_ID as u64 {
-                            let token_id = TokenId(token_id as u32);
-                            let token_symbol = tokens
-                                .get(&token_id)
-                                .map(|t| t.symbol.clone())
-                                .unwrap_or_else(|| "UNKNOWN".to_string());
-                            *tok_val =
-                                serde_json::to_value(token_symbol).expect("json string to value");
-                        } else {
-                            *tok_val =
-                                serde_json::to_value(token_id).expect("json string to value");
-                        }
-                    };
-                };
+    /// Loads the transactions applied to the account that were executed after the specified
+    /// `(block_number, block_index)` position, oldest first. Starts from the first transaction
+    /// of the account if the position is not specified.
+    ///
+    /// Rejected transactions have no position in the block and thus are not returned.
+    pub async fn get_account_transactions_history_newer_than(
+        &mut self,
+        address: &Address,
+        tx_id: Option<(u64, u64)>,
+        limit: u64,
+        filter: &AccountHistoryFilter,
+    ) -> QueryResult<Vec<TransactionsHistoryItem>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let (block_number, block_index) = tx_id
+            .map(|(block_number, block_index)| (block_number as i64, block_index as i32))
+            .unwrap_or((-1, -1));
+
+        let mut tx_history = sqlx::query_as!(
+            TransactionsHistoryItem,
+            r#"
+            WITH aggr_exec AS (
+                SELECT 
+                   aggregate_operations.confirmed, 
+                   execute_aggregated_blocks_binding.block_number 
+               FROM aggregate_operations
+                   INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+               WHERE aggregate_operations.confirmed = true 
+            ), tx_hashes AS (
+                SELECT DISTINCT tx_hash FROM tx_filters
+                WHERE address = $1 AND ($5::integer IS NULL OR token = $5)
+            ), transactions as (
+                select
+                    *
+                from (
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        tx,
+                        'sync-tx:' || encode(executed_transactions.tx_hash, 'hex') as hash,
+                        null as pq_id,
+                        null as eth_block,
+                        success,
+                        fail_reason,
+                        block_number,
+                        block_index,
+                        created_at,
+                        batch_id,
+                        eth_sign_data->'signature'->>'signature' as eth_signature,
+                        false as is_priority_op
+                    from tx_hashes
+                    inner join executed_transactions
+                        on tx_hashes.tx_hash = executed_transactions.tx_hash
+                    where
+                        block_index IS NOT NULL
+                        AND (block_number, block_index) > ($2, $3)
+                        AND ($6::numeric IS NULL OR (tx->>'amount')::numeric >= $6)
+                        AND ($7::numeric IS NULL OR (tx->>'amount')::numeric <= $7)
+                        AND ($8::numeric IS NULL OR NOT EXISTS (
+                            SELECT 1 FROM ticker_price
+                            INNER JOIN tokens ON tokens.id = ticker_price.token_id
+                            WHERE tx->>'type' = 'Transfer'
+                                AND ticker_price.token_id = (tx->>'token')::integer
+                                AND (tx->>'amount')::numeric * ticker_price.usd_price
+                                    < $8 * power(10::numeric, tokens.decimals)
+                        ))
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        operation as tx,
+                        '0x' || encode(eth_hash, 'hex') as hash,
+                        priority_op_serialid as pq_id,
+                        eth_block,
+                        true as success,
+                        null as fail_reason,
+                        block_number,
+                        block_index,
+                        created_at,
+                        Null::bigint as batch_id,
+                        Null::text as eth_signature,
+                        true as is_priority_op
+                    from 
+                        executed_priority_operations
+                    where 
+                        (
+                            from_account = $1
+                            or
+                            to_account = $1
+                        )
+                        and
+                        (block_number, block_index) > ($2, $3)
+                        AND ($5::integer IS NULL OR (operation->'priority_op'->>'token')::integer = $5)
+                        AND ($6::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric >= $6)
+                        AND ($7::numeric IS NULL OR (operation->'priority_op'->>'amount')::numeric <= $7)
+                    ) t
+                order by
+                    block_number asc, block_index asc
+                limit 
+                    $4
+            )
+            select
+                tx_id as "tx_id!",
+                hash as "hash?",
+                eth_block as "eth_block?",
+                pq_id as "pq_id?",
+                tx as "tx!",
+                success as "success?",
+                fail_reason as "fail_reason?",
+                true as "commited!",
+                coalesce(verified.confirmed, false) as "verified!",
+                created_at as "created_at!",
+                transactions.batch_id as "batch_id?",
+                'sync-tx:' || encode(txs_batches_hashes.batch_hash, 'hex') as "batch_hash?",
+                eth_signature as "eth_signature?",
+                is_priority_op as "is_priority_op!",
+                NULL::numeric as "usd_value_at_time?"
+            from transactions
+            LEFT JOIN txs_batches_hashes ON txs_batches_hashes.batch_id = transactions.batch_id
+            left join aggr_exec verified on
+                verified.block_number = transactions.block_number AND verified.confirmed = true
+            order by transactions.block_number asc, transactions.block_index asc
+            "#,
+            address.as_ref(),
+            block_number,
+            block_index,
+            limit as i64,
+            filter.token_param(), filter.min_amount_param(), filter.max_amount_param(),
+            filter.dust_threshold_param()
+        ).fetch_all(transaction.conn())
+        .await?;
+
+        if !tx_history.is_empty() {
+            let tokens = transaction.tokens_schema().load_tokens().await?;
+            replace_token_ids_with_symbols(&mut tx_history, &tokens);
+        }
+
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_transactions_history_newer_than",
+            start.elapsed()
+        );
+        Ok(tx_history)
+    }
+
+    /// Loads the page of the `ForcedExit` transactions initiated by the account, newest first.
+    /// `offset` is the number of the newer transactions to skip.
+    ///
+    /// Returns an empty list if the account doesn't exist.
+    pub async fn get_forced_exits_by_initiator(
+        &mut self,
+        initiator: Address,
+        filter: ForcedExitsFilter,
+        offset: u32,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageForcedExit>> {
+        let start = Instant::now();
+        let initiator_id = match self
+            .0
+            .chain()
+            .account_schema()
+            .account_id_by_address(initiator)
+            .await?
+        {
+            Some(account_id) => account_id,
+            None => return Ok(Vec::new()),
+        };
+        let last_verified = self
+            .0
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        // `None` stands for no filter, otherwise only the successful transactions
+        // with the matching verification status are returned.
+        let verified = match filter {
+            ForcedExitsFilter::Pending => Some(false),
+            ForcedExitsFilter::Completed => Some(true),
+            ForcedExitsFilter::All => None,
+        };
+
+        let forced_exits = sqlx::query_as!(
+            StorageForcedExit,
+            r#"
+                SELECT
+                    '0x' || encode(tx_hash, 'hex') as "tx_hash!",
+                    tx->>'target' as "target!",
+                    (tx->>'token')::bigint as "token!",
+                    operation->>'withdraw_amount' as "amount?",
+                    block_number,
+                    success,
+                    (success AND block_number <= $2) as "verified!",
+                    fail_reason,
+                    created_at
+                FROM executed_transactions
+                WHERE tx->>'type' = 'ForcedExit'
+                    AND (tx->>'initiatorAccountId')::bigint = $1
+                    AND ($3::boolean IS NULL OR (success AND (block_number <= $2) = $3))
+                ORDER BY sequence_number DESC
+                OFFSET $4
+                LIMIT $5
+            "#,
+            i64::from(*initiator_id),
+            i64::from(*last_verified),
+            verified,
+            i64::from(offset),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_forced_exits_by_initiator",
+            start.elapsed()
+        );
+        Ok(forced_exits)
+    }
+
+    /// Loads the latest executed deposits to the account, newest first.
+    pub async fn get_account_deposits(
+        &mut self,
+        address: Address,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageDeposit>> {
+        let start = Instant::now();
+        let deposits = sqlx::query_as!(
+            StorageDeposit,
+            r#"
+                SELECT
+                    '0x' || encode(eth_hash, 'hex') as "eth_hash!",
+                    operation->'priority_op'->>'from' as "from!",
+                    (operation->'priority_op'->>'token')::bigint as "token!",
+                    operation->'priority_op'->>'amount' as "amount!",
+                    block_number,
+                    eth_block,
+                    created_at
+                FROM executed_priority_operations
+                WHERE to_account = $1 AND operation->>'type' = 'Deposit'
+                ORDER BY priority_op_serialid DESC
+                LIMIT $2
+            "#,
+            address.as_bytes(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_deposits",
+            start.elapsed()
+        );
+        Ok(deposits)
+    }
+
+    /// Loads the latest rejected transactions alongside with the rejection reasons, newest first.
+    pub async fn load_recent_rejected_txs(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<BlockTransactionItem>> {
+        let start = Instant::now();
+        let rejected_txs = sqlx::query_as!(
+            BlockTransactionItem,
+            r#"
+                SELECT
+                    '0x' || encode(tx_hash, 'hex') as "tx_hash!",
+                    block_number,
+                    tx as op,
+                    block_index,
+                    success,
+                    fail_reason,
+                    created_at,
+                    batch_id
+                FROM executed_transactions
+                WHERE success = false
+                ORDER BY created_at DESC
+                LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.load_recent_rejected_txs",
+            start.elapsed()
+        );
+        Ok(rejected_txs)
+    }
+
+    /// Loads the transactions applied to the account which precede the anchor transaction
+    /// in the history, i.e. the ones older than it. Unlike `get_account_transactions_history_from`,
+    /// the anchor is identified by its hash. Returns `None` if the anchor transaction isn't executed.
+    pub async fn get_account_history_before(
+        &mut self,
+        address: &Address,
+        anchor_hash: TxHash,
+        limit: u64,
+        filter: &AccountHistoryFilter,
+    ) -> QueryResult<Option<Vec<TransactionsHistoryItem>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        // Priority operations are reported in the history with their L1 hashes. They may share
+        // the hash, same as in `get_tx_sequence_number` the latest one is used.
+        let anchor = sqlx::query!(
+            r#"
+                SELECT block_number as "block_number!", block_index as "block_index?" FROM (
+                    SELECT block_number, block_index, sequence_number FROM executed_transactions
+                    WHERE tx_hash = $1
+                    UNION ALL
+                    SELECT block_number, block_index, sequence_number FROM executed_priority_operations
+                    WHERE tx_hash = $1 OR eth_hash = $1
+                ) t
+                ORDER BY sequence_number DESC
+                LIMIT 1
+            "#,
+            anchor_hash.as_ref()
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+        // Rejected transactions don't have an index in block and aren't reported in the history.
+        let anchor = anchor.and_then(|record| {
+            record
+                .block_index
+                .map(|block_index| (record.block_number as u64, block_index as u64))
+        });
+
+        let tx_history = match anchor {
+            Some(anchor) => Some(
+                OperationsExtSchema(&mut transaction)
+                    .get_account_transactions_history_from(
+                        address,
+                        anchor,
+                        SearchDirection::Older,
+                        limit,
+                        filter,
+                    )
+                    .await?,
+            ),
+            None => None,
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_history_before",
+            start.elapsed()
+        );
+        Ok(tx_history)
+    }
+
+    /// Loads the transactions applied to any of the given accounts, ordered by the block number
+    /// and the index in block descending. If the cursor (block number and index in block) is set,
+    /// only the transactions older than it are loaded.
+    ///
+    /// Transactions related to several accounts of the list are reported once. Failed transactions
+    /// don't have an index in block, so they can't be used as the cursor; they are skipped
+    /// if `successful_only` is set.
+    pub async fn get_multi_account_history(
+        &mut self,
+        addresses: &[Address],
+        limit: u64,
+        cursor: Option<(u64, u64)>,
+        successful_only: bool,
+    ) -> QueryResult<Vec<TransactionsHistoryItem>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let (cursor_block, cursor_index) = match cursor {
+            Some((block_number, block_index)) => {
+                (Some(block_number as i64), Some(block_index as i32))
             }
+            None => (None, None),
+        };
+
+        // The query is the same as the one of `get_account_transactions_history_from`,
+        // except for filtering the transactions by the list of the accounts.
+        let mut tx_history = sqlx::query_as!(
+            TransactionsHistoryItem,
+            r#"
+            WITH aggr_exec AS (
+                SELECT 
+                   aggregate_operations.confirmed, 
+                   execute_aggregated_blocks_binding.block_number 
+               FROM aggregate_operations
+                   INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+               WHERE aggregate_operations.confirmed = true 
+            ), tx_hashes AS (
+                SELECT DISTINCT tx_hash FROM tx_filters
+                WHERE address = ANY($1)
+            ), transactions as (
+                select
+                    *
+                from (
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        tx,
+                        'sync-tx:' || encode(executed_transactions.tx_hash, 'hex') as hash,
+                        null as pq_id,
+                        null as eth_block,
+                        success,
+                        fail_reason,
+                        block_number,
+                        block_index,
+                        created_at,
+                        batch_id,
+                        eth_sign_data->'signature'->>'signature' as eth_signature,
+                        false as is_priority_op
+                    from tx_hashes
+                    inner join executed_transactions
+                        on tx_hashes.tx_hash = executed_transactions.tx_hash
+                    where
+                        (
+                            $2::bigint IS NULL
+                            or block_number < $2
+                            or (block_number = $2 and block_index < $3)
+                        )
+                        and (not $5 or success)
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        operation as tx,
+                        '0x' || encode(eth_hash, 'hex') as hash,
+                        priority_op_serialid as pq_id,
+                        eth_block,
+                        true as success,
+                        null as fail_reason,
+                        block_number,
+                        block_index,
+                        created_at,
+                        Null::bigint as batch_id,
+                        Null::text as eth_signature,
+                        true as is_priority_op
+                    from 
+                        executed_priority_operations
+                    where 
+                        (
+                            from_account = ANY($1)
+                            or
+                            to_account = ANY($1)
+                        )
+                        and
+                        (
+                            $2::bigint IS NULL
+                            or block_number < $2
+                            or (block_number = $2 and block_index < $3)
+                        )
+                    ) t
+                order by
+                    block_number desc, block_index desc
+                limit 
+                    $4
+            )
+            select
+                tx_id as "tx_id!",
+                hash as "hash?",
+                eth_block as "eth_block?",
+                pq_id as "pq_id?",
+                tx as "tx!",
+                success as "success?",
+                fail_reason as "fail_reason?",
+                true as "commited!",
+                coalesce(verified.confirmed, false) as "verified!",
+                created_at as "created_at!",
+                transactions.batch_id as "batch_id?",
+                'sync-tx:' || encode(txs_batches_hashes.batch_hash, 'hex') as "batch_hash?",
+                eth_signature as "eth_signature?",
+                is_priority_op as "is_priority_op!",
+                NULL::numeric as "usd_value_at_time?"
+            from transactions
+            LEFT JOIN txs_batches_hashes ON txs_batches_hashes.batch_id = transactions.batch_id
+            left join aggr_exec verified on
+                verified.block_number = transactions.block_number AND verified.confirmed = true
+            order by transactions.block_number desc, transactions.block_index desc
+            "#,
+            &addresses,
+            cursor_block,
+            cursor_index,
+            limit as i64,
+            successful_only
+        ).fetch_all(transaction.conn())
+        .await?;
+
+        if !tx_history.is_empty() {
+            let tokens = transaction.tokens_schema().load_tokens().await?;
+            replace_token_ids_with_symbols(&mut tx_history, &tokens);
         }
 
         transaction.commit().await?;
         metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_history_from",
+            "sql.chain.operations_ext.get_multi_account_history",
             start.elapsed()
         );
         Ok(tx_history)
@@ -1034,6 +1733,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         query.from.address,
                         address,
                         query.from.token,
+                        query.from.min_amount.as_ref(),
+                        query.from.max_amount.as_ref(),
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1048,6 +1749,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     .get_tx_seq_numbers_for_account(
                         query.from.address,
                         query.from.token,
+                        query.from.min_amount.as_ref(),
+                        query.from.max_amount.as_ref(),
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1112,11 +1815,14 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(txs)
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn get_executed_transactions_for_two_accounts(
         &mut self,
         address: Address,
         second_address: Address,
         token: Option<TokenId>,
+        min_amount: Option<&BigUint>,
+        max_amount: Option<&BigUint>,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
@@ -1139,12 +1845,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         } else {
             ""
         };
+        let amount_query = amount_range_condition(min_amount, max_amount, 6, 7);
 
         let query = format!(
             r#"
                 WITH tx_hashes AS (
                     SELECT DISTINCT tx_hash FROM tx_filters
-                    WHERE address = $1 {} 
+                    WHERE address = $1 {} {}
                     INTERSECT
                     SELECT DISTINCT tx_hash FROM tx_filters
                     WHERE address = $2 {}
@@ -1166,7 +1873,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 {}
                 
             "#,
-            token_query, token_query, query_direction
+            token_query, amount_query, token_query, query_direction
         );
 
         Ok(sqlx::query_as(&query)
@@ -1175,6 +1882,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(min_amount.cloned().map(biguint_to_big_decimal))
+            .bind(max_amount.cloned().map(biguint_to_big_decimal))
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1183,6 +1892,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         &mut self,
         address: Address,
         token: Option<TokenId>,
+        min_amount: Option<&BigUint>,
+        max_amount: Option<&BigUint>,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
@@ -1205,10 +1916,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         } else {
             ""
         };
+        let amount_query = amount_range_condition(min_amount, max_amount, 5, 6);
 
         let query = format!(
-            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
-            token_query, query_direction
+            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {} {}",
+            token_query, amount_query, query_direction
         );
 
         Ok(sqlx::query_as(&query)
@@ -1216,6 +1928,8 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(min_amount.cloned().map(biguint_to_big_decimal))
+            .bind(max_amount.cloned().map(biguint_to_big_decimal))
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1354,10 +2068,43 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         address: Address,
         token: Option<TokenId>,
         second_address: Option<Address>,
+        min_amount: Option<&BigUint>,
+        max_amount: Option<&BigUint>,
     ) -> QueryResult<u32> {
         let start = Instant::now();
 
-        let count = if let Some(second_address) = second_address {
+        let count = if min_amount.is_some() || max_amount.is_some() {
+            let second_address_query = if second_address.is_some() {
+                "INTERSECT
+                SELECT DISTINCT tx_hash FROM tx_filters
+                WHERE address = $4 AND ($2::boolean OR token = $3)"
+            } else {
+                ""
+            };
+            let query = format!(
+                r#"
+                WITH tx_hashes AS (
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $1 AND ($2::boolean OR token = $3) {}
+                    {}
+                )
+                SELECT COUNT(*) FROM tx_hashes
+                "#,
+                amount_range_condition(min_amount, max_amount, 5, 6),
+                second_address_query
+            );
+
+            let (count,): (i64,) = sqlx::query_as(&query)
+                .bind(address.as_bytes())
+                .bind(token.is_none())
+                .bind(token.unwrap_or_default().0 as i32)
+                .bind(second_address.unwrap_or_default().as_bytes().to_vec())
+                .bind(min_amount.cloned().map(biguint_to_big_decimal))
+                .bind(max_amount.cloned().map(biguint_to_big_decimal))
+                .fetch_one(self.0.conn())
+                .await?;
+            count
+        } else if let Some(second_address) = second_address {
             sqlx::query!(
                 r#"
                 WITH tx_hashes AS (
@@ -1571,6 +2318,46 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Loads the transaction at the given position within the batch it was submitted in.
+    /// Transactions in the batch are ordered the same way they were executed.
+    pub async fn get_batch_transaction(
+        &mut self,
+        batch_id: i64,
+        index: u32,
+    ) -> QueryResult<Option<BlockTransactionItem>> {
+        let start = Instant::now();
+
+        let tx = sqlx::query_as!(
+            BlockTransactionItem,
+            r#"
+                SELECT
+                    '0x' || encode(tx_hash, 'hex') as "tx_hash!",
+                    block_number,
+                    tx as op,
+                    block_index,
+                    success,
+                    fail_reason,
+                    created_at,
+                    batch_id
+                FROM executed_transactions
+                WHERE batch_id = $1
+                ORDER BY sequence_number ASC
+                OFFSET $2
+                LIMIT 1
+            "#,
+            batch_id,
+            i64::from(index)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_batch_transaction",
+            start.elapsed()
+        );
+        Ok(tx)
+    }
+
     pub async fn tx_data_for_web3(&mut self, hash: &[u8]) -> QueryResult<Option<Web3TxData>> {
         let start = Instant::now();
 
@@ -2035,3 +2822,42 @@ async fn tx_data_from_storage(
         complete_withdrawals_tx_hash,
     ))
 }
+
+/// Replaces the IDs of the fungible tokens in the transactions history items with the token symbols.
+fn replace_token_ids_with_symbols(
+    tx_history: &mut [TransactionsHistoryItem],
+    tokens: &HashMap<TokenId, Token>,
+) {
+    for tx_item in tx_history {
+        let tx_info = match tx_item.tx["type"].as_str().unwrap_or("NONE") {
+            "NONE" => {
+                vlog::warn!("Tx history item type not found, tx: {:?}", tx_item);
+                continue;
+            }
+            "Deposit" | "FullExit" => tx_item.tx.get_mut("priority_op"),
+            _ => Some(&mut tx_item.tx),
+        };
+
+        let tx_info = if let Some(tx_info) = tx_info {
+            tx_info
+        } else {
+            vlog::warn!("tx_info not found for tx: {:?}", tx_item);
+            continue;
+        };
+
+        if let Some(tok_val) = tx_info.get_mut("token") {
+            if let Some(token_id) = tok_val.as_u64() {
+                if token_id < params::MIN_NFT_TOKEN_ID as u64 {
+                    let token_id = TokenId(token_id as u32);
+                    let token_symbol = tokens
+                        .get(&token_id)
+                        .map(|t| t.symbol.clone())
+                        .unwrap_or_else(|| "UNKNOWN".to_string());
+                    *tok_val = serde_json::to_value(token_symbol).expect("json string to value");
+                } else {
+                    *tok_val = serde_json::to_value(token_id).expect("json string to value");
+                }
+            };
+        };
+    }
+}
@@ -6,7 +6,7 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
-use sqlx::FromRow;
+use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 // Local imports
 use crate::prover::records::ProverRun;
@@ -18,6 +18,17 @@ pub struct AccountCreatedAt {
     pub created_at: DateTime<Utc>,
 }
 
+/// Timestamps of the first and the last operations of the account,
+/// obtained from the [`account_activity_span`] query.
+///
+/// [`account_activity_span`]: super::OperationsExtSchema::account_activity_span()
+#[derive(Debug, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct AccountActivitySpan {
+    pub first_tx_at: Option<DateTime<Utc>>,
+    pub last_tx_at: Option<DateTime<Utc>>,
+    pub tx_count: i64,
+}
+
 /// A single entry from the raw response of the [`get_account_transactions_history`] query.
 ///
 /// [`get_account_transactions_history`]: super::OperationsExtSchema::get_account_transactions_history()
@@ -34,6 +45,33 @@ pub struct TransactionsHistoryItem {
     pub verified: bool,
     pub created_at: DateTime<Utc>,
     pub batch_id: Option<i64>,
+    /// Hash of the batch the transaction was sent in, `None` for the
+    /// transactions sent on their own and for priority operations.
+    pub batch_hash: Option<String>,
+    /// Hex-encoded Ethereum signature attached to the transaction.
+    ///
+    /// Only available for transactions that require an L1 signature,
+    /// `None` for priority operations and transactions without one.
+    pub eth_signature: Option<String>,
+    /// Whether the entry is a priority operation originated from L1.
+    pub is_priority_op: bool,
+    /// Value of the transaction amount in USD at the time of its block. Only loaded by
+    /// `get_account_transactions_history`, `None` for the rest of the queries.
+    #[serde(skip)]
+    pub usd_value_at_time: Option<BigDecimal>,
+}
+
+/// Raw response of the [`get_latest_change_pubkey`] query.
+///
+/// [`get_latest_change_pubkey`]: super::OperationsExtSchema::get_latest_change_pubkey()
+#[derive(Debug, FromRow, PartialEq)]
+pub struct StorageChangePubKeyTx {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub block_index: Option<i32>,
+    /// Public key hash set by the transaction, in the `sync:` prefixed format.
+    pub new_pk_hash: String,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Stored information resulted from executing the transaction.
@@ -55,6 +93,12 @@ pub struct PriorityOpReceiptResponse {
     pub committed: bool,
     pub verified: bool,
     pub prover_run: Option<ProverRun>,
+    /// Number of the L2 block containing the operation, once the block is verified.
+    pub verified_in_l2_block: Option<u32>,
+    /// Hash of the Ethereum transaction that verified the block containing the operation.
+    pub verify_eth_tx_hash: Option<String>,
+    /// Whether the verify Ethereum transaction has the required amount of L1 confirmations.
+    pub finalized: bool,
 }
 
 /// Stored executed operation (can be both L1 or L2)
@@ -204,3 +248,32 @@ pub struct SequenceNumberRecord {
     pub sequence_number: i64,
     pub is_priority: bool,
 }
+
+/// `ForcedExit` transaction initiated by the account, see `get_forced_exits_by_initiator`.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct StorageForcedExit {
+    pub tx_hash: String,
+    pub target: String,
+    pub token: i64,
+    /// Withdrawn amount, `None` for the rejected transactions.
+    pub amount: Option<String>,
+    pub block_number: i64,
+    pub success: bool,
+    /// Whether the block containing the transaction is verified.
+    pub verified: bool,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Executed deposit to the account, see `get_account_deposits`.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct StorageDeposit {
+    pub eth_hash: String,
+    pub from: String,
+    pub token: i64,
+    pub amount: String,
+    pub block_number: i64,
+    /// Ethereum block the deposit was submitted in.
+    pub eth_block: i64,
+    pub created_at: DateTime<Utc>,
+}
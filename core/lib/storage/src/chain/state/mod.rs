@@ -1,6 +1,7 @@
 // Built-in deps
 use std::{cmp, collections::HashMap, time::Instant};
 // External imports
+use futures_util::stream::{BoxStream, StreamExt, TryStreamExt};
 use num::BigInt;
 use sqlx::types::BigDecimal;
 // Workspace imports
@@ -478,6 +479,29 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         result
     }
 
+    /// Streams the non-zero balances of all the accounts in the verified state,
+    /// ordered by the account ID and the token ID.
+    ///
+    /// Rows are fetched from the database as the stream is polled, so unlike
+    /// `load_verified_state` this method never keeps the whole state in memory.
+    /// Use `StorageProcessor::start_snapshot_transaction` to stream the balances
+    /// of a single block.
+    pub fn verified_balances_stream(self) -> BoxStream<'a, QueryResult<StorageAccountBalance>> {
+        sqlx::query_as!(
+            StorageAccountBalance,
+            r#"
+            SELECT balances.account_id, accounts.address, balances.coin_id, balances.balance
+            FROM balances
+            INNER JOIN accounts ON accounts.id = balances.account_id
+            WHERE balances.balance > 0
+            ORDER BY balances.account_id, balances.coin_id
+            "#
+        )
+        .fetch(self.0.conn())
+        .map_err(anyhow::Error::from)
+        .boxed()
+    }
+
     /// Loads the verified account map state along with a block number
     /// to which this state applies.
     /// If the provided block number is `None`, then the latest committed
@@ -1,13 +1,29 @@
 use std::cmp::max;
 // Built-in deps
+use std::collections::BTreeMap;
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
 
 // Workspace imports
-use zksync_types::{BlockNumber, SequentialTxId};
+use zksync_types::{
+    prover::{ProverJobStatus, ProverJobType},
+    BlockNumber, SequentialTxId,
+};
 // Local imports
 use crate::{QueryResult, StorageProcessor};
 
+/// Usage of a single token for paying the transaction fees.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct StorageFeeTokenUsage {
+    pub token_id: i32,
+    pub symbol: String,
+    /// Amount of the successful transactions that paid fee in the token.
+    pub tx_count: i64,
+    pub total_fee: BigDecimal,
+}
+
 /// Auxiliary schema encapsulating the stats counting logic for the storage tables.
 #[derive(Debug)]
 pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
@@ -29,6 +45,197 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         Ok(count as u32)
     }
 
+    /// Returns the amounts of blocks after the given one that await for the proof generation
+    /// and that are being proven at the moment, respectively.
+    ///
+    /// Blocks which already have proofs are not counted.
+    pub async fn count_blocks_by_proof_status(
+        &mut self,
+        after_block: BlockNumber,
+    ) -> QueryResult<(u32, u32)> {
+        let start = Instant::now();
+        // Job statuses are ordered, so the maximum one defines the proof status of the block.
+        let counts = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE COALESCE(jobs.status, $2) = $2) AS "pending!",
+                COUNT(*) FILTER (WHERE jobs.status = $3) AS "proving!"
+            FROM blocks
+            LEFT JOIN LATERAL (
+                SELECT MAX(job_status) AS status FROM prover_job_queue
+                WHERE job_type = $4 AND blocks.number BETWEEN first_block AND last_block
+            ) jobs ON true
+            WHERE blocks.number > $1
+            "#,
+            i64::from(*after_block),
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number(),
+            ProverJobType::SingleProof.to_string(),
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.stats.count_blocks_by_proof_status",
+            start.elapsed()
+        );
+        Ok((counts.pending as u32, counts.proving as u32))
+    }
+
+    /// Returns the amounts of operations of each type executed in the given (inclusive) range of blocks.
+    ///
+    /// Both L2 transactions and priority operations are counted, rejected transactions are not.
+    pub async fn count_operations_by_type(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<BTreeMap<String, u64>> {
+        let start = Instant::now();
+        let counts = sqlx::query!(
+            r#"
+            SELECT op_type AS "op_type!", COUNT(*) AS "count!" FROM (
+                SELECT tx->>'type' AS op_type FROM executed_transactions
+                WHERE block_number BETWEEN $1 AND $2 AND success = true
+                UNION ALL
+                SELECT operation->>'type' AS op_type FROM executed_priority_operations
+                WHERE block_number BETWEEN $1 AND $2
+            ) operations
+            WHERE op_type IS NOT NULL
+            GROUP BY op_type
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| (row.op_type, row.count as u64))
+        .collect();
+
+        metrics::histogram!("sql.chain.stats.count_operations_by_type", start.elapsed());
+        Ok(counts)
+    }
+
+    /// Returns the amounts of priority operations received from L1 but not executed yet,
+    /// and of the ones among them still awaiting for the L1 confirmations, respectively.
+    pub async fn count_pending_priority_operations(&mut self) -> QueryResult<(u64, u64)> {
+        let start = Instant::now();
+        let counts = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "pending!",
+                COUNT(*) FILTER (WHERE confirmed = false) AS "unconfirmed!"
+            FROM mempool_priority_operations
+            "#
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.stats.count_pending_priority_operations",
+            start.elapsed()
+        );
+        Ok((counts.pending as u64, counts.unconfirmed as u64))
+    }
+
+    /// Returns the amounts of transactions that paid fees in each token and the sums of these fees
+    /// for the given (inclusive) range of blocks, ordered by the token ID.
+    ///
+    /// Only successful transactions with non-zero fee are counted, so e.g. the transactions
+    /// whose fee is paid by another transaction of the same batch are not. Fees are summed up
+    /// from the per-block sums stored in the `block_token_fees` table when the block is saved.
+    pub async fn load_fee_token_usage(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<StorageFeeTokenUsage>> {
+        let start = Instant::now();
+        let usage = sqlx::query_as!(
+            StorageFeeTokenUsage,
+            r#"
+            SELECT
+                block_token_fees.token_id AS "token_id!",
+                tokens.symbol AS "symbol!",
+                SUM(block_token_fees.tx_count)::bigint AS "tx_count!",
+                SUM(block_token_fees.total_fee) AS "total_fee!"
+            FROM block_token_fees
+            INNER JOIN tokens ON tokens.id = block_token_fees.token_id
+            WHERE block_token_fees.block_number BETWEEN $1 AND $2 AND block_token_fees.total_fee > 0
+            GROUP BY block_token_fees.token_id, tokens.symbol
+            ORDER BY block_token_fees.token_id
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.load_fee_token_usage", start.elapsed());
+        Ok(usage)
+    }
+
+    /// Returns the intervals (in seconds) between the timestamps of the consecutive blocks
+    /// created since the given Unix timestamp, ordered by the block number.
+    ///
+    /// The first block in the range has no preceding block within it, and thus is not counted.
+    pub async fn load_block_intervals(&mut self, since: i64) -> QueryResult<Vec<u64>> {
+        let start = Instant::now();
+        let intervals = sqlx::query!(
+            r#"
+            SELECT block_interval AS "block_interval!" FROM (
+                SELECT number, timestamp - LAG(timestamp) OVER (ORDER BY number) AS block_interval
+                FROM blocks
+                WHERE timestamp >= $1
+            ) intervals
+            WHERE block_interval IS NOT NULL
+            ORDER BY number
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        // Timestamps of the blocks are not guaranteed to be monotonic.
+        .map(|row| row.block_interval.max(0) as u64)
+        .collect();
+
+        metrics::histogram!("sql.chain.stats.load_block_intervals", start.elapsed());
+        Ok(intervals)
+    }
+
+    /// Returns the times (in seconds) between the confirmed commitment and the confirmed
+    /// execution of the blocks executed since the given time, ordered by the block number.
+    pub async fn load_proving_times(&mut self, since: DateTime<Utc>) -> QueryResult<Vec<u64>> {
+        let start = Instant::now();
+        let proving_times = sqlx::query!(
+            r#"
+            SELECT
+                EXTRACT(EPOCH FROM (verified.created_at - committed.created_at))::bigint
+                    AS "proving_time!"
+            FROM commit_aggregated_blocks_binding
+                INNER JOIN aggregate_operations committed
+                    ON committed.id = commit_aggregated_blocks_binding.op_id
+                INNER JOIN execute_aggregated_blocks_binding
+                    ON execute_aggregated_blocks_binding.block_number = commit_aggregated_blocks_binding.block_number
+                INNER JOIN aggregate_operations verified
+                    ON verified.id = execute_aggregated_blocks_binding.op_id
+            WHERE committed.confirmed = true
+                AND verified.confirmed = true
+                AND verified.created_at >= $1
+            ORDER BY commit_aggregated_blocks_binding.block_number
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| row.proving_time.max(0) as u64)
+        .collect();
+
+        metrics::histogram!("sql.chain.stats.load_proving_times", start.elapsed());
+        Ok(proving_times)
+    }
+
     /// Count total transactions after tx_id, and return count and max tx_id.
     /// It allows us to cache count of transactions and make these queries much faster
     pub async fn count_total_transactions(
@@ -104,6 +104,26 @@ impl ConnectionPool {
         Ok(StorageProcessor::from_pool(connection))
     }
 
+    /// Creates a `StorageProcessor` entity, waiting for a free connection no longer than
+    /// the provided timeout.
+    ///
+    /// Unlike `access_storage`, this method doesn't retry and returns an error right away,
+    /// so it's intended for the contexts which should fail fast under load (e.g. API handlers).
+    pub async fn access_storage_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> anyhow::Result<StorageProcessor<'_>> {
+        let start = Instant::now();
+        let connection = self
+            .pool
+            .timeout_get(&Timeouts::wait_millis(timeout.as_millis() as u64))
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to get connection to db: {}", err))?;
+        metrics::histogram!("sql.connection_acquire", start.elapsed());
+
+        Ok(StorageProcessor::from_pool(connection))
+    }
+
     async fn get_pooled_connection(&self) -> PooledConnection {
         let mut retry_count = 0;
 
@@ -11,7 +11,7 @@ use zksync_types::{
     event::{
         account::AccountStateChangeStatus, block::BlockStatus, transaction::TransactionStatus,
     },
-    BlockNumber, H256, U256,
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
 use self::records::{ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
@@ -580,6 +580,44 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the amount of gas used by the mined Ethereum transaction with the given hash.
+    pub async fn save_eth_tx_gas_used(&mut self, hash: &H256, gas_used: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
+        sqlx::query!(
+            "UPDATE eth_operations SET gas_used = $1 WHERE id = $2",
+            gas_used as i64,
+            eth_op_id
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.save_eth_tx_gas_used", start.elapsed());
+        Ok(())
+    }
+
+    /// Stores the sender of the mined Ethereum transaction with the given hash.
+    pub async fn save_eth_tx_sender(&mut self, hash: &H256, sender: Address) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
+        sqlx::query!(
+            "UPDATE eth_operations SET from_address = $1 WHERE id = $2",
+            sender.as_bytes(),
+            eth_op_id
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.save_eth_tx_sender", start.elapsed());
+        Ok(())
+    }
+
     /// Obtains the next nonce to use and updates the corresponding entry in the database
     /// for the next invocation.
     ///
@@ -170,6 +170,21 @@ impl<'a> StorageProcessor<'a> {
         Ok(processor)
     }
 
+    /// Starts a transaction in which all the queries observe the same snapshot of the database,
+    /// i.e. the changes committed after the first query of the transaction are not visible.
+    /// Must not be called within another transaction, since the isolation level can't be
+    /// changed for the nested ones.
+    pub async fn start_snapshot_transaction<'c: 'b, 'b>(
+        &'c mut self,
+    ) -> Result<StorageProcessor<'b>, anyhow::Error> {
+        let mut processor = self.start_transaction().await?;
+        sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(processor.conn())
+            .await?;
+
+        Ok(processor)
+    }
+
     /// Checks if the `StorageProcessor` is currently within database transaction.
     pub fn in_transaction(&self) -> bool {
         self.in_transaction
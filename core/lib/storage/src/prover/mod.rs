@@ -1,5 +1,5 @@
 // Built-in deps
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External imports
 use anyhow::format_err;
 // Workspace imports
@@ -170,6 +170,59 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Loads the latest single proof job which includes the given block.
+    pub async fn load_block_proof_job(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageProverJobQueue>> {
+        let start = Instant::now();
+        let job = sqlx::query_as!(
+            StorageProverJobQueue,
+            r#"
+                SELECT * FROM prover_job_queue
+                WHERE job_type = $1 AND $2 BETWEEN first_block AND last_block
+                ORDER BY id DESC
+                LIMIT 1
+            "#,
+            ProverJobType::SingleProof.to_string(),
+            i64::from(*block_number),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_block_proof_job");
+        Ok(job)
+    }
+
+    /// Returns the average time from adding a single proof job to the queue up to storing its proof,
+    /// calculated over the given amount of the latest completed jobs. `None` if there are no such jobs.
+    pub async fn load_average_proof_time(
+        &mut self,
+        jobs_count: u32,
+    ) -> QueryResult<Option<Duration>> {
+        let start = Instant::now();
+        let average_ms = sqlx::query!(
+            r#"
+                SELECT (AVG(EXTRACT(EPOCH FROM updated_at - created_at)) * 1000)::bigint AS average_ms
+                FROM (
+                    SELECT created_at, updated_at FROM prover_job_queue
+                    WHERE job_type = $1 AND job_status = $2
+                    ORDER BY id DESC
+                    LIMIT $3
+                ) jobs
+            "#,
+            ProverJobType::SingleProof.to_string(),
+            ProverJobStatus::Done.to_number(),
+            i64::from(jobs_count),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .average_ms;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_average_proof_time");
+        Ok(average_ms.map(|average_ms| Duration::from_millis(average_ms.max(0) as u64)))
+    }
+
     /// Stores the proof for a block.
     pub async fn store_proof(
         &mut self,
@@ -4,16 +4,22 @@ use num::{BigUint, Zero};
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
     aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    AccountUpdate, Address, BlockNumber, Nonce, PubKeyHash, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
+use super::operations_ext::{
+    commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::test_data::{gen_sample_block, gen_unique_aggregated_operation, generate_nft};
 use crate::tests::{create_rng, db_test, ACCOUNT_MUTEX};
 use crate::{
     chain::{
-        account::{records::EthAccountType, AccountSchema},
+        account::{
+            records::{Account2FA, AccountLastUpdates, EthAccountType},
+            AccountSchema,
+        },
         block::BlockSchema,
         state::StateSchema,
     },
@@ -41,6 +47,49 @@ async fn eth_account_type(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the 2FA setting is derived from the stored account type.
+#[db_test]
+async fn account_2fa(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Accounts without the stored type are owned ones, 2FA is required for them.
+    let two_fa = AccountSchema(&mut storage)
+        .get_account_2fa(AccountId(18))
+        .await?;
+    assert_eq!(two_fa, Account2FA::Required);
+
+    let pub_key_hash = PubKeyHash::from_hex("sync:0000000000000000000000000000000000000001")
+        .expect("Valid pub key hash");
+    AccountSchema(&mut storage)
+        .set_account_type(AccountId(18), EthAccountType::No2FA(Some(pub_key_hash)))
+        .await?;
+    let two_fa = AccountSchema(&mut storage)
+        .get_account_2fa(AccountId(18))
+        .await?;
+    assert_eq!(two_fa, Account2FA::DisabledForPubKeyHash(pub_key_hash));
+
+    AccountSchema(&mut storage)
+        .set_account_type(AccountId(19), EthAccountType::No2FA(None))
+        .await?;
+    let two_fa = AccountSchema(&mut storage)
+        .get_accounts_2fa(&[AccountId(17), AccountId(18), AccountId(19)])
+        .await?;
+    assert_eq!(two_fa.len(), 2);
+    assert_eq!(
+        two_fa[&AccountId(18)],
+        Account2FA::DisabledForPubKeyHash(pub_key_hash)
+    );
+    assert_eq!(two_fa[&AccountId(19)], Account2FA::Disabled);
+
+    AccountSchema(&mut storage)
+        .set_account_type(AccountId(18), EthAccountType::Owned)
+        .await?;
+    let two_fa = AccountSchema(&mut storage)
+        .get_account_2fa(AccountId(18))
+        .await?;
+    assert_eq!(two_fa, Account2FA::Required);
+
+    Ok(())
+}
+
 /// Checks that stored accounts can be obtained once they're committed.
 #[db_test]
 async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -137,7 +186,21 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                 .last_committed_state_for_account(*account_id)
                 .await?
                 .1,
-            Some(got_account)
+            Some(got_account.clone())
+        );
+
+        // The state at the block is built from the account diffs up to it.
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .account_state_at_block(*account_id, BlockNumber(1))
+                .await?,
+            Some((BlockNumber(1), got_account))
+        );
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .account_state_at_block(*account_id, BlockNumber(0))
+                .await?,
+            None
         );
 
         // Check account address and ID getters.
@@ -155,6 +218,34 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         );
     }
 
+    // Check the bulk lookups, the accounts that don't exist should be skipped.
+    let mut addresses: Vec<_> = accounts_block
+        .values()
+        .map(|account| account.address)
+        .collect();
+    addresses.push(Address::zero());
+    let account_ids = AccountSchema(&mut storage)
+        .account_ids_by_addresses(&addresses)
+        .await?;
+    assert_eq!(account_ids.len(), accounts_block.len());
+    for (account_id, account) in accounts_block.iter() {
+        assert_eq!(account_ids[&account.address], *account_id);
+    }
+
+    let mut account_ids: Vec<_> = accounts_block.keys().copied().collect();
+    account_ids.push(AccountId(u32::MAX));
+    let account_states = AccountSchema(&mut storage)
+        .account_states_by_ids(&account_ids)
+        .await?;
+    assert_eq!(account_states.len(), accounts_block.len());
+    for (account_id, account_state) in account_states {
+        let expected_state = AccountSchema(&mut storage)
+            .account_state_by_id(account_id)
+            .await?;
+        assert_eq!(account_state.committed, expected_state.committed);
+        assert!(account_state.verified.is_none());
+    }
+
     // Now add a proof, verify block and apply a state update.
     OperationsSchema(&mut storage)
         .store_aggregated_action(gen_unique_aggregated_operation(
@@ -167,6 +258,19 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         .apply_state_update(BlockNumber(1))
         .await?;
 
+    // The bulk lookup should also report the verified states, including the minted NFTs.
+    let account_states = AccountSchema(&mut storage)
+        .account_states_by_ids(&account_ids)
+        .await?;
+    assert_eq!(account_states.len(), accounts_block.len());
+    for (account_id, account_state) in account_states {
+        let expected_state = AccountSchema(&mut storage)
+            .account_state_by_id(account_id)
+            .await?;
+        assert_eq!(account_state.committed, expected_state.committed);
+        assert_eq!(account_state.verified, expected_state.verified);
+    }
+
     // After that all the accounts should have a verified state.
     for (account_id, account) in accounts_block {
         let (last_finalized, _) = AccountSchema(&mut storage)
@@ -534,3 +638,55 @@ async fn test_get_nft_owner(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Test `get_account_last_updates` method
+#[db_test]
+async fn account_last_updates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let address = setup.from_zksync_account.address;
+
+    let updates = storage
+        .chain()
+        .account_schema()
+        .get_account_last_updates(address)
+        .await?;
+    assert_eq!(updates, AccountLastUpdates::default());
+
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    let committed = storage
+        .chain()
+        .account_schema()
+        .get_account_last_updates(address)
+        .await?;
+    assert!(committed.committed_at.is_some());
+    assert!(committed.verified_at.is_none());
+    assert_eq!(committed.committed_block, Some(BlockNumber(2)));
+    assert_eq!(committed.verified_block, None);
+
+    verify_block(&mut storage, BlockNumber(1)).await?;
+    let verified = storage
+        .chain()
+        .account_schema()
+        .get_account_last_updates(address)
+        .await?;
+    assert_eq!(verified.committed_at, committed.committed_at);
+    assert!(verified.verified_at.is_some());
+    assert!(verified.verified_at <= verified.committed_at);
+    assert_eq!(verified.committed_block, Some(BlockNumber(2)));
+    assert_eq!(verified.verified_block, Some(BlockNumber(1)));
+
+    // The bulk method skips the accounts without operations.
+    let to_address = setup.to_zksync_account.address;
+    let mut bulk = storage
+        .chain()
+        .account_schema()
+        .get_accounts_last_updates(&[address, to_address, Address::random()])
+        .await?;
+    assert_eq!(bulk.remove(&address), Some(verified));
+    assert!(bulk.remove(&to_address).is_some());
+    assert!(bulk.is_empty());
+
+    Ok(())
+}
@@ -1,24 +1,34 @@
+// Built-in imports
+use std::collections::{HashMap, HashSet};
 // External imports
+use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_api_types::v02::pagination::{
     ApiEither, BlockAndTxHash, PaginationDirection, PaginationQuery,
 };
-use zksync_crypto::{convert::FeConvert, rand::XorShiftRng};
+use zksync_crypto::{convert::FeConvert, proof::EncodedSingleProof, rand::XorShiftRng};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    block::Block,
+    block::{Block, ExecutedOperations},
     helpers::apply_updates,
     tx::{ChangePubKeyType, TxHash},
-    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, TokenId,
+    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, TokenId, TokenLike,
+    TokenPrice,
 };
+use zksync_utils::biguint_to_big_decimal;
 // Local imports
 use super::operations_ext::{
     commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
 };
 use crate::{
     chain::{
-        block::{records::StorageBlockDetails, BlockSchema},
-        operations::OperationsSchema,
+        block::{
+            records::StorageBlockDetails, BlockDetailsExpansion, BlockRangeFilter, BlockSchema,
+        },
+        operations::{
+            records::{NewExecutedPriorityOperation, NewExecutedTransaction},
+            OperationsSchema,
+        },
         state::StateSchema,
     },
     ethereum::EthereumSchema,
@@ -172,7 +182,15 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
         let mut queries = vec![
             expected_block_detail.block_number.to_string(),
             hex::encode(&expected_block_detail.new_state_root),
+            format!(
+                "sync-bl:{}",
+                hex::encode(&expected_block_detail.new_state_root)
+            ),
             hex::encode(&expected_block_detail.commit_tx_hash.as_ref().unwrap()),
+            format!(
+                "0x{}",
+                hex::encode(&expected_block_detail.commit_tx_hash.as_ref().unwrap())
+            ),
         ];
         if let Some(verify_tx_hash) = expected_block_detail.verify_tx_hash.as_ref() {
             queries.push(hex::encode(&verify_tx_hash));
@@ -346,6 +364,25 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
         .await
         .is_none());
 
+    // Malformed hashes must not match anything (and must not cause a panic).
+    let malformed_queries = vec![
+        "sync-bl:".to_string(),
+        "sync-bl:zz".to_string(),
+        format!("sync-bl:{}", "z".repeat(64)),
+        format!("sync-bl:{}", "00".repeat(31)),
+        "0xdeadbeef".to_string(),
+    ];
+    for query in malformed_queries {
+        assert!(
+            BlockSchema(&mut storage)
+                .find_block_by_height_or_hash(query.clone())
+                .await
+                .is_none(),
+            "Block was found using malformed query {}",
+            query
+        );
+    }
+
     Ok(())
 }
 
@@ -561,6 +598,237 @@ async fn test_block_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         check_block_range_asc(&mut storage, max_block, limit, n_commited_block_number).await?;
     }
 
+    // Blocks in this test contain no operations, so only filters that admit
+    // empty blocks should match them.
+    let all_blocks = BlockSchema(&mut storage)
+        .load_block_range_desc(n_commited_block_number, 100)
+        .await?;
+    let filter = BlockRangeFilter {
+        max_tx_count: Some(0),
+        fee_token: Some(TokenId(0)),
+        max_total_fee: Some(0u32.into()),
+        ..Default::default()
+    };
+    let filtered_blocks = BlockSchema(&mut storage)
+        .load_filtered_block_range_desc(n_commited_block_number, 100, &filter)
+        .await?;
+    assert_eq!(filtered_blocks, all_blocks);
+
+    let all_blocks_asc = BlockSchema(&mut storage)
+        .load_block_range_asc(BlockNumber(1), 100)
+        .await?;
+    let filtered_blocks = BlockSchema(&mut storage)
+        .load_filtered_block_range_asc(BlockNumber(1), 100, &filter)
+        .await?;
+    assert_eq!(filtered_blocks, all_blocks_asc);
+
+    let filter = BlockRangeFilter {
+        min_tx_count: Some(1),
+        ..Default::default()
+    };
+    let filtered_blocks = BlockSchema(&mut storage)
+        .load_filtered_block_range_desc(n_commited_block_number, 100, &filter)
+        .await?;
+    assert!(filtered_blocks.is_empty());
+    let filtered_blocks = BlockSchema(&mut storage)
+        .load_filtered_block_range_asc(BlockNumber(1), 100, &filter)
+        .await?;
+    assert!(filtered_blocks.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the fees of the finished block are summed up per fee token,
+/// and that the blocks are only filtered by the fees paid in the requested token.
+#[db_test]
+async fn block_range_fee_filter(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    let mut block_fees = HashMap::new();
+    for op in &setup.blocks[0].block_transactions {
+        if let ExecutedOperations::Tx(tx) = op {
+            if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                *block_fees.entry(token_id).or_insert_with(BigUint::default) += fee;
+            }
+        }
+    }
+    let (fee_token, total_fee) = block_fees
+        .into_iter()
+        .find(|(_, fee)| *fee > BigUint::default())
+        .unwrap();
+    let unused_token = TokenId(u32::from(u16::MAX));
+
+    let filter = |fee_token, min_total_fee, max_total_fee| BlockRangeFilter {
+        fee_token: Some(fee_token),
+        min_total_fee,
+        max_total_fee,
+        ..Default::default()
+    };
+    let test_vector = vec![
+        (
+            fee_token,
+            Some(total_fee.clone()),
+            Some(total_fee.clone()),
+            1,
+        ),
+        (fee_token, Some(&total_fee + 1u32), None, 0),
+        (fee_token, None, Some(&total_fee - 1u32), 0),
+        (unused_token, Some(1u32.into()), None, 0),
+        (unused_token, None, Some(0u32.into()), 1),
+    ];
+    for (fee_token, min_total_fee, max_total_fee, expected_len) in test_vector {
+        let filter = filter(fee_token, min_total_fee, max_total_fee);
+        let blocks = BlockSchema(&mut storage)
+            .load_filtered_block_range_desc(BlockNumber(1), 10, &filter)
+            .await?;
+        assert_eq!(blocks.len(), expected_len, "{:?}", filter);
+        let blocks = BlockSchema(&mut storage)
+            .load_filtered_block_range_asc(BlockNumber(1), 10, &filter)
+            .await?;
+        assert_eq!(blocks.len(), expected_len, "{:?}", filter);
+    }
+
+    Ok(())
+}
+
+/// Checks that the block fees are converted with the token prices stored for the block,
+/// and that the fees are unknown until the prices of all the fee tokens are known.
+#[db_test]
+async fn block_fees(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    BlockSchema(&mut storage)
+        .store_block_fees(BlockNumber(1))
+        .await?;
+    let fees = BlockSchema(&mut storage)
+        .load_blocks_fees(&[BlockNumber(1), BlockNumber(2)])
+        .await?;
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[&BlockNumber(1)].total_fee_usd, None);
+    assert_eq!(fees[&BlockNumber(1)].total_fee_eth_wei, None);
+
+    // With the same price of all the tokens, the fee in wei is the fee
+    // in the smallest units of the token scaled to 18 decimals.
+    let mut expected_fee_usd = BigUint::default();
+    let mut expected_fee_wei = BigUint::default();
+    for op in &setup.blocks[0].block_transactions {
+        if let ExecutedOperations::Tx(tx) = op {
+            if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                let token = setup
+                    .tokens
+                    .iter()
+                    .find(|token| token.id == token_id)
+                    .unwrap();
+                let scale = BigUint::from(10u32).pow(18 - u32::from(token.decimals));
+                expected_fee_usd += &fee * 2u32 * &scale;
+                expected_fee_wei += fee * scale;
+            }
+        }
+    }
+    for token in &setup.tokens[0..3] {
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(2u32)),
+            last_updated: chrono::Utc::now(),
+        };
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(token.id, price)
+            .await?;
+    }
+    storage
+        .tokens_schema()
+        .store_block_token_prices(BlockNumber(1))
+        .await?;
+    BlockSchema(&mut storage)
+        .store_block_fees(BlockNumber(1))
+        .await?;
+
+    let fees = BlockSchema(&mut storage)
+        .load_blocks_fees(&[BlockNumber(1)])
+        .await?;
+    let expected_fee_usd = biguint_to_big_decimal(expected_fee_usd)
+        / biguint_to_big_decimal(BigUint::from(10u32).pow(18));
+    assert_eq!(fees[&BlockNumber(1)].total_fee_usd, Some(expected_fee_usd));
+    assert_eq!(
+        fees[&BlockNumber(1)].total_fee_eth_wei,
+        Some(biguint_to_big_decimal(expected_fee_wei))
+    );
+
+    Ok(())
+}
+
+/// Checks that the block statistics report the fees per token, since the fees
+/// paid in tokens with different decimals can't be summed up.
+#[db_test]
+async fn expanded_block_statistics(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    let mut expected_fees: HashMap<TokenId, BigUint> = HashMap::new();
+    let mut expected_tx_count = 0;
+    for op in &setup.blocks[0].block_transactions {
+        match op {
+            ExecutedOperations::Tx(tx) if tx.success => {
+                expected_tx_count += 1;
+                if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                    if fee > BigUint::default() {
+                        *expected_fees.entry(token_id).or_default() += fee;
+                    }
+                }
+            }
+            ExecutedOperations::PriorityOp(_) => expected_tx_count += 1,
+            _ => {}
+        }
+    }
+    // The block should contain fees paid in tokens with different decimals.
+    let fee_decimals: HashSet<_> = setup
+        .tokens
+        .iter()
+        .filter(|token| expected_fees.contains_key(&token.id))
+        .map(|token| token.decimals)
+        .collect();
+    assert!(fee_decimals.len() > 1);
+
+    let expansion = BlockDetailsExpansion {
+        statistics: true,
+        ..Default::default()
+    };
+    let block = BlockSchema(&mut storage)
+        .load_expanded_block_details(BlockNumber(1), &expansion)
+        .await?
+        .expect("block should be committed");
+    assert_eq!(block.tx_count, Some(expected_tx_count));
+
+    let mut expected_token_ids: Vec<_> = expected_fees.keys().copied().collect();
+    expected_token_ids.sort();
+    let expected_totals: Vec<_> = expected_token_ids
+        .iter()
+        .map(|token_id| expected_fees[token_id].to_string())
+        .collect();
+    let expected_token_ids: Vec<_> = expected_token_ids
+        .into_iter()
+        .map(|token_id| *token_id as i32)
+        .collect();
+    assert_eq!(block.fee_token_ids, Some(expected_token_ids));
+    assert_eq!(block.fee_token_totals, Some(expected_totals));
+
+    // Without the expansion the statistics aren't loaded.
+    let block = BlockSchema(&mut storage)
+        .load_expanded_block_details(BlockNumber(1), &BlockDetailsExpansion::default())
+        .await?
+        .unwrap();
+    assert_eq!(block.tx_count, None);
+    assert_eq!(block.fee_token_ids, None);
+    assert_eq!(block.fee_token_totals, None);
+
     Ok(())
 }
 
@@ -1292,6 +1560,30 @@ async fn test_get_block_number_by_hash(mut storage: StorageProcessor<'_>) -> Que
     Ok(())
 }
 
+/// Checks that `load_state_roots` returns the root hashes of the existing blocks only.
+#[db_test]
+async fn test_load_state_roots(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut expected_roots = HashMap::new();
+    for block_number in 1..=2 {
+        let block = gen_sample_block(BlockNumber(block_number), BLOCK_SIZE_CHUNKS, Vec::new());
+        expected_roots.insert(block.block_number, block.new_root_hash.to_bytes());
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(block)
+            .await?;
+    }
+
+    let state_roots = storage
+        .chain()
+        .block_schema()
+        .load_state_roots(&[BlockNumber(1), BlockNumber(2), BlockNumber(3)])
+        .await?;
+    assert_eq!(state_roots, expected_roots);
+
+    Ok(())
+}
+
 /// Check that `get_block_transactions_hashes` works correctly
 #[db_test]
 async fn test_get_block_transactions_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1393,3 +1685,295 @@ async fn test_incomplete_block_logic(mut storage: StorageProcessor<'_>) -> Query
 
     Ok(())
 }
+
+/// Checks that `get_last_block_before_timestamp` resolves the timestamps into the latest blocks
+/// created before them, taking the upper bound of the block number into account.
+#[db_test]
+async fn last_block_before_timestamp(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for (block_number, timestamp) in [(1, 100), (2, 110), (3, 140)] {
+        let mut block = gen_sample_block(BlockNumber(block_number), BLOCK_SIZE_CHUNKS, vec![]);
+        block.timestamp = timestamp;
+        BlockSchema(&mut storage).save_full_block(block).await?;
+    }
+
+    let cases = [
+        (99, BlockNumber(3), None),
+        (100, BlockNumber(3), Some(BlockNumber(1))),
+        (139, BlockNumber(3), Some(BlockNumber(2))),
+        (1000, BlockNumber(3), Some(BlockNumber(3))),
+        (1000, BlockNumber(2), Some(BlockNumber(2))),
+        (1000, BlockNumber(0), None),
+    ];
+    for (timestamp, max_block, expected) in cases {
+        let block_number = BlockSchema(&mut storage)
+            .get_last_block_before_timestamp(timestamp, max_block)
+            .await?;
+        assert_eq!(block_number, expected, "timestamp: {}", timestamp);
+    }
+
+    Ok(())
+}
+
+/// Checks the lifecycle of the exit proof requests: a request stays pending until the proof
+/// is stored, and a request for the later block replaces the stored proof.
+#[db_test]
+async fn exit_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (account_id, token_id) = (AccountId(1), TokenId(0));
+    let proof = EncodedSingleProof::default();
+
+    assert!(BlockSchema(&mut storage)
+        .get_exit_proof(account_id, token_id)
+        .await?
+        .is_none());
+    assert!(
+        BlockSchema(&mut storage)
+            .request_exit_proof(account_id, token_id, BlockNumber(1))
+            .await?
+    );
+    // Repeated requests are no-op.
+    assert!(
+        !BlockSchema(&mut storage)
+            .request_exit_proof(account_id, token_id, BlockNumber(1))
+            .await?
+    );
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .count_pending_exit_proofs()
+            .await?,
+        1
+    );
+    let pending = BlockSchema(&mut storage)
+        .get_pending_exit_proof()
+        .await?
+        .unwrap();
+    assert_eq!(
+        (pending.account_id, pending.token_id, pending.block_number),
+        (1, 0, 1)
+    );
+    assert!(pending.proof.is_none());
+
+    BlockSchema(&mut storage)
+        .store_exit_proof(account_id, token_id, BlockNumber(1), &proof, 100u32.into())
+        .await?;
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .count_pending_exit_proofs()
+            .await?,
+        0
+    );
+    let stored = BlockSchema(&mut storage)
+        .get_exit_proof(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.proof, Some(serde_json::to_value(&proof).unwrap()));
+    assert_eq!(stored.amount, Some(100u32.into()));
+
+    // Proof for the previous block is discarded once the later one is requested.
+    assert!(
+        BlockSchema(&mut storage)
+            .request_exit_proof(account_id, token_id, BlockNumber(2))
+            .await?
+    );
+    BlockSchema(&mut storage)
+        .store_exit_proof(account_id, token_id, BlockNumber(1), &proof, 100u32.into())
+        .await?;
+    let stored = BlockSchema(&mut storage)
+        .get_exit_proof(account_id, token_id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.block_number, 2);
+    assert!(stored.proof.is_none());
+
+    Ok(())
+}
+
+/// Checks that `load_blocks_fees_by_token` sums the fees of the block transactions per fee token.
+#[db_test]
+async fn blocks_fees_by_token(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    let mut expected_fees = HashMap::new();
+    for op in &setup.blocks[0].block_transactions {
+        if let ExecutedOperations::Tx(tx) = op {
+            if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                *expected_fees
+                    .entry(*token_id as i32)
+                    .or_insert_with(BigUint::default) += fee;
+            }
+        }
+    }
+    let expected_fees: HashMap<_, _> = expected_fees
+        .into_iter()
+        .map(|(token_id, fee)| (token_id, biguint_to_big_decimal(fee)))
+        .collect();
+
+    let fees = BlockSchema(&mut storage)
+        .load_blocks_fees_by_token(&[BlockNumber(1), BlockNumber(2)])
+        .await?;
+    assert!(fees.iter().all(|fee| fee.block_number == 1));
+    let fees: HashMap<_, _> = fees
+        .into_iter()
+        .map(|fee| (fee.token_id, fee.total_fee))
+        .collect();
+    assert_eq!(fees, expected_fees);
+
+    let fees = BlockSchema(&mut storage)
+        .load_blocks_fees_by_token(&[BlockNumber(2)])
+        .await?;
+    assert!(fees.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `get_block_transactions` filters the block operations by type.
+#[db_test]
+async fn block_transactions_by_type(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let all_txs = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(1), None)
+        .await?;
+    assert_eq!(all_txs.len(), setup.blocks[0].block_transactions.len());
+
+    let deposits = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(1), Some("deposit"))
+        .await?;
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(deposits[0].op["type"], "Deposit");
+
+    // Type is compared case-insensitively.
+    let full_exits = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(1), Some("FULLEXIT"))
+        .await?;
+    assert_eq!(full_exits.len(), 1);
+    assert_eq!(full_exits[0].op["type"], "FullExit");
+
+    let unknown = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(1), Some("unknown"))
+        .await?;
+    assert!(unknown.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `load_blocks_tx_counts` counts the successful operations of the requested blocks.
+#[db_test]
+async fn blocks_tx_counts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let expected_counts: HashMap<_, _> = setup
+        .blocks
+        .iter()
+        .map(|block| {
+            let count = block
+                .block_transactions
+                .iter()
+                .filter(|op| match op {
+                    ExecutedOperations::Tx(tx) => tx.success,
+                    ExecutedOperations::PriorityOp(_) => true,
+                })
+                .count();
+            (block.block_number, count as u32)
+        })
+        .collect();
+
+    let counts = BlockSchema(&mut storage)
+        .load_blocks_tx_counts(&[BlockNumber(1), BlockNumber(2), BlockNumber(3)])
+        .await?;
+    assert_eq!(counts, expected_counts);
+
+    let counts = BlockSchema(&mut storage)
+        .load_blocks_tx_counts(&[BlockNumber(3)])
+        .await?;
+    assert!(counts.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `get_rejected_txs` only returns the rejected transactions of the block.
+#[db_test]
+async fn block_rejected_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let rejected_txs = BlockSchema(&mut storage)
+        .get_rejected_txs(BlockNumber(1))
+        .await?;
+    assert!(rejected_txs.is_empty());
+
+    // The transfer with the index 2 is the rejected one.
+    let rejected_txs = BlockSchema(&mut storage)
+        .get_rejected_txs(BlockNumber(2))
+        .await?;
+    assert_eq!(rejected_txs.len(), 1);
+    assert_eq!(
+        rejected_txs[0].tx_hash,
+        format!("0x{}", hex::encode(setup.get_tx_hash(1, 2).as_ref()))
+    );
+    assert_eq!(rejected_txs[0].block_index, None);
+    assert!(!rejected_txs[0].success);
+
+    Ok(())
+}
+
+/// Checks that `load_blocks_unique_senders` counts the distinct senders of both L1 and L2
+/// operations and ignores the failed transactions.
+#[db_test]
+async fn blocks_unique_senders(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let mut expected_senders = HashMap::new();
+    for block in &setup.blocks {
+        let mut senders = HashSet::new();
+        for op in &block.block_transactions {
+            let sender = match op {
+                ExecutedOperations::Tx(tx) if tx.success => {
+                    NewExecutedTransaction::prepare_stored_tx(
+                        tx.as_ref().clone(),
+                        block.block_number,
+                        &mut storage,
+                    )
+                    .await?
+                    .from_account
+                }
+                ExecutedOperations::Tx(_) => continue,
+                ExecutedOperations::PriorityOp(op) => {
+                    NewExecutedPriorityOperation::prepare_stored_priority_op(
+                        op.as_ref().clone(),
+                        block.block_number,
+                    )
+                    .from_account
+                }
+            };
+            senders.insert(sender);
+        }
+        expected_senders.insert(block.block_number, senders.len() as u32);
+    }
+    // Several operations of the block are sent by the same account.
+    assert!(expected_senders[&BlockNumber(1)] < setup.blocks[0].block_transactions.len() as u32);
+
+    let senders = BlockSchema(&mut storage)
+        .load_blocks_unique_senders(&[BlockNumber(1), BlockNumber(2), BlockNumber(3)])
+        .await?;
+    assert_eq!(senders, expected_senders);
+
+    let senders = BlockSchema(&mut storage)
+        .load_blocks_unique_senders(&[BlockNumber(3)])
+        .await?;
+    assert!(senders.is_empty());
+
+    Ok(())
+}
@@ -165,6 +165,27 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the pending transactions are loaded in the order they were received.
+#[db_test]
+async fn load_pending_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(5);
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let pending_txs = MempoolSchema(&mut storage).load_pending_txs(3).await?;
+    let expected_hashes: Vec<_> = txs.iter().take(3).map(|tx| tx.hash()).collect();
+    let hashes: Vec<_> = pending_txs.iter().map(|tx| tx.hash()).collect();
+    assert_eq!(hashes, expected_hashes);
+
+    let pending_txs = MempoolSchema(&mut storage).load_pending_txs(10).await?;
+    assert_eq!(pending_txs.len(), txs.len());
+    let count = MempoolSchema(&mut storage).get_pending_txs_count().await?;
+    assert_eq!(count as usize, txs.len());
+
+    Ok(())
+}
+
 /// Checks the save&load routine for mempool schema.
 #[db_test]
 async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -512,3 +533,89 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the priority operations awaiting for the execution can be found by the serial ID.
+#[db_test]
+async fn get_pending_operation_by_serial_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let priority_op = PriorityOp {
+        serial_id: 5,
+        data: ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(0),
+            eth_address: Address::random(),
+            token: TokenId(0),
+            is_legacy: false,
+        }),
+        deadline_block: 0,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: None,
+    };
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op.clone()], false)
+        .await?;
+
+    let loaded = MempoolSchema(&mut storage)
+        .get_pending_operation_by_serial_id(5)
+        .await?
+        .expect("Priority operation should be found");
+    assert_eq!(loaded.eth_hash, priority_op.eth_hash);
+    assert_eq!(loaded.eth_block, priority_op.eth_block);
+    assert!(MempoolSchema(&mut storage)
+        .get_pending_operation_by_serial_id(6)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that `count_pending_priority_operations` counts the priority operations stored in the mempool.
+#[db_test]
+async fn pending_priority_operations_count(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let priority_ops: Vec<_> = (0..3)
+        .map(|serial_id| PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::FullExit(FullExit {
+                account_id: AccountId(0),
+                eth_address: Address::random(),
+                token: TokenId(0),
+                is_legacy: false,
+            }),
+            deadline_block: 0,
+            eth_hash: H256::random(),
+            eth_block: serial_id,
+            eth_block_index: None,
+        })
+        .collect();
+
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_pending_priority_operations()
+        .await?;
+    assert_eq!(counts, (0, 0));
+
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&priority_ops[..2], true)
+        .await?;
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&priority_ops[2..], false)
+        .await?;
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_pending_priority_operations()
+        .await?;
+    assert_eq!(counts, (3, 1));
+
+    MempoolSchema(&mut storage)
+        .remove_priority_ops_from_mempool(&[0])
+        .await?;
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_pending_priority_operations()
+        .await?;
+    assert_eq!(counts, (2, 1));
+
+    Ok(())
+}
@@ -206,7 +206,7 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
 
     // Get the block transactions and check if there are exactly 2 txs.
     let block_txs = BlockSchema(&mut storage)
-        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32), None)
         .await?;
 
     assert_eq!(block_txs.len(), 2);
@@ -266,7 +266,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     // Get the block transactions and check if there is exactly 1 tx (failed tx not copied but replaced).
     let block_txs = BlockSchema(&mut storage)
-        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32), None)
         .await?;
     assert_eq!(block_txs.len(), 1);
 
@@ -286,7 +286,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     // ...and there still must be one operation.
     let block_txs = BlockSchema(&mut storage)
-        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32), None)
         .await?;
     assert_eq!(block_txs.len(), 1);
 
@@ -369,7 +369,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, None, None)
         .await?;
     assert_eq!(count, 4);
     assert_eq!(count_tx_filters, 4);
@@ -388,7 +388,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, None, None)
         .await?;
     assert_eq!(count, 2);
     assert_eq!(count_tx_filters, 2);
@@ -406,7 +406,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, None, None)
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
@@ -419,7 +419,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, None, None)
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
@@ -514,12 +514,12 @@ async fn test_remove_executed_priority_operations(
 
     // Check that priority operation from the 3rd block is present and from the 4th is not.
     let block3_txs = BlockSchema(&mut storage)
-        .get_block_transactions(BlockNumber(3))
+        .get_block_transactions(BlockNumber(3), None)
         .await?;
     assert!(!block3_txs.is_empty());
 
     let block4_txs = BlockSchema(&mut storage)
-        .get_block_transactions(BlockNumber(4))
+        .get_block_transactions(BlockNumber(4), None)
         .await?;
     assert!(block4_txs.is_empty());
 
@@ -1,6 +1,12 @@
 // Built-in imports
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
 // External imports
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+use sqlx::types::BigDecimal;
 // Workspace imports
 use zksync_api_types::v02::{
     pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
@@ -11,14 +17,15 @@ use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
     tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    AccountId, AccountUpdate, Address, BlockNumber, ExecutedOperations, Nonce, TokenPrice,
+    ZkSyncOp, H256,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
 use crate::{
     chain::block::BlockSchema,
     chain::operations::OperationsSchema,
-    chain::operations_ext::SearchDirection,
+    chain::operations_ext::{AccountHistoryFilter, ForcedExitsFilter, SearchDirection},
     test_data::{
         dummy_ethereum_tx_hash, gen_sample_block, gen_unique_aggregated_operation,
         BLOCK_SIZE_CHUNKS,
@@ -231,13 +238,24 @@ async fn get_account_transactions_history(mut storage: StorageProcessor<'_>) ->
     let from_history = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_history(&setup.from_zksync_account.address, 0, 10)
+        .get_account_transactions_history(
+            &setup.from_zksync_account.address,
+            0,
+            10,
+            &Default::default(),
+        )
         .await?;
 
     for tx in &from_history {
         let tx_type: &str = tx.tx["type"].as_str().expect("no tx_type");
 
         assert!(tx.hash.is_some());
+        assert_eq!(
+            tx.is_priority_op,
+            matches!(tx_type, "Deposit" | "FullExit"),
+            "Unexpected priority flag for {}",
+            tx_type
+        );
 
         if let Some((from, to, token, amount)) = expected_behavior.get(tx_type) {
             let tx_info = match tx_type {
@@ -259,7 +277,12 @@ async fn get_account_transactions_history(mut storage: StorageProcessor<'_>) ->
     let to_history = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_history(&setup.to_zksync_account.address, 0, 10)
+        .get_account_transactions_history(
+            &setup.to_zksync_account.address,
+            0,
+            10,
+            &Default::default(),
+        )
         .await?;
 
     assert_eq!(from_history.len(), 10);
@@ -313,12 +336,18 @@ async fn get_account_transactions_history_from(
                 &setup.from_zksync_account.address,
                 offset_from,
                 limit_from,
+                &Default::default(),
             )
             .await?;
         let expected_to_history = storage
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_history(&setup.to_zksync_account.address, offset_to, limit_to)
+            .get_account_transactions_history(
+                &setup.to_zksync_account.address,
+                offset_to,
+                limit_to,
+                &Default::default(),
+            )
             .await?;
 
         let from_history = storage
@@ -329,6 +358,7 @@ async fn get_account_transactions_history_from(
                 (block_id, tx_id),
                 direction,
                 limit_from,
+                &Default::default(),
             )
             .await?;
         let to_history = storage
@@ -339,6 +369,7 @@ async fn get_account_transactions_history_from(
                 (block_id, tx_id),
                 direction,
                 limit_to,
+                &Default::default(),
             )
             .await?;
 
@@ -359,6 +390,446 @@ async fn get_account_transactions_history_from(
     Ok(())
 }
 
+/// Checks that the account history is filtered by the token and the amount.
+#[db_test]
+async fn get_filtered_account_transactions_history(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    // Swaps of the token have no amount of their own, so only the transfers match.
+    let filter = AccountHistoryFilter {
+        token: Some(setup.tokens[1].id),
+        min_amount: Some(setup.amount.clone()),
+        max_amount: Some(setup.amount.clone()),
+        dust_threshold_usd: None,
+    };
+    let expected_hashes: Vec<_> = [(1, 2), (1, 1), (0, 2), (0, 1)]
+        .iter()
+        .map(|&(block, index)| Some(setup.get_tx_hash(block, index).to_string()))
+        .collect();
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &filter)
+        .await?;
+    let hashes: Vec<_> = history.into_iter().map(|tx| tx.hash).collect();
+    assert_eq!(hashes, expected_hashes);
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_from(
+            &address,
+            (3, 0),
+            SearchDirection::Older,
+            100,
+            &filter,
+        )
+        .await?;
+    let hashes: Vec<_> = history.into_iter().map(|tx| tx.hash).collect();
+    assert_eq!(hashes, expected_hashes);
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_newer_than(&address, None, 100, &filter)
+        .await?;
+    let hashes: Vec<_> = history.into_iter().rev().map(|tx| tx.hash).collect();
+    assert_eq!(hashes, expected_hashes);
+
+    let filter = AccountHistoryFilter {
+        min_amount: Some(&setup.amount + 1u32),
+        ..filter
+    };
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &filter)
+        .await?;
+    assert!(history.is_empty());
+
+    Ok(())
+}
+
+/// Loads the hashes of the account history in all the ways, checking that they match.
+async fn load_history_hashes(
+    storage: &mut StorageProcessor<'_>,
+    address: &Address,
+    filter: &AccountHistoryFilter,
+) -> QueryResult<HashSet<Option<String>>> {
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(address, 0, 100, filter)
+        .await?;
+    let from_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_from(address, (2, 0), SearchDirection::Older, 100, filter)
+        .await?;
+    let newer_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_newer_than(address, None, 100, filter)
+        .await?;
+
+    let hashes: HashSet<_> = history.into_iter().map(|tx| tx.hash).collect();
+    let from_hashes: HashSet<_> = from_history.into_iter().map(|tx| tx.hash).collect();
+    let newer_hashes: HashSet<_> = newer_history.into_iter().map(|tx| tx.hash).collect();
+    assert_eq!(hashes, from_hashes);
+    assert_eq!(hashes, newer_hashes);
+    Ok(hashes)
+}
+
+/// Checks that the transfers worth less than the dust threshold are excluded from the history.
+#[db_test]
+async fn get_account_transactions_history_without_dust(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let transfer_hashes: HashSet<_> = [(0, 1), (0, 2)]
+        .iter()
+        .map(|&(block, index)| Some(setup.get_tx_hash(block, index).to_string()))
+        .collect();
+    let full_history = load_history_hashes(&mut storage, &address, &Default::default()).await?;
+    let with_threshold = |threshold: u32| AccountHistoryFilter {
+        dust_threshold_usd: Some(Ratio::from_integer(threshold.into())),
+        ..Default::default()
+    };
+
+    // Transfers of the tokens without a price are kept.
+    let history = load_history_hashes(&mut storage, &address, &with_threshold(1)).await?;
+    assert_eq!(history, full_history);
+
+    // The transferred amount is worth exactly one dollar.
+    let token_id = setup.tokens[1].id;
+    let unit_price = BigUint::from(10u32).pow(u32::from(setup.tokens[1].decimals));
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(
+            token_id,
+            TokenPrice {
+                usd_price: Ratio::from_integer(unit_price),
+                last_updated: Utc::now(),
+            },
+        )
+        .await?;
+    let history = load_history_hashes(&mut storage, &address, &with_threshold(1)).await?;
+    assert_eq!(history, full_history);
+
+    let expected_history: HashSet<_> = full_history.difference(&transfer_hashes).cloned().collect();
+    let history = load_history_hashes(&mut storage, &address, &with_threshold(2)).await?;
+    assert_eq!(history, expected_history);
+
+    Ok(())
+}
+
+/// Checks that `get_account_transactions_history` reports the values of the transactions
+/// in USD by the token prices stored at the time of their blocks.
+#[db_test]
+async fn get_account_transactions_history_usd_values(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // The transferred amount is worth exactly one dollar.
+    let token_id = setup.tokens[1].id;
+    let unit_price = BigUint::from(10u32).pow(u32::from(setup.tokens[1].decimals));
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(
+            token_id,
+            TokenPrice {
+                usd_price: Ratio::from_integer(unit_price),
+                last_updated: Utc::now(),
+            },
+        )
+        .await?;
+    storage
+        .tokens_schema()
+        .store_block_token_prices(BlockNumber(1))
+        .await?;
+
+    let address = setup.from_zksync_account.address;
+    let transfer_hashes: HashSet<_> = [(0, 1), (0, 2)]
+        .iter()
+        .map(|&(block, index)| Some(setup.get_tx_hash(block, index).to_string()))
+        .collect();
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &Default::default())
+        .await?;
+    for tx in history {
+        // The rest of the tokens have no price.
+        let expected_value = if transfer_hashes.contains(&tx.hash) {
+            Some(BigDecimal::from(1))
+        } else {
+            None
+        };
+        assert_eq!(tx.usd_value_at_time, expected_value, "{:?}", tx.hash);
+    }
+
+    Ok(())
+}
+
+/// Checks that the whole account history can be loaded page by page with
+/// the `get_account_transactions_history_newer_than` method.
+#[db_test]
+async fn get_account_transactions_history_newer_than(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let mut expected_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &Default::default())
+        .await?;
+    expected_history.reverse();
+
+    let mut history = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_newer_than(&address, cursor, 3, &Default::default())
+            .await?;
+        let last_tx = match page.last() {
+            Some(last_tx) => last_tx,
+            None => break,
+        };
+        let parts: Vec<u64> = last_tx
+            .tx_id
+            .split(',')
+            .map(|part| part.parse().unwrap())
+            .collect();
+        cursor = Some((parts[0], parts[1]));
+        assert!(page.len() <= 3);
+        history.extend(page);
+    }
+    assert_eq!(history, expected_history);
+
+    Ok(())
+}
+
+/// Checks that the rejected transactions, which have no position in the block,
+/// are skipped by `get_account_transactions_history_newer_than` and don't break the paging.
+#[db_test]
+async fn get_account_transactions_history_newer_than_with_rejected_tx(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block_with_rejected_op(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let mut expected_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &Default::default())
+        .await?;
+    expected_history.retain(|tx| tx.success != Some(false));
+    expected_history.reverse();
+
+    // The rejected transfer follows the second transaction of the first block,
+    // so it's right on the boundary of the pages.
+    let mut history = Vec::new();
+    let mut cursor = None;
+    for _ in 0..expected_history.len() {
+        let page = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_newer_than(&address, cursor, 2, &Default::default())
+            .await?;
+        let last_tx = match page.last() {
+            Some(last_tx) => last_tx,
+            None => break,
+        };
+        let parts: Vec<u64> = last_tx
+            .tx_id
+            .split(',')
+            .map(|part| part.parse().unwrap())
+            .collect();
+        assert_eq!(parts.len(), 2, "{}", last_tx.tx_id);
+        cursor = Some((parts[0], parts[1]));
+        history.extend(page);
+    }
+    assert_eq!(history, expected_history);
+
+    Ok(())
+}
+
+/// Checks that `get_account_history_before` loads the transactions preceding the anchor one.
+#[db_test]
+async fn get_account_history_before(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100, &Default::default())
+        .await?;
+
+    // L1 hashes of the priority operations in the test data aren't unique, so only L2 transactions
+    // are used as anchors.
+    let anchors: Vec<_> = (0..history.len())
+        .filter(|&idx| !history[idx].is_priority_op)
+        .collect();
+    for &anchor_idx in &[
+        anchors[0],
+        anchors[anchors.len() / 2],
+        anchors[anchors.len() - 1],
+    ] {
+        let anchor_hash = TxHash::from_str(history[anchor_idx].hash.as_ref().unwrap()).unwrap();
+        let page = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_history_before(&address, anchor_hash, 3, &Default::default())
+            .await?
+            .expect("Anchor transaction is executed");
+        let expected_len = std::cmp::min(3, history.len() - anchor_idx - 1);
+        assert_eq!(
+            page,
+            history[anchor_idx + 1..anchor_idx + 1 + expected_len],
+            "anchor index: {}",
+            anchor_idx
+        );
+    }
+
+    let unknown_anchor = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_history_before(&address, TxHash::default(), 3, &Default::default())
+        .await?;
+    assert!(unknown_anchor.is_none());
+
+    Ok(())
+}
+
+/// Checks that `get_multi_account_history` merges the histories of the accounts
+/// and paginates them with the cursor.
+#[db_test]
+async fn get_multi_account_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let from_address = setup.from_zksync_account.address;
+    let to_address = setup.to_zksync_account.address;
+    let from_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&from_address, 0, 100, &Default::default())
+        .await?;
+    let to_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&to_address, 0, 100, &Default::default())
+        .await?;
+
+    // History of a single account is the same as the one loaded by the account-specific method.
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_multi_account_history(&[from_address, Address::random()], 100, None, false)
+        .await?;
+    assert_eq!(history, from_history);
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_multi_account_history(&[from_address, to_address], 100, None, false)
+        .await?;
+    let mut expected_ids: Vec<_> = from_history
+        .iter()
+        .chain(&to_history)
+        .map(|item| item.tx_id.clone())
+        .collect();
+    expected_ids.sort_by_key(|tx_id| {
+        let (block_number, block_index) = tx_id.split_once(',').unwrap();
+        let block_number: u64 = block_number.parse().unwrap();
+        let block_index: u64 = block_index.parse().unwrap();
+        std::cmp::Reverse((block_number, block_index))
+    });
+    expected_ids.dedup();
+    let ids: Vec<_> = history.iter().map(|item| item.tx_id.clone()).collect();
+    assert_eq!(ids, expected_ids);
+
+    // Next page starts right after the cursor.
+    let cursor = {
+        let (block_number, block_index) = ids[4].split_once(',').unwrap();
+        (block_number.parse().unwrap(), block_index.parse().unwrap())
+    };
+    let page = storage
+        .chain()
+        .operations_ext_schema()
+        .get_multi_account_history(&[from_address, to_address], 3, Some(cursor), false)
+        .await?;
+    assert_eq!(page, history[5..8]);
+
+    Ok(())
+}
+
+/// Checks that the failed transactions are skipped by `get_multi_account_history`
+/// if only the successful ones are requested.
+#[db_test]
+async fn get_multi_account_history_successful_only(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block_with_rejected_op(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_multi_account_history(&[address], 100, None, false)
+        .await?;
+    assert!(history.iter().any(|item| item.success == Some(false)));
+
+    let successful_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_multi_account_history(&[address], 100, None, true)
+        .await?;
+    let expected_history: Vec<_> = history
+        .into_iter()
+        .filter(|item| item.success != Some(false))
+        .collect();
+    assert_eq!(successful_history, expected_history);
+    // Every item can be used as the cursor.
+    assert!(successful_history
+        .iter()
+        .all(|item| item.tx_id.contains(',')));
+
+    Ok(())
+}
+
 pub struct ReceiptRequest {
     tx_hash: TxHash,
     direction: PaginationDirection,
@@ -386,6 +857,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
                 token: None,
                 second_address: None,
+                min_amount: None,
+                max_amount: None,
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -514,6 +987,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                     tx_hash: ApiEither::from(request.tx_hash),
                     token: None,
                     second_address: None,
+                    min_amount: None,
+                    max_amount: None,
                 },
                 limit: request.limit,
                 direction: request.direction,
@@ -533,6 +1008,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(1, 2)),
                 token: None,
                 second_address: None,
+                min_amount: None,
+                max_amount: None,
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -551,6 +1028,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
                 token: None,
                 second_address: None,
+                min_amount: None,
+                max_amount: None,
             },
             limit: 2,
             direction: PaginationDirection::Newer,
@@ -570,6 +1049,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                min_amount: None,
+                max_amount: None,
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -585,6 +1066,8 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                min_amount: None,
+                max_amount: None,
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -595,6 +1078,69 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
     let to_txs_hashes: Vec<TxHash> = to_txs.into_iter().map(|tx| tx.tx_hash).collect();
     assert_eq!(from_txs_hashes, to_txs_hashes);
 
+    // All the transfers in the setup transfer the same amount, while swaps have no amount
+    // of their own, so only the transfers of the token (including the rejected one) match.
+    let token = Some(setup.tokens[1].id);
+    let count_by_amount = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(from, token, None, Some(&setup.amount), Some(&setup.amount))
+        .await?;
+    assert_eq!(count_by_amount, 4);
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions(&PaginationQuery {
+            from: AccountTxsRequest {
+                address: from,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
+                token,
+                second_address: None,
+                min_amount: Some(setup.amount.clone()),
+                max_amount: Some(setup.amount.clone()),
+            },
+            limit: 10,
+            direction: PaginationDirection::Newer,
+        })
+        .await?
+        .unwrap();
+    let txs_hashes: Vec<TxHash> = txs.into_iter().map(|tx| tx.tx_hash).collect();
+    assert_eq!(
+        txs_hashes,
+        vec![
+            setup.get_tx_hash(0, 1),
+            setup.get_tx_hash(0, 2),
+            setup.get_tx_hash(1, 1),
+            setup.get_tx_hash(1, 2),
+        ]
+    );
+
+    let larger_amount = &setup.amount + 1u32;
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(from, token, None, Some(&larger_amount), None)
+        .await?;
+    assert_eq!(count, 0);
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions(&PaginationQuery {
+            from: AccountTxsRequest {
+                address: from,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
+                token,
+                second_address: None,
+                min_amount: Some(larger_amount),
+                max_amount: None,
+            },
+            limit: 5,
+            direction: PaginationDirection::Older,
+        })
+        .await?
+        .unwrap();
+    assert!(txs.is_empty());
+
     Ok(())
 }
 
@@ -782,6 +1328,62 @@ async fn get_batch_info(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the transactions history reports the hashes of the batches.
+#[db_test]
+async fn transactions_history_batch_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_batch(2, true);
+
+    let txs: Vec<_> = setup.blocks[1]
+        .block_transactions
+        .iter()
+        .map(|tx| tx.get_executed_tx().unwrap().signed_tx.clone())
+        .collect();
+    let batch_id = storage
+        .chain()
+        .mempool_schema()
+        .insert_batch(&txs, Vec::new())
+        .await?;
+    setup.blocks[1]
+        .block_transactions
+        .iter_mut()
+        .for_each(|tx| match tx {
+            ExecutedOperations::Tx(tx) => {
+                tx.batch_id = Some(batch_id);
+            }
+            _ => unreachable!(),
+        });
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let batch_txs: Vec<_> = (0..3).map(|idx| setup.get_tx_hash(1, idx)).collect();
+    let batch_hash = TxHash::batch_hash(&batch_txs).to_string();
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(
+            &setup.from_zksync_account.address,
+            0,
+            100,
+            &Default::default(),
+        )
+        .await?;
+    assert!(!history.is_empty());
+    for item in history {
+        let in_batch = batch_txs
+            .iter()
+            .any(|tx_hash| item.hash.as_deref() == Some(tx_hash.to_string().as_str()));
+        if in_batch {
+            assert_eq!(item.batch_hash.as_deref(), Some(batch_hash.as_str()));
+        } else {
+            assert_eq!(item.batch_hash, None);
+        }
+    }
+
+    Ok(())
+}
+
 /// Test `get_account_transactions_count` method
 #[db_test]
 async fn account_transactions_count(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -792,13 +1394,242 @@ async fn account_transactions_count(mut storage: StorageProcessor<'_>) -> QueryR
     let count_after_saving = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(setup.from_zksync_account.address, None, None)
+        .get_account_transactions_count(setup.from_zksync_account.address, None, None, None, None)
         .await?;
     assert_eq!(count_after_saving, 10);
 
     Ok(())
 }
 
+/// Test `account_activity_span` method
+#[db_test]
+async fn account_activity_span(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+
+    let span = storage
+        .chain()
+        .operations_ext_schema()
+        .account_activity_span(&setup.from_zksync_account.address)
+        .await?;
+    assert_eq!(span.tx_count, 0);
+    assert!(span.first_tx_at.is_none());
+    assert!(span.last_tx_at.is_none());
+
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let span = storage
+        .chain()
+        .operations_ext_schema()
+        .account_activity_span(&setup.from_zksync_account.address)
+        .await?;
+    assert!(span.tx_count > 0);
+    assert!(span.first_tx_at.unwrap() <= span.last_tx_at.unwrap());
+
+    Ok(())
+}
+
+/// Checks that `get_forced_exits_by_initiator` filters the forced exits by their status
+/// and splits them into pages.
+#[db_test]
+async fn get_forced_exits_by_initiator(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block_with_forced_exits(1);
+    setup.add_block_with_forced_exits(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    let initiator = setup.from_zksync_account.address;
+    let tx_hash = |block, index| {
+        format!(
+            "0x{}",
+            hex::encode(setup.get_tx_hash(block, index).as_ref())
+        )
+    };
+    for (filter, expected_hashes) in [
+        (ForcedExitsFilter::Pending, vec![tx_hash(1, 0)]),
+        (ForcedExitsFilter::Completed, vec![tx_hash(0, 0)]),
+        (
+            ForcedExitsFilter::All,
+            vec![tx_hash(1, 1), tx_hash(1, 0), tx_hash(0, 1), tx_hash(0, 0)],
+        ),
+    ] {
+        let forced_exits = storage
+            .chain()
+            .operations_ext_schema()
+            .get_forced_exits_by_initiator(initiator, filter, 0, 10)
+            .await?;
+        let hashes: Vec<_> = forced_exits.iter().map(|tx| tx.tx_hash.clone()).collect();
+        assert_eq!(hashes, expected_hashes, "{:?}", filter);
+    }
+
+    let forced_exits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_forced_exits_by_initiator(initiator, ForcedExitsFilter::All, 0, 10)
+        .await?;
+    let target = format!("{:?}", setup.to_zksync_account.address);
+    assert!(forced_exits.iter().all(|tx| tx.target == target));
+    // Rejected transactions don't withdraw anything.
+    assert_eq!(forced_exits[0].amount, None);
+    assert!(forced_exits[0].fail_reason.is_some());
+    assert_eq!(forced_exits[1].amount, Some(setup.amount.to_string()));
+    assert!(!forced_exits[1].verified);
+    assert!(forced_exits[3].verified);
+
+    // The target of the forced exits hasn't initiated any.
+    let forced_exits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_forced_exits_by_initiator(
+            setup.to_zksync_account.address,
+            ForcedExitsFilter::All,
+            0,
+            10,
+        )
+        .await?;
+    assert!(forced_exits.is_empty());
+
+    let forced_exits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_forced_exits_by_initiator(initiator, ForcedExitsFilter::All, 1, 2)
+        .await?;
+    let hashes: Vec<_> = forced_exits.iter().map(|tx| tx.tx_hash.clone()).collect();
+    assert_eq!(hashes, vec![tx_hash(1, 0), tx_hash(0, 1)]);
+
+    Ok(())
+}
+
+/// Checks that `get_account_deposits` returns the deposits to the account, newest first.
+#[db_test]
+async fn get_account_deposits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let deposits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_deposits(setup.to_zksync_account.address, 10)
+        .await?;
+    let block_numbers: Vec<_> = deposits
+        .iter()
+        .map(|deposit| deposit.block_number)
+        .collect();
+    assert_eq!(block_numbers, vec![2, 1]);
+    let deposit = &deposits[1];
+    let eth_hash = match &setup.blocks[0].block_transactions[0] {
+        ExecutedOperations::PriorityOp(op) => op.priority_op.eth_hash,
+        ExecutedOperations::Tx(_) => unreachable!("The first operation of the block is a deposit"),
+    };
+    assert_eq!(deposit.eth_hash, format!("{:?}", eth_hash));
+    assert_eq!(
+        deposit.from,
+        format!("{:?}", setup.from_zksync_account.address)
+    );
+    assert_eq!(deposit.token, i64::from(*setup.tokens[0].id));
+    assert_eq!(deposit.amount, setup.amount.to_string());
+    assert_eq!(deposit.eth_block, 10);
+
+    let deposits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_deposits(setup.to_zksync_account.address, 1)
+        .await?;
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(deposits[0].block_number, 2);
+
+    // The sender of the deposits hasn't received any.
+    let deposits = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_deposits(setup.from_zksync_account.address, 10)
+        .await?;
+    assert!(deposits.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `load_recent_rejected_txs` returns the rejected transactions of all the blocks.
+#[db_test]
+async fn recent_rejected_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    setup.add_block_with_rejected_op(3);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let rejected_txs = storage
+        .chain()
+        .operations_ext_schema()
+        .load_recent_rejected_txs(10)
+        .await?;
+    // The latest rejected transaction goes first.
+    let expected_hashes: Vec<_> = [2, 1]
+        .iter()
+        .map(|&block| format!("0x{}", hex::encode(setup.get_tx_hash(block, 2).as_ref())))
+        .collect();
+    let hashes: Vec<_> = rejected_txs.iter().map(|tx| tx.tx_hash.clone()).collect();
+    assert_eq!(hashes, expected_hashes);
+    assert!(rejected_txs.iter().all(|tx| !tx.success));
+
+    let rejected_txs = storage
+        .chain()
+        .operations_ext_schema()
+        .load_recent_rejected_txs(1)
+        .await?;
+    assert_eq!(rejected_txs.len(), 1);
+    assert_eq!(rejected_txs[0].tx_hash, expected_hashes[0]);
+
+    Ok(())
+}
+
+/// Checks that `get_priority_op_receipt` reports the verification data only after the block is verified.
+#[db_test]
+async fn priority_op_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    // Serial ID of the deposit operation in the first block.
+    let serial_id = 2;
+
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_receipt(serial_id)
+        .await?;
+    assert!(!receipt.committed);
+    assert!(!receipt.finalized);
+
+    commit_schema_data(&mut storage, &setup).await?;
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_receipt(serial_id)
+        .await?;
+    assert!(receipt.committed);
+    assert!(!receipt.verified);
+    assert_eq!(receipt.verified_in_l2_block, None);
+    assert_eq!(receipt.verify_eth_tx_hash, None);
+    assert!(!receipt.finalized);
+
+    verify_block(&mut storage, BlockNumber(1)).await?;
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .get_priority_op_receipt(serial_id)
+        .await?;
+    assert!(receipt.verified);
+    assert_eq!(receipt.verified_in_l2_block, Some(1));
+    assert!(receipt.verify_eth_tx_hash.is_some());
+    assert!(receipt.finalized);
+
+    Ok(())
+}
+
 /// Test `get_account_last_tx_hash` method
 #[db_test]
 async fn account_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1208,7 +2039,7 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
         let txs = storage
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_history(address, 0, 1)
+            .get_account_transactions_history(address, 0, 1, &Default::default())
             .await?;
         assert_eq!(txs.len(), 1);
         assert_eq!(txs[0].hash.clone().unwrap(), tx_hash.to_string());
@@ -1218,7 +2049,13 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
         let txs = storage
             .chain()
             .operations_ext_schema()
-            .get_account_transactions_history_from(address, (2, 1), SearchDirection::Older, 1)
+            .get_account_transactions_history_from(
+                address,
+                (2, 1),
+                SearchDirection::Older,
+                1,
+                &Default::default(),
+            )
             .await?;
         assert_eq!(txs.len(), 1);
         assert_eq!(txs[0].hash.clone().unwrap(), tx_hash.to_string());
@@ -1234,6 +2071,8 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
                     tx_hash: ApiEither::from(tx_hash),
                     token: None,
                     second_address: None,
+                    min_amount: None,
+                    max_amount: None,
                 },
                 limit: 1,
                 direction: PaginationDirection::Older,
@@ -1256,3 +2095,87 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
 
     Ok(())
 }
+
+/// Checks that `get_tx_hash_by_nonce` finds the successful transactions of the account by their nonces.
+#[db_test]
+async fn tx_hash_by_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block_with_rejected_op(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // Some of the test transactions don't increment the nonce, so several
+    // successful transactions may share the same one.
+    let address = setup.from_zksync_account.address;
+    let mut txs_by_nonce: BTreeMap<Nonce, Vec<TxHash>> = BTreeMap::new();
+    for op in &setup.blocks[0].block_transactions {
+        if let ExecutedOperations::Tx(tx) = op {
+            if tx.signed_tx.account() != address {
+                continue;
+            }
+            let hashes = txs_by_nonce.entry(tx.signed_tx.nonce()).or_default();
+            // Rejected transactions don't use the nonce.
+            if tx.success {
+                hashes.push(tx.signed_tx.hash());
+            }
+        }
+    }
+    // The rejected transfer is the only one with its nonce.
+    assert!(txs_by_nonce.values().any(|hashes| hashes.is_empty()));
+
+    for (nonce, hashes) in txs_by_nonce {
+        let tx_hash = storage
+            .chain()
+            .operations_ext_schema()
+            .get_tx_hash_by_nonce(&address, nonce)
+            .await?;
+        match tx_hash {
+            Some(tx_hash) => assert!(hashes.contains(&tx_hash)),
+            None => assert!(hashes.is_empty()),
+        }
+    }
+
+    let tx_hash = storage
+        .chain()
+        .operations_ext_schema()
+        .get_tx_hash_by_nonce(&address, Nonce(u32::MAX))
+        .await?;
+    assert_eq!(tx_hash, None);
+
+    Ok(())
+}
+
+/// Checks that `get_latest_change_pubkey` returns the most recent `ChangePubKey` transaction of the account.
+#[db_test]
+async fn latest_change_pubkey(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // `ChangePubKey` transactions have the index 4 in the test blocks.
+    let change_pubkey = storage
+        .chain()
+        .operations_ext_schema()
+        .get_latest_change_pubkey(&setup.from_zksync_account.address)
+        .await?
+        .expect("Account has the `ChangePubKey` transactions");
+    assert_eq!(
+        TxHash::from_slice(&change_pubkey.tx_hash).unwrap(),
+        setup.get_tx_hash(1, 4)
+    );
+    assert_eq!(change_pubkey.block_number, 2);
+    assert_eq!(change_pubkey.block_index, Some(4));
+    assert_eq!(
+        change_pubkey.new_pk_hash,
+        setup.from_zksync_account.pubkey_hash.as_hex()
+    );
+
+    let change_pubkey = storage
+        .chain()
+        .operations_ext_schema()
+        .get_latest_change_pubkey(&Address::random())
+        .await?;
+    assert_eq!(change_pubkey, None);
+
+    Ok(())
+}
@@ -10,9 +10,9 @@ use zksync_types::{
     operations::{ChangePubKeyOp, ZkSyncOp},
     priority_ops::PriorityOp,
     tx::{ChangePubKeyType, TxHash},
-    AccountId, Address, BlockNumber, CloseOp, Deposit, DepositOp, FullExit, FullExitOp, MintNFTOp,
-    SwapOp, Token, TokenId, TokenKind, TransferOp, TransferToNewOp, WithdrawNFTOp, WithdrawOp,
-    H256,
+    AccountId, Address, BlockNumber, CloseOp, Deposit, DepositOp, ForcedExitOp, FullExit,
+    FullExitOp, MintNFTOp, SwapOp, Token, TokenId, TokenKind, TransferOp, TransferToNewOp,
+    WithdrawNFTOp, WithdrawOp, H256,
 };
 // Local imports
 
@@ -183,6 +183,30 @@ impl TransactionsHistoryTestSetup {
         self.blocks.push(block);
     }
 
+    /// Adds a block with the successful and the rejected `ForcedExit` transactions
+    /// initiated by the `from` account against the `to` one.
+    pub fn add_block_with_forced_exits(&mut self, block_id: u32) {
+        let operations = vec![
+            self.create_forced_exit_tx(Some(0)),
+            self.create_forced_exit_tx(None),
+        ];
+
+        let block = Block::new(
+            BlockNumber(block_id),
+            Fr::zero(),
+            AccountId(0),
+            operations,
+            (0, 0), // Not important
+            100,
+            1_000_000.into(), // Not important
+            1_500_000.into(), // Not important
+            Default::default(),
+            0,
+        );
+
+        self.blocks.push(block);
+    }
+
     fn create_deposit_op(
         &mut self,
         serial_id: u64,
@@ -352,6 +376,33 @@ impl TransactionsHistoryTestSetup {
         ExecutedOperations::Tx(Box::new(executed_withdraw_op))
     }
 
+    fn create_forced_exit_tx(&mut self, block_index: Option<u32>) -> ExecutedOperations {
+        let forced_exit_op = ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+            tx: self.from_zksync_account.sign_forced_exit(
+                self.tokens[2].id,
+                0u32.into(),
+                &self.to_zksync_account.address,
+                None,
+                true,
+                Default::default(),
+            ),
+            target_account_id: self.to_zksync_account.get_account_id().unwrap(),
+            withdraw_amount: block_index.map(|_| self.amount.clone().into()),
+        }));
+
+        let executed_forced_exit_op = ExecutedTx {
+            signed_tx: forced_exit_op.try_get_tx().unwrap().into(),
+            success: block_index.is_some(),
+            op: Some(forced_exit_op),
+            fail_reason: block_index.map_or(Some("Insufficient balance".to_string()), |_| None),
+            block_index,
+            created_at: self.get_tx_time(),
+            batch_id: None,
+        };
+
+        ExecutedOperations::Tx(Box::new(executed_forced_exit_op))
+    }
+
     fn create_mint_nft_tx(&mut self, block_index: Option<u32>) -> ExecutedOperations {
         let mint_nft_op = ZkSyncOp::MintNFTOp(Box::new(MintNFTOp {
             tx: self
@@ -1,10 +1,13 @@
 // External imports
+use futures_util::stream::TryStreamExt;
+use num::Zero;
 // Workspace imports
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::{
     helpers::apply_updates, AccountId, AccountMap, AccountUpdate, Address, BlockNumber, Nonce,
     TokenId, H256, NFT,
 };
+use zksync_utils::big_decimal_to_ratio;
 // Local imports
 use super::block::apply_random_updates;
 use crate::{
@@ -220,6 +223,60 @@ async fn state_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the balances stream returns the whole verified state in the expected order.
+#[db_test]
+async fn verified_balances_stream(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let (accounts_block_1, updates_block_1) = apply_random_updates(AccountMap::default(), &mut rng);
+    let (accounts_block_2, updates_block_2) =
+        apply_random_updates(accounts_block_1.clone(), &mut rng);
+
+    // Verify the first block only, so the second one must not affect the stream.
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates_block_1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates_block_2, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    let balances: Vec<_> = StateSchema(&mut storage)
+        .verified_balances_stream()
+        .try_collect()
+        .await?;
+
+    let keys: Vec<_> = balances
+        .iter()
+        .map(|balance| (balance.account_id, balance.coin_id))
+        .collect();
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort_unstable();
+    sorted_keys.dedup();
+    assert_eq!(keys, sorted_keys);
+
+    for balance in &balances {
+        let account = accounts_block_1
+            .get(&AccountId(balance.account_id as u32))
+            .expect("Unknown account in the stream");
+        assert_eq!(Address::from_slice(&balance.address), account.address);
+
+        let amount = big_decimal_to_ratio(&balance.balance).unwrap().to_integer();
+        assert_eq!(amount, account.get_balance(TokenId(balance.coin_id as u32)));
+        // Zero balances may be kept in the table, but they are not streamed.
+        assert!(!amount.is_zero());
+    }
+    let expected_nonzero_balances: usize = accounts_block_1
+        .values()
+        .map(|account| account.get_nonzero_balances().len())
+        .sum();
+    assert_eq!(balances.len(), expected_nonzero_balances);
+
+    Ok(())
+}
+
 /// Checks if account updates are removed correctly.
 #[db_test]
 async fn test_remove_account_updates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
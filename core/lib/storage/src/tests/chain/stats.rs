@@ -0,0 +1,225 @@
+// Built-in imports
+use std::collections::BTreeMap;
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, BlockNumber, ExecutedOperations, TokenLike,
+};
+use zksync_utils::biguint_to_big_decimal;
+// Local imports
+use super::operations_ext::{
+    commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup,
+};
+use crate::{
+    chain::{block::BlockSchema, operations::OperationsSchema},
+    test_data::{gen_sample_block, gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS},
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+/// Checks that `load_fee_token_usage` aggregates the fees of the successful transactions per fee token.
+#[db_test]
+async fn fee_token_usage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+
+    let mut expected_usage = BTreeMap::new();
+    for op in setup
+        .blocks
+        .iter()
+        .flat_map(|block| block.block_transactions.iter())
+    {
+        if let ExecutedOperations::Tx(tx) = op {
+            if !tx.success {
+                continue;
+            }
+            if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.signed_tx.tx.get_fee_info() {
+                if fee == BigUint::default() {
+                    continue;
+                }
+                let (tx_count, total_fee) = expected_usage
+                    .entry(*token_id as i32)
+                    .or_insert((0, BigUint::default()));
+                *tx_count += 1;
+                *total_fee += fee;
+            }
+        }
+    }
+    assert!(!expected_usage.is_empty());
+
+    let usage = storage
+        .chain()
+        .stats_schema()
+        .load_fee_token_usage(BlockNumber(1), BlockNumber(2))
+        .await?;
+    let usage: BTreeMap<_, _> = usage
+        .into_iter()
+        .map(|usage| (usage.token_id, (usage.tx_count, usage.total_fee)))
+        .collect();
+    let expected_usage: BTreeMap<_, _> = expected_usage
+        .into_iter()
+        .map(|(token_id, (tx_count, fee))| (token_id, (tx_count, biguint_to_big_decimal(fee))))
+        .collect();
+    assert_eq!(usage, expected_usage);
+
+    let usage = storage
+        .chain()
+        .stats_schema()
+        .load_fee_token_usage(BlockNumber(3), BlockNumber(10))
+        .await?;
+    assert!(usage.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `count_operations_by_type` counts the operations of the requested blocks only.
+#[db_test]
+async fn operations_count_by_type(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let mut expected_counts = BTreeMap::new();
+    for op in &setup.blocks[0].block_transactions {
+        let op_type = match op {
+            ExecutedOperations::Tx(tx) => serde_json::to_value(&tx.signed_tx.tx).unwrap(),
+            ExecutedOperations::PriorityOp(op) => serde_json::to_value(&op.op).unwrap(),
+        }["type"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        *expected_counts.entry(op_type).or_insert(0) += 1;
+    }
+    assert_eq!(expected_counts["Transfer"], 2);
+
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_operations_by_type(BlockNumber(1), BlockNumber(1))
+        .await?;
+    assert_eq!(counts, expected_counts);
+
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_operations_by_type(BlockNumber(1), BlockNumber(2))
+        .await?;
+    let doubled_counts: BTreeMap<_, _> = expected_counts
+        .into_iter()
+        .map(|(op_type, count)| (op_type, count * 2))
+        .collect();
+    assert_eq!(counts, doubled_counts);
+
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_operations_by_type(BlockNumber(3), BlockNumber(10))
+        .await?;
+    assert!(counts.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `load_block_intervals` returns the intervals between the blocks created since the given time.
+#[db_test]
+async fn block_intervals(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for (block_number, timestamp) in [(1, 100), (2, 110), (3, 140), (4, 145)] {
+        let mut block = gen_sample_block(BlockNumber(block_number), BLOCK_SIZE_CHUNKS, vec![]);
+        block.timestamp = timestamp;
+        BlockSchema(&mut storage).save_full_block(block).await?;
+    }
+
+    let intervals = storage
+        .chain()
+        .stats_schema()
+        .load_block_intervals(0)
+        .await?;
+    assert_eq!(intervals, vec![10, 30, 5]);
+
+    // The first block of the range is only used as a starting point.
+    let intervals = storage
+        .chain()
+        .stats_schema()
+        .load_block_intervals(110)
+        .await?;
+    assert_eq!(intervals, vec![30, 5]);
+
+    let intervals = storage
+        .chain()
+        .stats_schema()
+        .load_block_intervals(1000)
+        .await?;
+    assert!(intervals.is_empty());
+
+    Ok(())
+}
+
+/// Checks that `load_proving_times` only takes the confirmed commitments and executions into account.
+#[db_test]
+async fn proving_times(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let since = chrono::Utc::now() - chrono::Duration::minutes(1);
+    for block_number in 1..=3 {
+        BlockSchema(&mut storage)
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                BLOCK_SIZE_CHUNKS,
+                vec![],
+            ))
+            .await?;
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                BlockNumber(block_number),
+                AggregatedActionType::CommitBlocks,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+    }
+    for block_number in 1..=2 {
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                BlockNumber(block_number),
+                AggregatedActionType::ExecuteBlocks,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+    }
+
+    // Nothing is confirmed on L1 yet.
+    let proving_times = storage
+        .chain()
+        .stats_schema()
+        .load_proving_times(since)
+        .await?;
+    assert!(proving_times.is_empty());
+
+    for action_type in [
+        AggregatedActionType::CommitBlocks,
+        AggregatedActionType::ExecuteBlocks,
+    ] {
+        OperationsSchema(&mut storage)
+            .confirm_aggregated_operations(BlockNumber(1), BlockNumber(3), action_type)
+            .await?;
+    }
+    let proving_times = storage
+        .chain()
+        .stats_schema()
+        .load_proving_times(since)
+        .await?;
+    // The third block is not executed.
+    assert_eq!(proving_times.len(), 2);
+
+    let proving_times = storage
+        .chain()
+        .stats_schema()
+        .load_proving_times(chrono::Utc::now() + chrono::Duration::minutes(1))
+        .await?;
+    assert!(proving_times.is_empty());
+
+    Ok(())
+}
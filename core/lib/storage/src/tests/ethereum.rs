@@ -5,13 +5,21 @@ use std::str::FromStr;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     ethereum::ETHOperation,
-    BlockNumber, H256, U256,
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
 use crate::test_data::{gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS};
 use crate::tests::db_test;
 use crate::{
-    chain::operations::OperationsSchema, ethereum::EthereumSchema, QueryResult, StorageProcessor,
+    chain::{
+        block::{
+            records::{StorageBlockGasUsed, StorageBlockVerifier},
+            BlockSchema,
+        },
+        operations::OperationsSchema,
+    },
+    ethereum::EthereumSchema,
+    QueryResult, StorageProcessor,
 };
 use num::BigUint;
 
@@ -358,6 +366,203 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
     Ok(())
 }
 
+/// Checks that the gas used by the confirmed commit and proof transactions is reported for the block.
+#[db_test]
+async fn blocks_gas_used(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let commit_operation = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+
+    let params = EthereumTxParams::new("CommitBlocks".into(), commit_operation);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+
+    // The transaction is not confirmed yet.
+    let gas_used = BlockSchema(&mut storage)
+        .load_blocks_gas_used(&[block_number])
+        .await?;
+    assert!(gas_used.is_empty());
+
+    EthereumSchema(&mut storage)
+        .save_eth_tx_gas_used(&params.hash, 150_000)
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    let gas_used = BlockSchema(&mut storage)
+        .load_blocks_gas_used(&[block_number, BlockNumber(2)])
+        .await?;
+    assert_eq!(
+        gas_used,
+        vec![StorageBlockGasUsed {
+            block_number: 1,
+            commit_gas_used: Some(150_000),
+            verify_gas_used: None,
+        }]
+    );
+
+    // The block is verified by the transaction publishing its proof.
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::PublishProofBlocksOnchain,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let proof_operation = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            block_number,
+        )
+        .await?;
+    let params = EthereumTxParams::new("PublishProofBlocksOnchain".into(), proof_operation);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+    EthereumSchema(&mut storage)
+        .save_eth_tx_gas_used(&params.hash, 400_000)
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    let gas_used = BlockSchema(&mut storage)
+        .load_blocks_gas_used(&[block_number])
+        .await?;
+    assert_eq!(
+        gas_used,
+        vec![StorageBlockGasUsed {
+            block_number: 1,
+            commit_gas_used: Some(150_000),
+            verify_gas_used: Some(400_000),
+        }]
+    );
+
+    Ok(())
+}
+
+/// Checks that the sender of the confirmed proof transaction is reported for the block.
+#[db_test]
+async fn blocks_verifiers(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::PublishProofBlocksOnchain,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let proof_operation = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            block_number,
+        )
+        .await?;
+
+    let params = EthereumTxParams::new("PublishProofBlocksOnchain".into(), proof_operation);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::PublishProofBlocksOnchain,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+
+    // The transaction is not confirmed yet.
+    let verifiers = BlockSchema(&mut storage)
+        .load_blocks_verifiers(&[block_number])
+        .await?;
+    assert!(verifiers.is_empty());
+
+    let sender = Address::repeat_byte(0x11);
+    EthereumSchema(&mut storage)
+        .save_eth_tx_sender(&params.hash, sender)
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    let verifiers = BlockSchema(&mut storage)
+        .load_blocks_verifiers(&[block_number, BlockNumber(2)])
+        .await?;
+    let expected = vec![StorageBlockVerifier {
+        block_number: 1,
+        verifier_address: sender.as_bytes().to_vec(),
+    }];
+    assert_eq!(verifiers, expected);
+
+    // The sender of the execute transaction is not the verifier of the block.
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::ExecuteBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let execute_operation = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::ExecuteBlocks, block_number)
+        .await?;
+    let params = EthereumTxParams::new("ExecuteBlocks".into(), execute_operation);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::ExecuteBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+    EthereumSchema(&mut storage)
+        .save_eth_tx_sender(&params.hash, Address::repeat_byte(0x22))
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    let verifiers = BlockSchema(&mut storage)
+        .load_blocks_verifiers(&[block_number])
+        .await?;
+    assert_eq!(verifiers, expected);
+
+    Ok(())
+}
+
 /// Simple test for store/load of (average) gas price.
 #[db_test]
 async fn ethereum_gas_update(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -4,11 +4,16 @@ use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
+    aggregated_operations::AggregatedActionType,
+    prover::{ProverJob, ProverJobStatus, ProverJobType},
     BlockNumber,
 };
 // Local imports
-use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
+use crate::chain::block::ProofLatencyBucketSize;
+use crate::test_data::{
+    gen_sample_block, gen_unique_aggregated_operation, get_sample_aggregated_proof,
+    get_sample_single_proof, BLOCK_SIZE_CHUNKS,
+};
 use crate::tests::db_test;
 use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
 
@@ -125,6 +130,22 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
     assert!(loaded_proof.is_some());
     assert!(loaded_aggregated_proof.is_some());
 
+    // Job of the proven block is done, and it's taken into account in the average proof time.
+    let job = ProverSchema(storage)
+        .load_block_proof_job(BlockNumber(1))
+        .await?
+        .expect("Job for the proven block should exist");
+    assert_eq!(job.id, stored_job_id);
+    assert_eq!(job.job_status, ProverJobStatus::Done.to_number());
+    assert!(ProverSchema(storage)
+        .load_block_proof_job(BlockNumber(2))
+        .await?
+        .is_none());
+    assert!(ProverSchema(storage)
+        .load_average_proof_time(100)
+        .await?
+        .is_some());
+
     Ok(())
 }
 
@@ -213,6 +234,74 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that `count_blocks_by_proof_status` distinguishes the blocks awaiting for proofs
+/// from the ones being proven.
+#[db_test]
+async fn test_count_blocks_by_proof_status(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                100,
+                Default::default(),
+            ))
+            .await?;
+    }
+    for block_number in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+
+    // Blocks without jobs await for proofs as well as the ones with idle jobs.
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_blocks_by_proof_status(BlockNumber(0))
+        .await?;
+    assert_eq!(counts, (3, 0));
+
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.first_block, BlockNumber(1));
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_blocks_by_proof_status(BlockNumber(0))
+        .await?;
+    assert_eq!(counts, (2, 1));
+
+    // Proven blocks are not counted.
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_blocks_by_proof_status(BlockNumber(0))
+        .await?;
+    assert_eq!(counts, (2, 0));
+
+    let counts = storage
+        .chain()
+        .stats_schema()
+        .count_blocks_by_proof_status(BlockNumber(2))
+        .await?;
+    assert_eq!(counts, (1, 0));
+
+    Ok(())
+}
+
 /// Checks that the witness can be stored and loaded.
 #[db_test]
 async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -441,3 +530,80 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that `get_proof_latency_buckets` only takes the proven blocks of the range into account.
+#[db_test]
+async fn proof_latency_buckets(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                BLOCK_SIZE_CHUNKS,
+                Default::default(),
+            ))
+            .await?;
+        storage
+            .chain()
+            .operations_schema()
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                BlockNumber(block_number),
+                AggregatedActionType::CommitBlocks,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+    }
+    // The third block is not proven yet.
+    for block_number in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                Default::default(),
+                0,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+        let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
+        ProverSchema(&mut storage)
+            .store_proof(
+                job_id,
+                BlockNumber(block_number),
+                &get_sample_single_proof(),
+            )
+            .await?;
+    }
+
+    let buckets = storage
+        .chain()
+        .block_schema()
+        .get_proof_latency_buckets(BlockNumber(1), BlockNumber(3), ProofLatencyBucketSize::Day)
+        .await?;
+    assert_eq!(buckets.len(), 1);
+    let bucket = &buckets[0];
+    assert_eq!(bucket.block_count, 2);
+    assert!(bucket.min_proof_ms >= 0.0);
+    assert!(bucket.min_proof_ms <= bucket.avg_proof_ms);
+    assert!(bucket.avg_proof_ms <= bucket.max_proof_ms);
+
+    let buckets = storage
+        .chain()
+        .block_schema()
+        .get_proof_latency_buckets(BlockNumber(2), BlockNumber(3), ProofLatencyBucketSize::Hour)
+        .await?;
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0].block_count, 1);
+
+    let buckets = storage
+        .chain()
+        .block_schema()
+        .get_proof_latency_buckets(BlockNumber(3), BlockNumber(3), ProofLatencyBucketSize::Hour)
+        .await?;
+    assert!(buckets.is_empty());
+
+    Ok(())
+}
@@ -7,7 +7,8 @@ use num::{rational::Ratio, BigUint};
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
     tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
-    Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
+    Token, TokenId, TokenKind, TokenLike, TokenPrice, TokenRiskLevel, WithdrawNFTOp, ZkSyncOp,
+    H256,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
@@ -179,6 +180,90 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the token prices are stored at the time of the blocks and then loaded by the blocks.
+#[db_test]
+async fn test_block_token_prices(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    const UNUSED_TOKEN_ID: TokenId = TokenId(1);
+
+    // The price of the token that isn't used in the blocks is never stored.
+    storage
+        .tokens_schema()
+        .store_or_update_token(Token::new(
+            UNUSED_TOKEN_ID,
+            "0000000000000000000000000000000000000001".parse().unwrap(),
+            "ABC",
+            18,
+            TokenKind::ERC20,
+        ))
+        .await?;
+    let price = TokenPrice {
+        usd_price: Ratio::from_integer(BigUint::from(1u32)),
+        last_updated: Utc::now(),
+    };
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(UNUSED_TOKEN_ID, price)
+        .await?;
+
+    // There are no prices for the tokens yet, so nothing is stored.
+    storage
+        .tokens_schema()
+        .store_block_token_prices(BlockNumber(1))
+        .await?;
+    for (block_number, usd_price) in &[(2u32, 100u32), (3, 200)] {
+        let price = TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(*usd_price)),
+            last_updated: chrono::Utc::now(),
+        };
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(TOKEN_ID, price)
+            .await?;
+        storage
+            .tokens_schema()
+            .store_block_token_prices(BlockNumber(*block_number))
+            .await?;
+    }
+    // Stale prices are not stored.
+    let price = TokenPrice {
+        usd_price: Ratio::from_integer(BigUint::from(300u32)),
+        last_updated: Utc::now() - chrono::Duration::days(1),
+    };
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(TOKEN_ID, price)
+        .await?;
+    storage
+        .tokens_schema()
+        .store_block_token_prices(BlockNumber(4))
+        .await?;
+
+    let pairs: Vec<_> = (1..=4)
+        .flat_map(|block_number| {
+            vec![
+                (BlockNumber(block_number), TOKEN_ID),
+                (BlockNumber(block_number), UNUSED_TOKEN_ID),
+            ]
+        })
+        .collect();
+    let prices = storage
+        .tokens_schema()
+        .load_block_token_prices(&pairs)
+        .await?;
+    assert_eq!(prices.len(), 2);
+    assert_eq!(
+        prices[&(BlockNumber(2), TOKEN_ID)],
+        Ratio::from_integer(BigUint::from(100u32))
+    );
+    assert_eq!(
+        prices[&(BlockNumber(3), TOKEN_ID)],
+        Ratio::from_integer(BigUint::from(200u32))
+    );
+
+    Ok(())
+}
+
 /// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
 #[db_test]
 async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -222,6 +307,166 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that token risk levels are stored and joined with the tokens list.
+#[db_test]
+async fn test_token_risk_levels(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(
+        TokenId(1),
+        "0000000000000000000000000000000000000001".parse().unwrap(),
+        "ABC",
+        9,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(token.clone())
+        .await?;
+
+    // Tokens without a configured risk level are reported as unknown.
+    let tokens = storage.tokens_schema().load_tokens_with_risk().await?;
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens
+        .iter()
+        .all(|(_, level)| *level == TokenRiskLevel::Unknown));
+
+    storage
+        .tokens_schema()
+        .store_token_risk_levels(&[(TokenId(0), TokenRiskLevel::Low)])
+        .await?;
+    let tokens = storage.tokens_schema().load_tokens_with_risk().await?;
+    assert_eq!(tokens[0].0.id, TokenId(0));
+    assert_eq!(tokens[0].1, TokenRiskLevel::Low);
+    assert_eq!(tokens[1], (token, TokenRiskLevel::Unknown));
+
+    // Stored levels are replaced entirely.
+    storage
+        .tokens_schema()
+        .store_token_risk_levels(&[(TokenId(1), TokenRiskLevel::High)])
+        .await?;
+    let tokens = storage.tokens_schema().load_tokens_with_risk().await?;
+    assert_eq!(tokens[0].1, TokenRiskLevel::Unknown);
+    assert_eq!(tokens[1].1, TokenRiskLevel::High);
+
+    // Levels of the remaining tokens are updated in place.
+    storage
+        .tokens_schema()
+        .store_token_risk_levels(&[(TokenId(1), TokenRiskLevel::Low)])
+        .await?;
+    let tokens = storage.tokens_schema().load_tokens_with_risk().await?;
+    assert_eq!(tokens[1].1, TokenRiskLevel::Low);
+
+    Ok(())
+}
+
+/// Checks that the suspended tokens are reported alongside with the suspension reasons.
+#[db_test]
+async fn test_suspended_tokens(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(
+        TokenId(1),
+        "0000000000000000000000000000000000000001".parse().unwrap(),
+        "ABC",
+        9,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(token.clone())
+        .await?;
+    storage
+        .tokens_schema()
+        .store_token_risk_levels(&[(TokenId(1), TokenRiskLevel::High)])
+        .await?;
+
+    let tokens = storage
+        .tokens_schema()
+        .load_tokens_with_suspension_status()
+        .await?;
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens.iter().all(|token| !token.is_suspended));
+    assert_eq!(tokens[1].token, token);
+    assert_eq!(tokens[1].risk_level, TokenRiskLevel::High);
+
+    storage
+        .tokens_schema()
+        .suspend_token(TokenId(0), None)
+        .await?;
+    storage
+        .tokens_schema()
+        .suspend_token(TokenId(1), Some("Contract upgrade"))
+        .await?;
+    let tokens = storage
+        .tokens_schema()
+        .load_tokens_with_suspension_status()
+        .await?;
+    assert!(tokens[0].is_suspended);
+    assert_eq!(tokens[0].suspension_reason, None);
+    assert!(tokens[1].is_suspended);
+    assert_eq!(
+        tokens[1].suspension_reason.as_deref(),
+        Some("Contract upgrade")
+    );
+    let suspended_tokens = storage
+        .tokens_schema()
+        .get_suspended_tokens(&[TokenId(1), TokenId(2)])
+        .await?;
+    assert_eq!(suspended_tokens, vec![TokenId(1)].into_iter().collect());
+
+    storage.tokens_schema().resume_token(TokenId(1)).await?;
+    let tokens = storage
+        .tokens_schema()
+        .load_tokens_with_suspension_status()
+        .await?;
+    assert!(tokens[0].is_suspended);
+    assert!(!tokens[1].is_suspended);
+    assert_eq!(tokens[1].suspension_reason, None);
+
+    Ok(())
+}
+
+/// Checks that `load_tokens_with_suspension_status` only loads the ERC20 tokens.
+#[db_test]
+async fn test_load_tokens_skips_nfts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(
+        TokenId(1),
+        "0000000000000000000000000000000000000001".parse().unwrap(),
+        "ABC",
+        9,
+        TokenKind::ERC20,
+    );
+    let nft = Token {
+        id: TokenId(MIN_NFT_TOKEN_ID),
+        address: "0000000000000000000000000000000000000005".parse().unwrap(),
+        symbol: "NFT".into(),
+        decimals: 0,
+        kind: TokenKind::NFT,
+        is_nft: true,
+    };
+    let removed_token = Token::new(
+        TokenId(2),
+        "0000000000000000000000000000000000000002".parse().unwrap(),
+        "DEF",
+        6,
+        TokenKind::None,
+    );
+    for stored_token in [&token, &nft, &removed_token] {
+        storage
+            .tokens_schema()
+            .store_or_update_token(stored_token.clone())
+            .await?;
+    }
+
+    let token_ids: Vec<TokenId> = storage
+        .tokens_schema()
+        .load_tokens_with_suspension_status()
+        .await?
+        .into_iter()
+        .map(|status| status.token.id)
+        .collect();
+    assert_eq!(token_ids, vec![TokenId(0), token.id]);
+
+    Ok(())
+}
+
 /// Checks the store/load factories for nft
 #[db_test]
 async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{Duration, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -10,10 +11,14 @@ use zksync_api_types::v02::{
     pagination::{PaginationDirection, PaginationQuery},
     token::ApiNFT,
 };
-use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
-use zksync_utils::ratio_to_big_decimal;
+use zksync_types::{
+    AccountId, Address, BlockNumber, Token, TokenId, TokenLike, TokenPrice, TokenRiskLevel, NFT,
+};
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind, TokenWithStatus,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
@@ -23,6 +28,9 @@ pub mod records;
 
 /// Precision of the USD price per token
 pub(crate) const STORED_USD_PRICE_PRECISION: usize = 6;
+/// Prices are updated by the ticker every few minutes, the older ones are considered stale
+/// and aren't stored as the prices at the time of the block.
+const BLOCK_TOKEN_PRICE_MAX_AGE_SECS: i64 = 60 * 60;
 
 /// Tokens schema handles the `tokens` table, providing methods to
 /// get and store new tokens.
@@ -187,6 +195,194 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(tokens.into_iter().map(|token| (token.id, token)).collect())
     }
 
+    /// Loads all the stored ERC20 tokens alongside with their configured risk levels.
+    /// Tokens without a configured risk level are returned with `TokenRiskLevel::Unknown`.
+    pub async fn load_tokens_with_risk(&mut self) -> QueryResult<Vec<(Token, TokenRiskLevel)>> {
+        let start = Instant::now();
+        let tokens = sqlx::query!(
+            r#"
+            SELECT tokens.id, tokens.address, tokens.decimals, tokens.kind as "kind: TokenKind",
+                tokens.symbol, token_risk_levels.risk_level as "risk_level?"
+            FROM tokens
+            LEFT JOIN token_risk_levels ON token_risk_levels.token_id = tokens.id
+            WHERE tokens.kind = 'ERC20'::token_kind
+            ORDER BY tokens.id ASC
+            "#,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = tokens
+            .into_iter()
+            .map(|row| {
+                let token = Token::from(DbToken {
+                    id: row.id,
+                    address: row.address,
+                    symbol: row.symbol,
+                    decimals: row.decimals,
+                    kind: row.kind,
+                });
+                let risk_level = row
+                    .risk_level
+                    .and_then(|level| level.parse().ok())
+                    .unwrap_or_default();
+                (token, risk_level)
+            })
+            .collect();
+
+        metrics::histogram!("sql.token.load_tokens_with_risk", start.elapsed());
+        Ok(result)
+    }
+
+    /// Loads all the stored ERC20 tokens alongside with their risk levels and the statuses
+    /// of the suspension by governance.
+    pub async fn load_tokens_with_suspension_status(
+        &mut self,
+    ) -> QueryResult<Vec<TokenWithStatus>> {
+        let start = Instant::now();
+        let tokens = sqlx::query!(
+            r#"
+            SELECT tokens.id, tokens.address, tokens.decimals, tokens.kind as "kind: TokenKind",
+                tokens.symbol, token_risk_levels.risk_level as "risk_level?",
+                suspended_tokens.token_id IS NOT NULL as "is_suspended!",
+                suspended_tokens.reason as "suspension_reason?"
+            FROM tokens
+            LEFT JOIN token_risk_levels ON token_risk_levels.token_id = tokens.id
+            LEFT JOIN suspended_tokens ON suspended_tokens.token_id = tokens.id
+            WHERE tokens.kind = 'ERC20'::token_kind
+            ORDER BY tokens.id ASC
+            "#,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let result = tokens
+            .into_iter()
+            .map(|row| TokenWithStatus {
+                token: Token::from(DbToken {
+                    id: row.id,
+                    address: row.address,
+                    symbol: row.symbol,
+                    decimals: row.decimals,
+                    kind: row.kind,
+                }),
+                risk_level: row
+                    .risk_level
+                    .and_then(|level| level.parse().ok())
+                    .unwrap_or_default(),
+                is_suspended: row.is_suspended,
+                suspension_reason: row.suspension_reason,
+            })
+            .collect();
+
+        metrics::histogram!(
+            "sql.token.load_tokens_with_suspension_status",
+            start.elapsed()
+        );
+        Ok(result)
+    }
+
+    /// Marks the token as suspended by governance, replacing the reason if it's already suspended.
+    pub async fn suspend_token(
+        &mut self,
+        token_id: TokenId,
+        reason: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO suspended_tokens (token_id, reason)
+            VALUES ($1, $2)
+            ON CONFLICT (token_id)
+            DO UPDATE SET reason = EXCLUDED.reason
+            "#,
+            *token_id as i32,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.suspend_token", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the suspension of the token.
+    pub async fn resume_token(&mut self, token_id: TokenId) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM suspended_tokens WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.resume_token", start.elapsed());
+        Ok(())
+    }
+
+    /// Returns the suspended ones among the given tokens.
+    pub async fn get_suspended_tokens(
+        &mut self,
+        token_ids: &[TokenId],
+    ) -> QueryResult<HashSet<TokenId>> {
+        let start = Instant::now();
+        let token_ids: Vec<i32> = token_ids.iter().map(|id| **id as i32).collect();
+        let suspended_tokens = sqlx::query!(
+            "SELECT token_id FROM suspended_tokens WHERE token_id = ANY($1)",
+            &token_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| TokenId(row.token_id as u32))
+        .collect();
+
+        metrics::histogram!("sql.token.get_suspended_tokens", start.elapsed());
+        Ok(suspended_tokens)
+    }
+
+    /// Replaces the stored token risk levels with the given ones.
+    ///
+    /// Levels are updated in place, and only the levels of the tokens missing in the given
+    /// list are removed, so the concurrent readers never observe the levels being reseeded.
+    pub async fn store_token_risk_levels(
+        &mut self,
+        risk_levels: &[(TokenId, TokenRiskLevel)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (token_ids, levels): (Vec<i32>, Vec<String>) = risk_levels
+            .iter()
+            .map(|(token_id, level)| (**token_id as i32, level.to_string()))
+            .unzip();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO token_risk_levels (token_id, risk_level)
+            SELECT u.token_id, u.risk_level
+                FROM UNNEST ($1::integer[], $2::text[])
+                AS u(token_id, risk_level)
+            ON CONFLICT (token_id)
+            DO UPDATE SET risk_level = EXCLUDED.risk_level
+            "#,
+            &token_ids,
+            &levels
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM token_risk_levels WHERE token_id <> ALL($1)",
+            &token_ids
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.token.store_token_risk_levels", start.elapsed());
+        Ok(())
+    }
+
     /// Loads tokens for the given pagination query
     pub async fn load_token_page(
         &mut self,
@@ -557,6 +753,82 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the current prices in USD of ETH and the tokens used by the block operations
+    /// as the prices at the time of the block. Tokens that have no known or fresh price are skipped.
+    pub async fn store_block_token_prices(&mut self, block_number: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        let updated_after = Utc::now() - Duration::seconds(BLOCK_TOKEN_PRICE_MAX_AGE_SECS);
+        sqlx::query!(
+            r#"
+            WITH block_tokens AS (
+                SELECT tx_filters.token FROM tx_filters
+                INNER JOIN executed_transactions ON executed_transactions.tx_hash = tx_filters.tx_hash
+                WHERE executed_transactions.block_number = $1
+                UNION
+                SELECT tx_filters.token FROM tx_filters
+                INNER JOIN executed_priority_operations ON executed_priority_operations.tx_hash = tx_filters.tx_hash
+                WHERE executed_priority_operations.block_number = $1
+                UNION
+                SELECT 0
+            )
+            INSERT INTO token_prices_historical ( block_number, token_id, usd_price )
+            SELECT $1, ticker_price.token_id, ticker_price.usd_price FROM ticker_price
+            INNER JOIN block_tokens ON block_tokens.token = ticker_price.token_id
+            WHERE ticker_price.last_updated > $2
+            ON CONFLICT (block_number, token_id) DO NOTHING
+            "#,
+            i64::from(*block_number),
+            updated_after
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_block_token_prices", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the prices of the tokens in USD at the time of the blocks for the given
+    /// `(block number, token ID)` pairs. Pairs without the stored price are omitted.
+    pub async fn load_block_token_prices(
+        &mut self,
+        pairs: &[(BlockNumber, TokenId)],
+    ) -> QueryResult<HashMap<(BlockNumber, TokenId), Ratio<BigUint>>> {
+        let start = Instant::now();
+        let (block_numbers, token_ids): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .map(|(block_number, token_id)| (i64::from(**block_number), **token_id as i32))
+            .unzip();
+        let records = sqlx::query!(
+            r#"
+            SELECT token_prices_historical.block_number, token_prices_historical.token_id, usd_price
+            FROM token_prices_historical
+            INNER JOIN UNNEST ($1::bigint[], $2::integer[]) AS pairs(block_number, token_id)
+                ON token_prices_historical.block_number = pairs.block_number
+                AND token_prices_historical.token_id = pairs.token_id
+            "#,
+            &block_numbers,
+            &token_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let prices = records
+            .into_iter()
+            .map(|record| {
+                let key = (
+                    BlockNumber(record.block_number as u32),
+                    TokenId(record.token_id as u32),
+                );
+                let price =
+                    big_decimal_to_ratio(&record.usd_price).expect("Price could not be negative");
+                (key, price)
+            })
+            .collect();
+
+        metrics::histogram!("sql.token.load_block_token_prices", start.elapsed());
+        Ok(prices)
+    }
+
     pub async fn store_nft_factory(
         &mut self,
         creator_id: AccountId,
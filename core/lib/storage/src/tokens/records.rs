@@ -10,7 +10,7 @@ use chrono::{DateTime, Utc};
 use zksync_api_types::v02::token::ApiNFT;
 use zksync_types::{
     tokens::{TokenMarketVolume, TokenPrice},
-    AccountId, Address, Token, TokenId, H256, NFT,
+    AccountId, Address, Token, TokenId, TokenRiskLevel, H256, NFT,
 };
 use zksync_utils::big_decimal_to_ratio;
 
@@ -168,3 +168,13 @@ impl From<DBMarketVolume> for TokenMarketVolume {
         }
     }
 }
+
+/// Token along with its risk level and the status of the suspension by governance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenWithStatus {
+    pub token: Token,
+    pub risk_level: TokenRiskLevel,
+    pub is_suspended: bool,
+    /// Reason of the suspension, if it's provided by governance.
+    pub suspension_reason: Option<String>,
+}
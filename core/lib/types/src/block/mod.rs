@@ -500,6 +500,23 @@ impl Block {
     }
 }
 
+/// Computes the positions of the operations within the block public data as
+/// `(chunk offset, chunk size)` pairs, parallel to the provided operations.
+///
+/// Operations must be in the order of their execution. Failed transactions don't
+/// occupy any chunks, so they are reported with the zero size at the current offset.
+pub fn compute_chunk_offsets(ops: &[ExecutedOperations]) -> Vec<(u32, u32)> {
+    let mut offset = 0;
+    ops.iter()
+        .map(|op| {
+            let size = op.get_executed_op().map_or(0, ZkSyncOp::chunks) as u32;
+            let position = (offset, size);
+            offset += size;
+            position
+        })
+        .collect()
+}
+
 /// Gets smallest block size given the list of supported chunk sizes.
 pub fn smallest_block_size_for_chunks(
     chunks_used: usize,
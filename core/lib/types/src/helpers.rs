@@ -1,8 +1,9 @@
 use num::{BigUint, FromPrimitive};
+use thiserror::Error;
 use zksync_crypto::params;
 use zksync_crypto::primitives::FloatConversions;
 
-use crate::{Account, AccountMap, AccountUpdates};
+use crate::{Account, AccountMap, AccountUpdates, Address};
 
 /// Given the account map, applies a sequence of updates to the state.
 pub fn apply_updates(accounts: &mut AccountMap, updates: AccountUpdates) {
@@ -131,6 +132,54 @@ pub fn closest_greater_or_eq_packable_token_amount(amount: &BigUint) -> BigUint
     unpack_token_amount(&fee_packed).expect("token amount repacking")
 }
 
+/// Formats the address in the mixed-case checksum encoding defined in EIP-55,
+/// e.g. `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`.
+pub fn to_checksum_address(address: &Address) -> String {
+    let address_hex = hex::encode(address.as_bytes());
+    let hash = tiny_keccak::keccak256(address_hex.as_bytes());
+
+    let checksummed: String = address_hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            // Letter is uppercased if the corresponding nibble of the hash is at least 8.
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddressParseError {
+    #[error("Address should be a 20 bytes long hex string")]
+    InvalidFormat,
+    #[error("Address doesn't match its EIP-55 checksum")]
+    InvalidChecksum,
+}
+
+/// Parses the hex address with the optional `0x` prefix.
+///
+/// Addresses in the single case are accepted as is, while the mixed-case ones
+/// are treated as the EIP-55 encoded ones and must have the correct checksum.
+pub fn parse_checksum_address(s: &str) -> Result<Address, AddressParseError> {
+    let address_hex = s.strip_prefix("0x").unwrap_or(s);
+    let mut bytes = [0_u8; 20];
+    hex::decode_to_slice(address_hex, &mut bytes).map_err(|_| AddressParseError::InvalidFormat)?;
+    let address = Address::from(bytes);
+
+    let is_mixed_case = address_hex.chars().any(|c| c.is_ascii_lowercase())
+        && address_hex.chars().any(|c| c.is_ascii_uppercase());
+    if is_mixed_case && to_checksum_address(&address)[2..] != *address_hex {
+        return Err(AddressParseError::InvalidChecksum);
+    }
+    Ok(address)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -223,6 +272,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn checksum_address() {
+        // Test vectors from the EIP-55 specification.
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for expected in &addresses {
+            let address: Address = expected[2..].to_lowercase().parse().unwrap();
+            assert_eq!(to_checksum_address(&address), *expected);
+        }
+        assert_eq!(
+            to_checksum_address(&Address::zero()),
+            "0x0000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn checksum_address_parsing() {
+        // Every valid representation of the same bytes should be parsed into the same address.
+        for _ in 0..100 {
+            let address = Address::random();
+            let checksummed = to_checksum_address(&address);
+            let representations = [
+                checksummed.clone(),
+                checksummed[2..].to_string(),
+                checksummed.to_lowercase(),
+                format!("0x{}", checksummed[2..].to_uppercase()),
+                checksummed[2..].to_uppercase(),
+            ];
+            for representation in &representations {
+                assert_eq!(parse_checksum_address(representation), Ok(address));
+            }
+        }
+
+        // Mixed-case address with the case of a single letter changed.
+        assert_eq!(
+            parse_checksum_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(AddressParseError::InvalidChecksum)
+        );
+        assert_eq!(
+            parse_checksum_address("0x1234"),
+            Err(AddressParseError::InvalidFormat)
+        );
+        assert_eq!(
+            parse_checksum_address("0xzz00000000000000000000000000000000000000"),
+            Err(AddressParseError::InvalidFormat)
+        );
+    }
+
     #[test]
     fn token_like_serialization() {
         #[derive(Debug, Serialize, Deserialize, PartialEq)]
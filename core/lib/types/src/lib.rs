@@ -68,7 +68,8 @@ pub use self::operations::{
 pub use self::priority_ops::{Deposit, FullExit, PriorityOp, ZkSyncPriorityOp};
 pub use self::register_factory::RegisterNFTFactoryEvent;
 pub use self::tokens::{
-    NewTokenEvent, Token, TokenInfo, TokenKind, TokenLike, TokenPrice, TxFeeTypes, NFT,
+    NewTokenEvent, Token, TokenInfo, TokenKind, TokenLike, TokenPrice, TokenRiskLevel, TxFeeTypes,
+    NFT,
 };
 pub use self::tx::{
     ForcedExit, MintNFT, Order, SignedZkSyncTx, Swap, Transfer, Withdraw, WithdrawNFT, ZkSyncTx,
@@ -3,7 +3,8 @@ use zksync_crypto::ff::Field;
 use zksync_crypto::Fr;
 
 use super::utils::*;
-use crate::block::Block;
+use crate::block::{compute_chunk_offsets, Block};
+use crate::operations::{ChangePubKeyOp, FullExitOp, WithdrawOp};
 
 /// Checks that we cannot create a block with invalid block sizes provided.
 #[test]
@@ -84,6 +85,29 @@ fn test_get_eth_public_data() {
     assert!(block.get_eth_public_data().iter().all(|&i| i == 0));
 }
 
+/// Checks that the chunk offsets of the operations follow their order in the block.
+#[test]
+fn test_compute_chunk_offsets() {
+    let operations = vec![
+        create_change_pubkey_tx(),
+        create_full_exit_op(),
+        create_withdraw_tx(),
+    ];
+
+    let change_pubkey_chunks = ChangePubKeyOp::CHUNKS as u32;
+    let full_exit_chunks = FullExitOp::CHUNKS as u32;
+    let withdraw_chunks = WithdrawOp::CHUNKS as u32;
+    assert_eq!(
+        compute_chunk_offsets(&operations),
+        vec![
+            (0, change_pubkey_chunks),
+            (change_pubkey_chunks, full_exit_chunks),
+            (change_pubkey_chunks + full_exit_chunks, withdraw_chunks),
+        ]
+    );
+    assert!(compute_chunk_offsets(&[]).is_empty());
+}
+
 #[test]
 fn test_get_eth_witness_data() {
     let operations = vec![
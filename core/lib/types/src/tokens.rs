@@ -160,6 +160,54 @@ impl Token {
     }
 }
 
+/// Risk level assigned to the token by the server operator.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenRiskLevel {
+    Low,
+    Medium,
+    High,
+    /// Risk level of the token is not configured.
+    Unknown,
+}
+
+impl Default for TokenRiskLevel {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl TokenRiskLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for TokenRiskLevel {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Ok(match string {
+            "low" => Self::Low,
+            "medium" => Self::Medium,
+            "high" => Self::High,
+            "unknown" => Self::Unknown,
+            another => return Err(another.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for TokenRiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// ERC-20 standard token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenInfo {
@@ -6,10 +6,13 @@ export type Interface = {
     outstanding_txs: number;
     mempool_size: number;
     core_status: null | CoreStatus;
+    monitored_eth_block: number | null;
+    avg_proof_time_ms: number | null;
 };
 
 export type CoreStatus = {
     main_database_available: boolean;
     replica_database_available: boolean;
     web3_available: boolean;
+    monitored_eth_block?: number | null;
 };